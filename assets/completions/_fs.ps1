@@ -36,6 +36,12 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'show all')
             [CompletionResult]::new('-F', '-F ', [CompletionResultType]::ParameterName, 'only show directories')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'show only files')
+            [CompletionResult]::new('--size', '--size', [CompletionResultType]::ParameterName, 'only show files of a size: +10M is at least, -1k at most')
+            [CompletionResult]::new('--changed-within', '--changed-within', [CompletionResultType]::ParameterName, 'only show entries modified within a duration (2d, 1h 30min)')
+            [CompletionResult]::new('--older-than', '--older-than', [CompletionResultType]::ParameterName, 'only show entries modified before a duration ago (1y, 2w)')
+            [CompletionResult]::new('--perm', '--perm', [CompletionResultType]::ParameterName, 'only show entries the current user can access with r, w and/or x')
+            [CompletionResult]::new('--owner', '--owner', [CompletionResultType]::ParameterName, 'only show entries owned by a user (me, a name or a uid)')
+            [CompletionResult]::new('--git', '--git', [CompletionResultType]::ParameterName, 'only show entries with a git status')
             [CompletionResult]::new('-t', '-t', [CompletionResultType]::ParameterName, 'restrict search to certain file types and extensions (`:t types` to list)')
             [CompletionResult]::new('--types', '--types', [CompletionResultType]::ParameterName, 'restrict search to certain file types and extensions (`:t types` to list)')
             [CompletionResult]::new('--transform', '--transform', [CompletionResultType]::ParameterName, 'Lua transform (path, tail) -> (path, display, tail). Missing display/tail keep the current values; a missing path omits the entry. Accepts a file when prefixed with @')
@@ -61,6 +67,9 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new(':c', ':c', [CompletionResultType]::ParameterValue, 'Browse a piped listing or a command''s output')
             [CompletionResult]::new(':rg', ':rg', [CompletionResultType]::ParameterValue, 'Full text search')
             [CompletionResult]::new(':', ':', [CompletionResultType]::ParameterValue, 'Full text search')
+            [CompletionResult]::new(':saved', ':saved', [CompletionResultType]::ParameterValue, 'Run a saved search')
+            [CompletionResult]::new(':s', ':s', [CompletionResultType]::ParameterValue, 'Run a saved search')
+            [CompletionResult]::new(':dupes', ':dupes', [CompletionResultType]::ParameterValue, 'Find duplicate files')
             [CompletionResult]::new(':tool', ':tool', [CompletionResultType]::ParameterValue, 'Plugins and utilities')
             [CompletionResult]::new(':t', ':t', [CompletionResultType]::ParameterValue, 'Plugins and utilities')
             [CompletionResult]::new(':info', ':info', [CompletionResultType]::ParameterValue, 'Stats and database records')
@@ -108,6 +117,8 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('--sort', '--sort', [CompletionResultType]::ParameterName, 'history sort order')
             [CompletionResult]::new('-l', '-l', [CompletionResultType]::ParameterName, 'l')
             [CompletionResult]::new('--list', '--list', [CompletionResultType]::ParameterName, 'list')
+            [CompletionResult]::new('--on', '--on', [CompletionResultType]::ParameterName, 'only show entries visited on DATE (today, yesterday, a weekday, <N>d or YYYY-MM-DD)')
+            [CompletionResult]::new('--since', '--since', [CompletionResultType]::ParameterName, 'only show entries visited since DATE')
             [CompletionResult]::new('--query', '--query', [CompletionResultType]::ParameterName, 'initial query')
             [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
             [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
@@ -128,6 +139,8 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('--sort', '--sort', [CompletionResultType]::ParameterName, 'history sort order')
             [CompletionResult]::new('-l', '-l', [CompletionResultType]::ParameterName, 'l')
             [CompletionResult]::new('--list', '--list', [CompletionResultType]::ParameterName, 'list')
+            [CompletionResult]::new('--on', '--on', [CompletionResultType]::ParameterName, 'only show entries visited on DATE (today, yesterday, a weekday, <N>d or YYYY-MM-DD)')
+            [CompletionResult]::new('--since', '--since', [CompletionResultType]::ParameterName, 'only show entries visited since DATE')
             [CompletionResult]::new('--initial-input', '--initial-input', [CompletionResultType]::ParameterName, 'initial-input')
             [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
             [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
@@ -152,6 +165,12 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'show all')
             [CompletionResult]::new('-F', '-F ', [CompletionResultType]::ParameterName, 'only show directories')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'show only files')
+            [CompletionResult]::new('--size', '--size', [CompletionResultType]::ParameterName, 'only show files of a size: +10M is at least, -1k at most')
+            [CompletionResult]::new('--changed-within', '--changed-within', [CompletionResultType]::ParameterName, 'only show entries modified within a duration (2d, 1h 30min)')
+            [CompletionResult]::new('--older-than', '--older-than', [CompletionResultType]::ParameterName, 'only show entries modified before a duration ago (1y, 2w)')
+            [CompletionResult]::new('--perm', '--perm', [CompletionResultType]::ParameterName, 'only show entries the current user can access with r, w and/or x')
+            [CompletionResult]::new('--owner', '--owner', [CompletionResultType]::ParameterName, 'only show entries owned by a user (me, a name or a uid)')
+            [CompletionResult]::new('--git', '--git', [CompletionResultType]::ParameterName, 'only show entries with a git status')
             [CompletionResult]::new('-t', '-t', [CompletionResultType]::ParameterName, 'restrict search to certain file types and extensions (`:t types` to list)')
             [CompletionResult]::new('--types', '--types', [CompletionResultType]::ParameterName, 'restrict search to certain file types and extensions (`:t types` to list)')
             [CompletionResult]::new('--transform', '--transform', [CompletionResultType]::ParameterName, 'Lua transform (path, tail) -> (path, display, tail). Missing display/tail keep the current values; a missing path omits the entry. Accepts a file when prefixed with @')
@@ -182,6 +201,12 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'show all')
             [CompletionResult]::new('-F', '-F ', [CompletionResultType]::ParameterName, 'only show directories')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'show only files')
+            [CompletionResult]::new('--size', '--size', [CompletionResultType]::ParameterName, 'only show files of a size: +10M is at least, -1k at most')
+            [CompletionResult]::new('--changed-within', '--changed-within', [CompletionResultType]::ParameterName, 'only show entries modified within a duration (2d, 1h 30min)')
+            [CompletionResult]::new('--older-than', '--older-than', [CompletionResultType]::ParameterName, 'only show entries modified before a duration ago (1y, 2w)')
+            [CompletionResult]::new('--perm', '--perm', [CompletionResultType]::ParameterName, 'only show entries the current user can access with r, w and/or x')
+            [CompletionResult]::new('--owner', '--owner', [CompletionResultType]::ParameterName, 'only show entries owned by a user (me, a name or a uid)')
+            [CompletionResult]::new('--git', '--git', [CompletionResultType]::ParameterName, 'only show entries with a git status')
             [CompletionResult]::new('-t', '-t', [CompletionResultType]::ParameterName, 'restrict search to certain file types and extensions (`:t types` to list)')
             [CompletionResult]::new('--types', '--types', [CompletionResultType]::ParameterName, 'restrict search to certain file types and extensions (`:t types` to list)')
             [CompletionResult]::new('--transform', '--transform', [CompletionResultType]::ParameterName, 'Lua transform (path, tail) -> (path, display, tail). Missing display/tail keep the current values; a missing path omits the entry. Accepts a file when prefixed with @')
@@ -211,6 +236,12 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'show all')
             [CompletionResult]::new('-F', '-F ', [CompletionResultType]::ParameterName, 'only show directories')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'show only files')
+            [CompletionResult]::new('--size', '--size', [CompletionResultType]::ParameterName, 'only show files of a size: +10M is at least, -1k at most')
+            [CompletionResult]::new('--changed-within', '--changed-within', [CompletionResultType]::ParameterName, 'only show entries modified within a duration (2d, 1h 30min)')
+            [CompletionResult]::new('--older-than', '--older-than', [CompletionResultType]::ParameterName, 'only show entries modified before a duration ago (1y, 2w)')
+            [CompletionResult]::new('--perm', '--perm', [CompletionResultType]::ParameterName, 'only show entries the current user can access with r, w and/or x')
+            [CompletionResult]::new('--owner', '--owner', [CompletionResultType]::ParameterName, 'only show entries owned by a user (me, a name or a uid)')
+            [CompletionResult]::new('--git', '--git', [CompletionResultType]::ParameterName, 'only show entries with a git status')
             [CompletionResult]::new('--sort', '--sort', [CompletionResultType]::ParameterName, 'sort')
             [CompletionResult]::new('--transform', '--transform', [CompletionResultType]::ParameterName, 'Lua transform (path, tail) -> (path, display, tail)')
             [CompletionResult]::new('--tail-sep', '--tail-sep', [CompletionResultType]::ParameterName, 'Delimiter used to split off the input into a (path, tail) pair')
@@ -237,6 +268,12 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'show all')
             [CompletionResult]::new('-F', '-F ', [CompletionResultType]::ParameterName, 'only show directories')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'show only files')
+            [CompletionResult]::new('--size', '--size', [CompletionResultType]::ParameterName, 'only show files of a size: +10M is at least, -1k at most')
+            [CompletionResult]::new('--changed-within', '--changed-within', [CompletionResultType]::ParameterName, 'only show entries modified within a duration (2d, 1h 30min)')
+            [CompletionResult]::new('--older-than', '--older-than', [CompletionResultType]::ParameterName, 'only show entries modified before a duration ago (1y, 2w)')
+            [CompletionResult]::new('--perm', '--perm', [CompletionResultType]::ParameterName, 'only show entries the current user can access with r, w and/or x')
+            [CompletionResult]::new('--owner', '--owner', [CompletionResultType]::ParameterName, 'only show entries owned by a user (me, a name or a uid)')
+            [CompletionResult]::new('--git', '--git', [CompletionResultType]::ParameterName, 'only show entries with a git status')
             [CompletionResult]::new('--sort', '--sort', [CompletionResultType]::ParameterName, 'sort')
             [CompletionResult]::new('--transform', '--transform', [CompletionResultType]::ParameterName, 'Lua transform (path, tail) -> (path, display, tail)')
             [CompletionResult]::new('--tail-sep', '--tail-sep', [CompletionResultType]::ParameterName, 'Delimiter used to split off the input into a (path, tail) pair')
@@ -263,6 +300,12 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'show all')
             [CompletionResult]::new('-F', '-F ', [CompletionResultType]::ParameterName, 'only show directories')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'show only files')
+            [CompletionResult]::new('--size', '--size', [CompletionResultType]::ParameterName, 'only show files of a size: +10M is at least, -1k at most')
+            [CompletionResult]::new('--changed-within', '--changed-within', [CompletionResultType]::ParameterName, 'only show entries modified within a duration (2d, 1h 30min)')
+            [CompletionResult]::new('--older-than', '--older-than', [CompletionResultType]::ParameterName, 'only show entries modified before a duration ago (1y, 2w)')
+            [CompletionResult]::new('--perm', '--perm', [CompletionResultType]::ParameterName, 'only show entries the current user can access with r, w and/or x')
+            [CompletionResult]::new('--owner', '--owner', [CompletionResultType]::ParameterName, 'only show entries owned by a user (me, a name or a uid)')
+            [CompletionResult]::new('--git', '--git', [CompletionResultType]::ParameterName, 'only show entries with a git status')
             [CompletionResult]::new('--sort', '--sort', [CompletionResultType]::ParameterName, 'sort')
             [CompletionResult]::new('-p', '-p', [CompletionResultType]::ParameterName, 'Files or directories to search in')
             [CompletionResult]::new('--path', '--path', [CompletionResultType]::ParameterName, 'Files or directories to search in')
@@ -310,6 +353,12 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'show all')
             [CompletionResult]::new('-F', '-F ', [CompletionResultType]::ParameterName, 'only show directories')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'show only files')
+            [CompletionResult]::new('--size', '--size', [CompletionResultType]::ParameterName, 'only show files of a size: +10M is at least, -1k at most')
+            [CompletionResult]::new('--changed-within', '--changed-within', [CompletionResultType]::ParameterName, 'only show entries modified within a duration (2d, 1h 30min)')
+            [CompletionResult]::new('--older-than', '--older-than', [CompletionResultType]::ParameterName, 'only show entries modified before a duration ago (1y, 2w)')
+            [CompletionResult]::new('--perm', '--perm', [CompletionResultType]::ParameterName, 'only show entries the current user can access with r, w and/or x')
+            [CompletionResult]::new('--owner', '--owner', [CompletionResultType]::ParameterName, 'only show entries owned by a user (me, a name or a uid)')
+            [CompletionResult]::new('--git', '--git', [CompletionResultType]::ParameterName, 'only show entries with a git status')
             [CompletionResult]::new('--sort', '--sort', [CompletionResultType]::ParameterName, 'sort')
             [CompletionResult]::new('-p', '-p', [CompletionResultType]::ParameterName, 'Files or directories to search in')
             [CompletionResult]::new('--path', '--path', [CompletionResultType]::ParameterName, 'Files or directories to search in')
@@ -351,6 +400,70 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('--alt-accept', '--alt-accept', [CompletionResultType]::ParameterName, 'alt-accept')
             break
         }
+        'fs;:saved' {
+            [CompletionResult]::new('--query', '--query', [CompletionResultType]::ParameterName, 'initial query, replacing the saved one')
+            [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
+            [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
+            [CompletionResult]::new('--mm-config', '--mm-config', [CompletionResultType]::ParameterName, 'matchmaker config path')
+            [CompletionResult]::new('--style', '--style', [CompletionResultType]::ParameterName, 'style')
+            [CompletionResult]::new('--fullscreen', '--fullscreen', [CompletionResultType]::ParameterName, 'fullscreen')
+            [CompletionResult]::new('--lock-prompt', '--lock-prompt', [CompletionResultType]::ParameterName, 'See `interface.prompt_locking`')
+            [CompletionResult]::new('--output-sep', '--output-sep', [CompletionResultType]::ParameterName, 'Separator printed after each result')
+            [CompletionResult]::new('--format', '--format', [CompletionResultType]::ParameterName, 'Output template for printed results')
+            [CompletionResult]::new('--opener', '--opener', [CompletionResultType]::ParameterName, 'Program used to open files on accept')
+            [CompletionResult]::new('--list', '--list', [CompletionResultType]::ParameterName, 'list')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'help')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Reduce the verbosity level')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Increase the verbosity level')
+            [CompletionResult]::new('--alt-accept', '--alt-accept', [CompletionResultType]::ParameterName, 'alt-accept')
+            break
+        }
+        'fs;:s' {
+            [CompletionResult]::new('--query', '--query', [CompletionResultType]::ParameterName, 'initial query, replacing the saved one')
+            [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
+            [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
+            [CompletionResult]::new('--mm-config', '--mm-config', [CompletionResultType]::ParameterName, 'matchmaker config path')
+            [CompletionResult]::new('--style', '--style', [CompletionResultType]::ParameterName, 'style')
+            [CompletionResult]::new('--fullscreen', '--fullscreen', [CompletionResultType]::ParameterName, 'fullscreen')
+            [CompletionResult]::new('--lock-prompt', '--lock-prompt', [CompletionResultType]::ParameterName, 'See `interface.prompt_locking`')
+            [CompletionResult]::new('--output-sep', '--output-sep', [CompletionResultType]::ParameterName, 'Separator printed after each result')
+            [CompletionResult]::new('--format', '--format', [CompletionResultType]::ParameterName, 'Output template for printed results')
+            [CompletionResult]::new('--opener', '--opener', [CompletionResultType]::ParameterName, 'Program used to open files on accept')
+            [CompletionResult]::new('--list', '--list', [CompletionResultType]::ParameterName, 'list')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'help')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Reduce the verbosity level')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Increase the verbosity level')
+            [CompletionResult]::new('--alt-accept', '--alt-accept', [CompletionResultType]::ParameterName, 'alt-accept')
+            break
+        }
+        'fs;:dupes' {
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'show hidden files and folders')
+            [CompletionResult]::new('-I', '-I ', [CompletionResultType]::ParameterName, 'HIDE ignored files')
+            [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'show all')
+            [CompletionResult]::new('-F', '-F ', [CompletionResultType]::ParameterName, 'only show directories')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'show only files')
+            [CompletionResult]::new('--size', '--size', [CompletionResultType]::ParameterName, 'only show files of a size: +10M is at least, -1k at most')
+            [CompletionResult]::new('--changed-within', '--changed-within', [CompletionResultType]::ParameterName, 'only show entries modified within a duration (2d, 1h 30min)')
+            [CompletionResult]::new('--older-than', '--older-than', [CompletionResultType]::ParameterName, 'only show entries modified before a duration ago (1y, 2w)')
+            [CompletionResult]::new('--perm', '--perm', [CompletionResultType]::ParameterName, 'only show entries the current user can access with r, w and/or x')
+            [CompletionResult]::new('--owner', '--owner', [CompletionResultType]::ParameterName, 'only show entries owned by a user (me, a name or a uid)')
+            [CompletionResult]::new('--git', '--git', [CompletionResultType]::ParameterName, 'only show entries with a git status')
+            [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
+            [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
+            [CompletionResult]::new('--mm-config', '--mm-config', [CompletionResultType]::ParameterName, 'matchmaker config path')
+            [CompletionResult]::new('--style', '--style', [CompletionResultType]::ParameterName, 'style')
+            [CompletionResult]::new('--fullscreen', '--fullscreen', [CompletionResultType]::ParameterName, 'fullscreen')
+            [CompletionResult]::new('--lock-prompt', '--lock-prompt', [CompletionResultType]::ParameterName, 'See `interface.prompt_locking`')
+            [CompletionResult]::new('--output-sep', '--output-sep', [CompletionResultType]::ParameterName, 'Separator printed after each result')
+            [CompletionResult]::new('--format', '--format', [CompletionResultType]::ParameterName, 'Output template for printed results')
+            [CompletionResult]::new('--opener', '--opener', [CompletionResultType]::ParameterName, 'Program used to open files on accept')
+            [CompletionResult]::new('--list', '--list', [CompletionResultType]::ParameterName, 'Print the groups, separated by an empty line')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'help')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Reduce the verbosity level')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Increase the verbosity level')
+            [CompletionResult]::new('--alt-accept', '--alt-accept', [CompletionResultType]::ParameterName, 'alt-accept')
+            break
+        }
         'fs;:tool' {
             [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
             [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
@@ -375,6 +488,8 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('showbinds', 'showbinds', [CompletionResultType]::ParameterValue, 'Show binds')
             [CompletionResult]::new('types', 'types', [CompletionResultType]::ParameterValue, 'List mappings supported by the --type parameter')
             [CompletionResult]::new('diskspace', 'diskspace', [CompletionResultType]::ParameterValue, 'Disk usage: compute directory sizes concurrently and print them')
+            [CompletionResult]::new('db', 'db', [CompletionResultType]::ParameterValue, 'Database statistics and maintenance')
+            [CompletionResult]::new('stash', 'stash', [CompletionResultType]::ParameterValue, 'Export, import and list stashes')
             [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Validate configuration and scripts; exits non-zero on errors')
             [CompletionResult]::new('showerror', 'showerror', [CompletionResultType]::ParameterValue, 'Display an error message and wait for keypress')
             break
@@ -403,6 +518,8 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('showbinds', 'showbinds', [CompletionResultType]::ParameterValue, 'Show binds')
             [CompletionResult]::new('types', 'types', [CompletionResultType]::ParameterValue, 'List mappings supported by the --type parameter')
             [CompletionResult]::new('diskspace', 'diskspace', [CompletionResultType]::ParameterValue, 'Disk usage: compute directory sizes concurrently and print them')
+            [CompletionResult]::new('db', 'db', [CompletionResultType]::ParameterValue, 'Database statistics and maintenance')
+            [CompletionResult]::new('stash', 'stash', [CompletionResultType]::ParameterValue, 'Export, import and list stashes')
             [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Validate configuration and scripts; exits non-zero on errors')
             [CompletionResult]::new('showerror', 'showerror', [CompletionResultType]::ParameterValue, 'Display an error message and wait for keypress')
             break
@@ -707,6 +824,66 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('--alt-accept', '--alt-accept', [CompletionResultType]::ParameterName, 'alt-accept')
             break
         }
+        'fs;:tool;db' {
+            [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
+            [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
+            [CompletionResult]::new('--mm-config', '--mm-config', [CompletionResultType]::ParameterName, 'matchmaker config path')
+            [CompletionResult]::new('--style', '--style', [CompletionResultType]::ParameterName, 'style')
+            [CompletionResult]::new('--fullscreen', '--fullscreen', [CompletionResultType]::ParameterName, 'fullscreen')
+            [CompletionResult]::new('--lock-prompt', '--lock-prompt', [CompletionResultType]::ParameterName, 'See `interface.prompt_locking`')
+            [CompletionResult]::new('--output-sep', '--output-sep', [CompletionResultType]::ParameterName, 'Separator printed after each result')
+            [CompletionResult]::new('--format', '--format', [CompletionResultType]::ParameterName, 'Output template for printed results')
+            [CompletionResult]::new('--opener', '--opener', [CompletionResultType]::ParameterName, 'Program used to open files on accept')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Reduce the verbosity level')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Increase the verbosity level')
+            [CompletionResult]::new('--alt-accept', '--alt-accept', [CompletionResultType]::ParameterName, 'alt-accept')
+            break
+        }
+        'fs;:t;db' {
+            [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
+            [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
+            [CompletionResult]::new('--mm-config', '--mm-config', [CompletionResultType]::ParameterName, 'matchmaker config path')
+            [CompletionResult]::new('--style', '--style', [CompletionResultType]::ParameterName, 'style')
+            [CompletionResult]::new('--fullscreen', '--fullscreen', [CompletionResultType]::ParameterName, 'fullscreen')
+            [CompletionResult]::new('--lock-prompt', '--lock-prompt', [CompletionResultType]::ParameterName, 'See `interface.prompt_locking`')
+            [CompletionResult]::new('--output-sep', '--output-sep', [CompletionResultType]::ParameterName, 'Separator printed after each result')
+            [CompletionResult]::new('--format', '--format', [CompletionResultType]::ParameterName, 'Output template for printed results')
+            [CompletionResult]::new('--opener', '--opener', [CompletionResultType]::ParameterName, 'Program used to open files on accept')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Reduce the verbosity level')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Increase the verbosity level')
+            [CompletionResult]::new('--alt-accept', '--alt-accept', [CompletionResultType]::ParameterName, 'alt-accept')
+            break
+        }
+        'fs;:tool;stash' {
+            [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
+            [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
+            [CompletionResult]::new('--mm-config', '--mm-config', [CompletionResultType]::ParameterName, 'matchmaker config path')
+            [CompletionResult]::new('--style', '--style', [CompletionResultType]::ParameterName, 'style')
+            [CompletionResult]::new('--fullscreen', '--fullscreen', [CompletionResultType]::ParameterName, 'fullscreen')
+            [CompletionResult]::new('--lock-prompt', '--lock-prompt', [CompletionResultType]::ParameterName, 'See `interface.prompt_locking`')
+            [CompletionResult]::new('--output-sep', '--output-sep', [CompletionResultType]::ParameterName, 'Separator printed after each result')
+            [CompletionResult]::new('--format', '--format', [CompletionResultType]::ParameterName, 'Output template for printed results')
+            [CompletionResult]::new('--opener', '--opener', [CompletionResultType]::ParameterName, 'Program used to open files on accept')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Reduce the verbosity level')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Increase the verbosity level')
+            [CompletionResult]::new('--alt-accept', '--alt-accept', [CompletionResultType]::ParameterName, 'alt-accept')
+            break
+        }
+        'fs;:t;stash' {
+            [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
+            [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
+            [CompletionResult]::new('--mm-config', '--mm-config', [CompletionResultType]::ParameterName, 'matchmaker config path')
+            [CompletionResult]::new('--style', '--style', [CompletionResultType]::ParameterName, 'style')
+            [CompletionResult]::new('--fullscreen', '--fullscreen', [CompletionResultType]::ParameterName, 'fullscreen')
+            [CompletionResult]::new('--lock-prompt', '--lock-prompt', [CompletionResultType]::ParameterName, 'See `interface.prompt_locking`')
+            [CompletionResult]::new('--output-sep', '--output-sep', [CompletionResultType]::ParameterName, 'Separator printed after each result')
+            [CompletionResult]::new('--format', '--format', [CompletionResultType]::ParameterName, 'Output template for printed results')
+            [CompletionResult]::new('--opener', '--opener', [CompletionResultType]::ParameterName, 'Program used to open files on accept')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Reduce the verbosity level')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Increase the verbosity level')
+            [CompletionResult]::new('--alt-accept', '--alt-accept', [CompletionResultType]::ParameterName, 'alt-accept')
+            break
+        }
         'fs;:tool;check' {
             [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
             [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
@@ -771,6 +948,8 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('--sort', '--sort', [CompletionResultType]::ParameterName, 'history sort order')
             [CompletionResult]::new('-l', '-l', [CompletionResultType]::ParameterName, 'maximum history entries to display')
             [CompletionResult]::new('--limit', '--limit', [CompletionResultType]::ParameterName, 'maximum history entries to display')
+            [CompletionResult]::new('--since', '--since', [CompletionResultType]::ParameterName, 'print the visits since DATE (today, yesterday, a weekday, <N>d or YYYY-MM-DD)')
+            [CompletionResult]::new('--until', '--until', [CompletionResultType]::ParameterName, 'print the visits up to and including DATE')
             [CompletionResult]::new('--override', '--override', [CompletionResultType]::ParameterName, 'config override')
             [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'config path')
             [CompletionResult]::new('--mm-config', '--mm-config', [CompletionResultType]::ParameterName, 'matchmaker config path')
@@ -782,6 +961,7 @@ Register-ArgumentCompleter -Native -CommandName 'fs' -ScriptBlock {
             [CompletionResult]::new('--opener', '--opener', [CompletionResultType]::ParameterName, 'Program used to open files on accept')
             [CompletionResult]::new('-m', '-m', [CompletionResultType]::ParameterName, 'Don''t print decorations')
             [CompletionResult]::new('--minimal', '--minimal', [CompletionResultType]::ParameterName, 'Don''t print decorations')
+            [CompletionResult]::new('--aliases', '--aliases', [CompletionResultType]::ParameterName, 'print aliased directories and files (just the aliases with --minimal)')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'help')
             [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Reduce the verbosity level')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Increase the verbosity level')
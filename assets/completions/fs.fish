@@ -1,6 +1,6 @@
 # Print an optspec for argparse to handle cmd's options that are independent of any subcommand.
 function __fish_fs_global_optspecs
-    string join \n q v override= config= mm-config= dump-config style= fullscreen= lock-prompt= alt-accept output-sep= format= opener= sort= h= I= a= F= f= A/no-all cd t/types= transform= reset-visibility list help V/version
+    string join \n q v override= config= mm-config= dump-config style= fullscreen= lock-prompt= alt-accept output-sep= format= opener= sort= h= I= a= F= f= size= changed-within= older-than= perm= owner= git= A/no-all cd t/types= transform= reset-visibility list help V/version
 end
 
 function __fish_fs_needs_command
@@ -55,6 +55,17 @@ complete -c fs -n "__fish_fs_needs_command" -s F -d 'only show directories' -r -
 false\t''"
 complete -c fs -n "__fish_fs_needs_command" -s f -d 'show only files' -r -f -a "true\t''
 false\t''"
+complete -c fs -n "__fish_fs_needs_command" -l size -d 'only show files of a size: +10M is at least, -1k at most' -r
+complete -c fs -n "__fish_fs_needs_command" -l changed-within -d 'only show entries modified within a duration (2d, 1h 30min)' -r
+complete -c fs -n "__fish_fs_needs_command" -l older-than -d 'only show entries modified before a duration ago (1y, 2w)' -r
+complete -c fs -n "__fish_fs_needs_command" -l perm -d 'only show entries the current user can access with r, w and/or x' -r
+complete -c fs -n "__fish_fs_needs_command" -l owner -d 'only show entries owned by a user (me, a name or a uid)' -r
+complete -c fs -n "__fish_fs_needs_command" -l git -d 'only show entries with a git status' -r -f -a "changed\t'Modified, staged, untracked or conflicted'
+modified\t''
+staged\t''
+untracked\t''
+conflicted\t''
+ignored\t''"
 complete -c fs -n "__fish_fs_needs_command" -s t -l types -d 'restrict search to certain file types and extensions (`:t types` to list)' -r
 complete -c fs -n "__fish_fs_needs_command" -l transform -d 'Lua transform (path, tail) -> (path, display, tail). Missing display/tail keep the current values; a missing path omits the entry. Accepts a file when prefixed with @' -r
 complete -c fs -n "__fish_fs_needs_command" -s q -d 'Reduce the verbosity level'
@@ -77,6 +88,9 @@ complete -c fs -n "__fish_fs_needs_command" -a ":custom" -d 'Browse a piped list
 complete -c fs -n "__fish_fs_needs_command" -a ":c" -d 'Browse a piped listing or a command\'s output'
 complete -c fs -n "__fish_fs_needs_command" -a ":rg" -d 'Full text search'
 complete -c fs -n "__fish_fs_needs_command" -a ":" -d 'Full text search'
+complete -c fs -n "__fish_fs_needs_command" -a ":saved" -d 'Run a saved search'
+complete -c fs -n "__fish_fs_needs_command" -a ":s" -d 'Run a saved search'
+complete -c fs -n "__fish_fs_needs_command" -a ":dupes" -d 'Find duplicate files'
 complete -c fs -n "__fish_fs_needs_command" -a ":tool" -d 'Plugins and utilities'
 complete -c fs -n "__fish_fs_needs_command" -a ":t" -d 'Plugins and utilities'
 complete -c fs -n "__fish_fs_needs_command" -a ":info" -d 'Stats and database records'
@@ -131,6 +145,8 @@ size\t''
 none\t''"
 complete -c fs -n "__fish_fs_using_subcommand :file" -s l -l list -r -f -a "_\t''
 all\t''"
+complete -c fs -n "__fish_fs_using_subcommand :file" -l on -d 'only show entries visited on DATE (today, yesterday, a weekday, <N>d or YYYY-MM-DD)' -r
+complete -c fs -n "__fish_fs_using_subcommand :file" -l since -d 'only show entries visited since DATE' -r
 complete -c fs -n "__fish_fs_using_subcommand :file" -l query -d 'initial query' -r
 complete -c fs -n "__fish_fs_using_subcommand :file" -l override -d 'config override' -r
 complete -c fs -n "__fish_fs_using_subcommand :file" -l config -d 'config path' -r -F
@@ -159,6 +175,8 @@ size\t''
 none\t''"
 complete -c fs -n "__fish_fs_using_subcommand :dir" -s l -l list -r -f -a "_\t''
 all\t''"
+complete -c fs -n "__fish_fs_using_subcommand :dir" -l on -d 'only show entries visited on DATE (today, yesterday, a weekday, <N>d or YYYY-MM-DD)' -r
+complete -c fs -n "__fish_fs_using_subcommand :dir" -l since -d 'only show entries visited since DATE' -r
 complete -c fs -n "__fish_fs_using_subcommand :dir" -l initial-input -r
 complete -c fs -n "__fish_fs_using_subcommand :dir" -l override -d 'config override' -r
 complete -c fs -n "__fish_fs_using_subcommand :dir" -l config -d 'config path' -r -F
@@ -196,6 +214,17 @@ complete -c fs -n "__fish_fs_using_subcommand :fd" -s F -d 'only show directorie
 false\t''"
 complete -c fs -n "__fish_fs_using_subcommand :fd" -s f -d 'show only files' -r -f -a "true\t''
 false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :fd" -l size -d 'only show files of a size: +10M is at least, -1k at most' -r
+complete -c fs -n "__fish_fs_using_subcommand :fd" -l changed-within -d 'only show entries modified within a duration (2d, 1h 30min)' -r
+complete -c fs -n "__fish_fs_using_subcommand :fd" -l older-than -d 'only show entries modified before a duration ago (1y, 2w)' -r
+complete -c fs -n "__fish_fs_using_subcommand :fd" -l perm -d 'only show entries the current user can access with r, w and/or x' -r
+complete -c fs -n "__fish_fs_using_subcommand :fd" -l owner -d 'only show entries owned by a user (me, a name or a uid)' -r
+complete -c fs -n "__fish_fs_using_subcommand :fd" -l git -d 'only show entries with a git status' -r -f -a "changed\t'Modified, staged, untracked or conflicted'
+modified\t''
+staged\t''
+untracked\t''
+conflicted\t''
+ignored\t''"
 complete -c fs -n "__fish_fs_using_subcommand :fd" -s t -l types -d 'restrict search to certain file types and extensions (`:t types` to list)' -r
 complete -c fs -n "__fish_fs_using_subcommand :fd" -l transform -d 'Lua transform (path, tail) -> (path, display, tail). Missing display/tail keep the current values; a missing path omits the entry. Accepts a file when prefixed with @' -r
 complete -c fs -n "__fish_fs_using_subcommand :fd" -l override -d 'config override' -r
@@ -237,6 +266,17 @@ complete -c fs -n "__fish_fs_using_subcommand ::" -s F -d 'only show directories
 false\t''"
 complete -c fs -n "__fish_fs_using_subcommand ::" -s f -d 'show only files' -r -f -a "true\t''
 false\t''"
+complete -c fs -n "__fish_fs_using_subcommand ::" -l size -d 'only show files of a size: +10M is at least, -1k at most' -r
+complete -c fs -n "__fish_fs_using_subcommand ::" -l changed-within -d 'only show entries modified within a duration (2d, 1h 30min)' -r
+complete -c fs -n "__fish_fs_using_subcommand ::" -l older-than -d 'only show entries modified before a duration ago (1y, 2w)' -r
+complete -c fs -n "__fish_fs_using_subcommand ::" -l perm -d 'only show entries the current user can access with r, w and/or x' -r
+complete -c fs -n "__fish_fs_using_subcommand ::" -l owner -d 'only show entries owned by a user (me, a name or a uid)' -r
+complete -c fs -n "__fish_fs_using_subcommand ::" -l git -d 'only show entries with a git status' -r -f -a "changed\t'Modified, staged, untracked or conflicted'
+modified\t''
+staged\t''
+untracked\t''
+conflicted\t''
+ignored\t''"
 complete -c fs -n "__fish_fs_using_subcommand ::" -s t -l types -d 'restrict search to certain file types and extensions (`:t types` to list)' -r
 complete -c fs -n "__fish_fs_using_subcommand ::" -l transform -d 'Lua transform (path, tail) -> (path, display, tail). Missing display/tail keep the current values; a missing path omits the entry. Accepts a file when prefixed with @' -r
 complete -c fs -n "__fish_fs_using_subcommand ::" -l override -d 'config override' -r
@@ -273,6 +313,17 @@ complete -c fs -n "__fish_fs_using_subcommand :custom" -s F -d 'only show direct
 false\t''"
 complete -c fs -n "__fish_fs_using_subcommand :custom" -s f -d 'show only files' -r -f -a "true\t''
 false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :custom" -l size -d 'only show files of a size: +10M is at least, -1k at most' -r
+complete -c fs -n "__fish_fs_using_subcommand :custom" -l changed-within -d 'only show entries modified within a duration (2d, 1h 30min)' -r
+complete -c fs -n "__fish_fs_using_subcommand :custom" -l older-than -d 'only show entries modified before a duration ago (1y, 2w)' -r
+complete -c fs -n "__fish_fs_using_subcommand :custom" -l perm -d 'only show entries the current user can access with r, w and/or x' -r
+complete -c fs -n "__fish_fs_using_subcommand :custom" -l owner -d 'only show entries owned by a user (me, a name or a uid)' -r
+complete -c fs -n "__fish_fs_using_subcommand :custom" -l git -d 'only show entries with a git status' -r -f -a "changed\t'Modified, staged, untracked or conflicted'
+modified\t''
+staged\t''
+untracked\t''
+conflicted\t''
+ignored\t''"
 complete -c fs -n "__fish_fs_using_subcommand :custom" -l sort -r -f -a "name\t''
 mtime\t''
 atime\t''
@@ -312,6 +363,17 @@ complete -c fs -n "__fish_fs_using_subcommand :c" -s F -d 'only show directories
 false\t''"
 complete -c fs -n "__fish_fs_using_subcommand :c" -s f -d 'show only files' -r -f -a "true\t''
 false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :c" -l size -d 'only show files of a size: +10M is at least, -1k at most' -r
+complete -c fs -n "__fish_fs_using_subcommand :c" -l changed-within -d 'only show entries modified within a duration (2d, 1h 30min)' -r
+complete -c fs -n "__fish_fs_using_subcommand :c" -l older-than -d 'only show entries modified before a duration ago (1y, 2w)' -r
+complete -c fs -n "__fish_fs_using_subcommand :c" -l perm -d 'only show entries the current user can access with r, w and/or x' -r
+complete -c fs -n "__fish_fs_using_subcommand :c" -l owner -d 'only show entries owned by a user (me, a name or a uid)' -r
+complete -c fs -n "__fish_fs_using_subcommand :c" -l git -d 'only show entries with a git status' -r -f -a "changed\t'Modified, staged, untracked or conflicted'
+modified\t''
+staged\t''
+untracked\t''
+conflicted\t''
+ignored\t''"
 complete -c fs -n "__fish_fs_using_subcommand :c" -l sort -r -f -a "name\t''
 mtime\t''
 atime\t''
@@ -351,6 +413,17 @@ complete -c fs -n "__fish_fs_using_subcommand :rg" -s F -d 'only show directorie
 false\t''"
 complete -c fs -n "__fish_fs_using_subcommand :rg" -s f -d 'show only files' -r -f -a "true\t''
 false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :rg" -l size -d 'only show files of a size: +10M is at least, -1k at most' -r
+complete -c fs -n "__fish_fs_using_subcommand :rg" -l changed-within -d 'only show entries modified within a duration (2d, 1h 30min)' -r
+complete -c fs -n "__fish_fs_using_subcommand :rg" -l older-than -d 'only show entries modified before a duration ago (1y, 2w)' -r
+complete -c fs -n "__fish_fs_using_subcommand :rg" -l perm -d 'only show entries the current user can access with r, w and/or x' -r
+complete -c fs -n "__fish_fs_using_subcommand :rg" -l owner -d 'only show entries owned by a user (me, a name or a uid)' -r
+complete -c fs -n "__fish_fs_using_subcommand :rg" -l git -d 'only show entries with a git status' -r -f -a "changed\t'Modified, staged, untracked or conflicted'
+modified\t''
+staged\t''
+untracked\t''
+conflicted\t''
+ignored\t''"
 complete -c fs -n "__fish_fs_using_subcommand :rg" -l sort -r -f -a "name\t''
 mtime\t''
 atime\t''
@@ -404,6 +477,17 @@ complete -c fs -n "__fish_fs_using_subcommand :" -s F -d 'only show directories'
 false\t''"
 complete -c fs -n "__fish_fs_using_subcommand :" -s f -d 'show only files' -r -f -a "true\t''
 false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :" -l size -d 'only show files of a size: +10M is at least, -1k at most' -r
+complete -c fs -n "__fish_fs_using_subcommand :" -l changed-within -d 'only show entries modified within a duration (2d, 1h 30min)' -r
+complete -c fs -n "__fish_fs_using_subcommand :" -l older-than -d 'only show entries modified before a duration ago (1y, 2w)' -r
+complete -c fs -n "__fish_fs_using_subcommand :" -l perm -d 'only show entries the current user can access with r, w and/or x' -r
+complete -c fs -n "__fish_fs_using_subcommand :" -l owner -d 'only show entries owned by a user (me, a name or a uid)' -r
+complete -c fs -n "__fish_fs_using_subcommand :" -l git -d 'only show entries with a git status' -r -f -a "changed\t'Modified, staged, untracked or conflicted'
+modified\t''
+staged\t''
+untracked\t''
+conflicted\t''
+ignored\t''"
 complete -c fs -n "__fish_fs_using_subcommand :" -l sort -r -f -a "name\t''
 mtime\t''
 atime\t''
@@ -447,38 +531,126 @@ complete -c fs -n "__fish_fs_using_subcommand :" -l help
 complete -c fs -n "__fish_fs_using_subcommand :" -s q -d 'Reduce the verbosity level'
 complete -c fs -n "__fish_fs_using_subcommand :" -s v -d 'Increase the verbosity level'
 complete -c fs -n "__fish_fs_using_subcommand :" -l alt-accept
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l override -d 'config override' -r
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l config -d 'config path' -r -F
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l mm-config -d 'matchmaker config path' -r -F
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l style -r -f -a "icons\t''
-icon-colors\t''
-colors\t''
-none\t''
-all\t''
-auto\t''"
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l fullscreen -r -f -a "true\t''
-false\t''"
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l lock-prompt -d 'See `interface.prompt_locking`' -r -f -a "true\t''
-false\t''"
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l output-sep -d 'Separator printed after each result' -r
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l format -d 'Output template for printed results' -r
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l opener -d 'Program used to open files on accept' -r
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l help
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -s q -d 'Reduce the verbosity level'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -s v -d 'Increase the verbosity level'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l alt-accept
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "colors"
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "liza" -d 'List directory (eza wrapper)'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "shell" -d 'Dump the initialization code for your shell'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "lessfilter" -d 'Context and preset dependent file handler'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "pager" -d 'Page a file or stdin through bat into minus (single optional path)'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "bump" -d 'Bump history entries'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "trash" -d 'Trash files with timed fallback prompts'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "showbinds" -d 'Show binds'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "types" -d 'List mappings supported by the --type parameter'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "diskspace" -d 'Disk usage: compute directory sizes concurrently and print them'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "check" -d 'Validate configuration and scripts; exits non-zero on errors'
-complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "showerror" -d 'Display an error message and wait for keypress'
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l query -d 'initial query, replacing the saved one' -r
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l override -d 'config override' -r
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l config -d 'config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l mm-config -d 'matchmaker config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l style -r -f -a "icons\t''
+icon-colors\t''
+colors\t''
+none\t''
+all\t''
+auto\t''"
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l fullscreen -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l lock-prompt -d 'See `interface.prompt_locking`' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l output-sep -d 'Separator printed after each result' -r
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l format -d 'Output template for printed results' -r
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l opener -d 'Program used to open files on accept' -r
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l list
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l help
+complete -c fs -n "__fish_fs_using_subcommand :saved" -s q -d 'Reduce the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :saved" -s v -d 'Increase the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :saved" -l alt-accept
+complete -c fs -n "__fish_fs_using_subcommand :s" -l query -d 'initial query, replacing the saved one' -r
+complete -c fs -n "__fish_fs_using_subcommand :s" -l override -d 'config override' -r
+complete -c fs -n "__fish_fs_using_subcommand :s" -l config -d 'config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :s" -l mm-config -d 'matchmaker config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :s" -l style -r -f -a "icons\t''
+icon-colors\t''
+colors\t''
+none\t''
+all\t''
+auto\t''"
+complete -c fs -n "__fish_fs_using_subcommand :s" -l fullscreen -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :s" -l lock-prompt -d 'See `interface.prompt_locking`' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :s" -l output-sep -d 'Separator printed after each result' -r
+complete -c fs -n "__fish_fs_using_subcommand :s" -l format -d 'Output template for printed results' -r
+complete -c fs -n "__fish_fs_using_subcommand :s" -l opener -d 'Program used to open files on accept' -r
+complete -c fs -n "__fish_fs_using_subcommand :s" -l list
+complete -c fs -n "__fish_fs_using_subcommand :s" -l help
+complete -c fs -n "__fish_fs_using_subcommand :s" -s q -d 'Reduce the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :s" -s v -d 'Increase the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :s" -l alt-accept
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -s h -d 'show hidden files and folders' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -s I -d 'HIDE ignored files' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -s a -d 'show all' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -s F -d 'only show directories' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -s f -d 'show only files' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l size -d 'only show files of a size: +10M is at least, -1k at most' -r
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l changed-within -d 'only show entries modified within a duration (2d, 1h 30min)' -r
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l older-than -d 'only show entries modified before a duration ago (1y, 2w)' -r
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l perm -d 'only show entries the current user can access with r, w and/or x' -r
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l owner -d 'only show entries owned by a user (me, a name or a uid)' -r
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l git -d 'only show entries with a git status' -r -f -a "changed\t'Modified, staged, untracked or conflicted'
+modified\t''
+staged\t''
+untracked\t''
+conflicted\t''
+ignored\t''"
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l override -d 'config override' -r
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l config -d 'config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l mm-config -d 'matchmaker config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l style -r -f -a "icons\t''
+icon-colors\t''
+colors\t''
+none\t''
+all\t''
+auto\t''"
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l fullscreen -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l lock-prompt -d 'See `interface.prompt_locking`' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l output-sep -d 'Separator printed after each result' -r
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l format -d 'Output template for printed results' -r
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l opener -d 'Program used to open files on accept' -r
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l list -d 'Print the groups, separated by an empty line'
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l help
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -s q -d 'Reduce the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -s v -d 'Increase the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :dupes" -l alt-accept
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l override -d 'config override' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l config -d 'config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l mm-config -d 'matchmaker config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l style -r -f -a "icons\t''
+icon-colors\t''
+colors\t''
+none\t''
+all\t''
+auto\t''"
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l fullscreen -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l lock-prompt -d 'See `interface.prompt_locking`' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l output-sep -d 'Separator printed after each result' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l format -d 'Output template for printed results' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l opener -d 'Program used to open files on accept' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l help
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -s q -d 'Reduce the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -s v -d 'Increase the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l alt-accept
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "colors"
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "liza" -d 'List directory (eza wrapper)'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "shell" -d 'Dump the initialization code for your shell'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "lessfilter" -d 'Context and preset dependent file handler'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "pager" -d 'Page a file or stdin through bat into minus (single optional path)'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "bump" -d 'Bump history entries'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "trash" -d 'Trash files with timed fallback prompts'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "showbinds" -d 'Show binds'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "types" -d 'List mappings supported by the --type parameter'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "diskspace" -d 'Disk usage: compute directory sizes concurrently and print them'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "db" -d 'Database statistics and maintenance'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "stash" -d 'Export, import and list stashes'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "check" -d 'Validate configuration and scripts; exits non-zero on errors'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "showerror" -d 'Display an error message and wait for keypress'
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from colors" -l override -d 'config override' -r
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from colors" -l config -d 'config path' -r -F
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from colors" -l mm-config -d 'matchmaker config path' -r -F
@@ -669,6 +841,44 @@ complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from diskspace" -s q -d 'Reduce the verbosity level'
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from diskspace" -s v -d 'Increase the verbosity level'
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from diskspace" -l alt-accept
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -l override -d 'config override' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -l config -d 'config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -l mm-config -d 'matchmaker config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -l style -r -f -a "icons\t''
+icon-colors\t''
+colors\t''
+none\t''
+all\t''
+auto\t''"
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -l fullscreen -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -l lock-prompt -d 'See `interface.prompt_locking`' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -l output-sep -d 'Separator printed after each result' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -l format -d 'Output template for printed results' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -l opener -d 'Program used to open files on accept' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -s q -d 'Reduce the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -s v -d 'Increase the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from db" -l alt-accept
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -l override -d 'config override' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -l config -d 'config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -l mm-config -d 'matchmaker config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -l style -r -f -a "icons\t''
+icon-colors\t''
+colors\t''
+none\t''
+all\t''
+auto\t''"
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -l fullscreen -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -l lock-prompt -d 'See `interface.prompt_locking`' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -l output-sep -d 'Separator printed after each result' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -l format -d 'Output template for printed results' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -l opener -d 'Program used to open files on accept' -r
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -s q -d 'Reduce the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -s v -d 'Increase the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from stash" -l alt-accept
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from check" -l override -d 'config override' -r
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from check" -l config -d 'config path' -r -F
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from check" -l mm-config -d 'matchmaker config path' -r -F
@@ -707,38 +917,40 @@ complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from showerror" -s q -d 'Reduce the verbosity level'
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from showerror" -s v -d 'Increase the verbosity level'
 complete -c fs -n "__fish_fs_using_subcommand :tool; and __fish_seen_subcommand_from showerror" -l alt-accept
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l override -d 'config override' -r
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l config -d 'config path' -r -F
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l mm-config -d 'matchmaker config path' -r -F
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l style -r -f -a "icons\t''
-icon-colors\t''
-colors\t''
-none\t''
-all\t''
-auto\t''"
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l fullscreen -r -f -a "true\t''
-false\t''"
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l lock-prompt -d 'See `interface.prompt_locking`' -r -f -a "true\t''
-false\t''"
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l output-sep -d 'Separator printed after each result' -r
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l format -d 'Output template for printed results' -r
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l opener -d 'Program used to open files on accept' -r
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l help
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -s q -d 'Reduce the verbosity level'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -s v -d 'Increase the verbosity level'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -l alt-accept
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "colors"
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "liza" -d 'List directory (eza wrapper)'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "shell" -d 'Dump the initialization code for your shell'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "lessfilter" -d 'Context and preset dependent file handler'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "pager" -d 'Page a file or stdin through bat into minus (single optional path)'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "bump" -d 'Bump history entries'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "trash" -d 'Trash files with timed fallback prompts'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "showbinds" -d 'Show binds'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "types" -d 'List mappings supported by the --type parameter'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "diskspace" -d 'Disk usage: compute directory sizes concurrently and print them'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "check" -d 'Validate configuration and scripts; exits non-zero on errors'
-complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace check showerror" -a "showerror" -d 'Display an error message and wait for keypress'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l override -d 'config override' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l config -d 'config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l mm-config -d 'matchmaker config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l style -r -f -a "icons\t''
+icon-colors\t''
+colors\t''
+none\t''
+all\t''
+auto\t''"
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l fullscreen -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l lock-prompt -d 'See `interface.prompt_locking`' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l output-sep -d 'Separator printed after each result' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l format -d 'Output template for printed results' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l opener -d 'Program used to open files on accept' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l help
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -s q -d 'Reduce the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -s v -d 'Increase the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -l alt-accept
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "colors"
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "liza" -d 'List directory (eza wrapper)'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "shell" -d 'Dump the initialization code for your shell'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "lessfilter" -d 'Context and preset dependent file handler'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "pager" -d 'Page a file or stdin through bat into minus (single optional path)'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "bump" -d 'Bump history entries'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "trash" -d 'Trash files with timed fallback prompts'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "showbinds" -d 'Show binds'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "types" -d 'List mappings supported by the --type parameter'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "diskspace" -d 'Disk usage: compute directory sizes concurrently and print them'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "db" -d 'Database statistics and maintenance'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "stash" -d 'Export, import and list stashes'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "check" -d 'Validate configuration and scripts; exits non-zero on errors'
+complete -c fs -n "__fish_fs_using_subcommand :t; and not __fish_seen_subcommand_from colors liza shell lessfilter pager bump trash showbinds types diskspace db stash check showerror" -a "showerror" -d 'Display an error message and wait for keypress'
 complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from colors" -l override -d 'config override' -r
 complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from colors" -l config -d 'config path' -r -F
 complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from colors" -l mm-config -d 'matchmaker config path' -r -F
@@ -929,6 +1141,44 @@ complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_fro
 complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from diskspace" -s q -d 'Reduce the verbosity level'
 complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from diskspace" -s v -d 'Increase the verbosity level'
 complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from diskspace" -l alt-accept
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -l override -d 'config override' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -l config -d 'config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -l mm-config -d 'matchmaker config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -l style -r -f -a "icons\t''
+icon-colors\t''
+colors\t''
+none\t''
+all\t''
+auto\t''"
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -l fullscreen -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -l lock-prompt -d 'See `interface.prompt_locking`' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -l output-sep -d 'Separator printed after each result' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -l format -d 'Output template for printed results' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -l opener -d 'Program used to open files on accept' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -s q -d 'Reduce the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -s v -d 'Increase the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from db" -l alt-accept
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -l override -d 'config override' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -l config -d 'config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -l mm-config -d 'matchmaker config path' -r -F
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -l style -r -f -a "icons\t''
+icon-colors\t''
+colors\t''
+none\t''
+all\t''
+auto\t''"
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -l fullscreen -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -l lock-prompt -d 'See `interface.prompt_locking`' -r -f -a "true\t''
+false\t''"
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -l output-sep -d 'Separator printed after each result' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -l format -d 'Output template for printed results' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -l opener -d 'Program used to open files on accept' -r
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -s q -d 'Reduce the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -s v -d 'Increase the verbosity level'
+complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from stash" -l alt-accept
 complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from check" -l override -d 'config override' -r
 complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from check" -l config -d 'config path' -r -F
 complete -c fs -n "__fish_fs_using_subcommand :t; and __fish_seen_subcommand_from check" -l mm-config -d 'matchmaker config path' -r -F
@@ -973,6 +1223,8 @@ atime\t''
 size\t''
 none\t''"
 complete -c fs -n "__fish_fs_using_subcommand :info" -s l -l limit -d 'maximum history entries to display' -r
+complete -c fs -n "__fish_fs_using_subcommand :info" -l since -d 'print the visits since DATE (today, yesterday, a weekday, <N>d or YYYY-MM-DD)' -r
+complete -c fs -n "__fish_fs_using_subcommand :info" -l until -d 'print the visits up to and including DATE' -r
 complete -c fs -n "__fish_fs_using_subcommand :info" -l override -d 'config override' -r
 complete -c fs -n "__fish_fs_using_subcommand :info" -l config -d 'config path' -r -F
 complete -c fs -n "__fish_fs_using_subcommand :info" -l mm-config -d 'matchmaker config path' -r -F
@@ -990,6 +1242,7 @@ complete -c fs -n "__fish_fs_using_subcommand :info" -l output-sep -d 'Separator
 complete -c fs -n "__fish_fs_using_subcommand :info" -l format -d 'Output template for printed results' -r
 complete -c fs -n "__fish_fs_using_subcommand :info" -l opener -d 'Program used to open files on accept' -r
 complete -c fs -n "__fish_fs_using_subcommand :info" -s m -l minimal -d 'Don\'t print decorations'
+complete -c fs -n "__fish_fs_using_subcommand :info" -l aliases -d 'print aliased directories and files (just the aliases with --minimal)'
 complete -c fs -n "__fish_fs_using_subcommand :info" -l help
 complete -c fs -n "__fish_fs_using_subcommand :info" -s q -d 'Reduce the verbosity level'
 complete -c fs -n "__fish_fs_using_subcommand :info" -s v -d 'Increase the verbosity level'
@@ -1,8 +1,23 @@
 use cba::bath::PathExt;
 use std::path::Path;
 
+use crate::{
+    git::{self, GitFilter},
+    predicates::{Age, Owner, Perms, Predicates, SizeBound},
+};
+
 #[allow(non_camel_case_types)]
-#[derive(Debug, Clone, Copy, Default, strum_macros::Display, PartialEq, Eq, clap::ValueEnum)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    PartialEq,
+    Eq,
+    clap::ValueEnum,
+)]
 #[cfg_attr(
     feature = "serde",
     derive(
@@ -77,6 +92,11 @@ pub struct Visibility {
 
     /// Don't follow symlinks (tui only).
     pub no_follow: bool,
+
+    /// Size, time, permission and owner filters.
+    pub predicates: Predicates,
+    /// Only show entries with a git status.
+    pub git: Option<GitFilter>,
 }
 
 impl Visibility {
@@ -88,6 +108,8 @@ impl Visibility {
         ignore: false,
         dirs: false,
         files: false,
+        predicates: Predicates::NONE,
+        git: None,
     };
 
     pub fn enable_hidden_if_empty_otherwise(mut self, cwd: &Path, modify_if: bool) -> Self {
@@ -119,14 +141,16 @@ impl Visibility {
         let mut push = true;
 
         if self.hidden_only {
-            return path.is_hidden()
+            return (path.is_hidden()
                 || if self.dirs {
                     path.is_dir()
                 } else if self.files {
                     path.is_file()
                 } else {
                     false
-                };
+                })
+                && self.predicates.matches(path)
+                && self.git_matches(path);
         } else if !self.hidden {
             push &= !path.is_hidden()
         }
@@ -137,7 +161,7 @@ impl Visibility {
             push &= path.is_file()
         }
 
-        push
+        push && self.predicates.matches(path) && self.git_matches(path)
     }
 
     /// applies the full visibility filter (notes: checks exists(), ignore not implemented)
@@ -187,7 +211,14 @@ impl Visibility {
                 };
         };
 
-        push
+        // fd applies these too, this catches those it can't (perm) and rg
+        push && self.predicates.matches(path) && self.git_matches(path)
+    }
+
+    /// Whether `path` passes the git status filter, computing the status of its repository on
+    /// first use.
+    pub fn git_matches(&self, path: &Path) -> bool {
+        self.git.is_none_or(|f| f.matches(git::status(path)))
     }
 
     pub fn is_default(&self) -> bool {
@@ -203,6 +234,8 @@ impl Visibility {
                 all: true,
                 dirs: self.dirs,
                 no_follow: self.no_follow,
+                predicates: self.predicates,
+                git: self.git,
                 ..Default::default()
             }
         } else {
@@ -298,6 +331,30 @@ pub struct PartialVisibility {
     /// Don't follow symlinks (tui only).
     #[arg(skip)]
     pub no_follow: Option<bool>,
+
+    /// only show files of a size: +10M is at least, -1k at most
+    #[arg(long, value_name = "SIZE", allow_hyphen_values = true)]
+    pub size: Option<SizeBound>,
+
+    /// only show entries modified within a duration (2d, 1h 30min)
+    #[arg(long, value_name = "AGE")]
+    pub changed_within: Option<Age>,
+
+    /// only show entries modified before a duration ago (1y, 2w)
+    #[arg(long, value_name = "AGE")]
+    pub older_than: Option<Age>,
+
+    /// only show entries the current user can access with r, w and/or x
+    #[arg(long, value_name = "rwx")]
+    pub perm: Option<Perms>,
+
+    /// only show entries owned by a user (me, a name or a uid)
+    #[arg(long, value_name = "USER")]
+    pub owner: Option<Owner>,
+
+    /// only show entries with a git status
+    #[arg(long, value_name = "STATUS")]
+    pub git: Option<GitFilter>,
 }
 
 impl PartialVisibility {
@@ -332,6 +389,27 @@ impl PartialVisibility {
     }
 }
 
+/// A patch reproducing `vis`: hidden and ignore are always explicit (so
+/// no pane default or git detection applies), the other flags only when set.
+impl From<Visibility> for PartialVisibility {
+    fn from(vis: Visibility) -> Self {
+        Self {
+            hidden: Some(vis.hidden),
+            ignore: Some(vis.ignore),
+            all: vis.all().then_some(true),
+            dirs: vis.dirs.then_some(true),
+            files: vis.files.then_some(true),
+            no_follow: vis.no_follow.then_some(true),
+            size: vis.predicates.size,
+            changed_within: vis.predicates.changed_within,
+            older_than: vis.predicates.older_than,
+            perm: vis.predicates.perm,
+            owner: vis.predicates.owner,
+            git: vis.git,
+        }
+    }
+}
+
 impl Visibility {
     pub fn apply(&mut self, patch: PartialVisibility) {
         if let Some(v) = patch.hidden {
@@ -352,6 +430,13 @@ impl Visibility {
         if let Some(v) = patch.no_follow {
             self.no_follow = v;
         }
+        let p = &mut self.predicates;
+        p.size = patch.size.or(p.size);
+        p.changed_within = patch.changed_within.or(p.changed_within);
+        p.older_than = patch.older_than.or(p.older_than);
+        p.perm = patch.perm.or(p.perm);
+        p.owner = patch.owner.or(p.owner);
+        self.git = patch.git.or(self.git);
         *self = self.validated();
     }
 }
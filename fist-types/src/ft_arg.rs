@@ -6,6 +6,8 @@ use super::{categories::FileCategory, filetypes::FileType};
 ///
 /// Ext: '.*'
 ///
+/// No extension: '.' (or an empty argument).
+///
 /// Groups: Configurable.
 #[derive(Debug, Clone)]
 pub enum FileTypeArg {
@@ -21,7 +23,7 @@ impl std::str::FromStr for FileTypeArg {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.is_empty() {
+        if s.is_empty() || s == "." {
             return Ok(Self::NoExt);
         }
         let s_lower = s.to_lowercase();
@@ -45,3 +47,34 @@ impl std::str::FromStr for FileTypeArg {
         Ok(FileTypeArg::Group(s.to_string()))
     }
 }
+
+/// Inverse of [`FromStr`](std::str::FromStr): the `-t` argument which parses back to `self`.
+impl std::fmt::Display for FileTypeArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileTypeArg::Type(t) => write!(f, "{t}"),
+            FileTypeArg::FileCategory(c) => write!(f, "{c}"),
+            FileTypeArg::Ext(e) => write!(f, ".{e}"),
+            FileTypeArg::Group(g) => f.write_str(g),
+            FileTypeArg::NoExt => f.write_str("."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_parses_back() {
+        for arg in ["f", "d", "image", ".rs", "mygroup", "."] {
+            let parsed: FileTypeArg = arg.parse().unwrap();
+            let displayed = parsed.to_string();
+            assert_eq!(displayed, arg);
+            let reparsed: FileTypeArg = displayed.parse().unwrap();
+            assert_eq!(format!("{reparsed:?}"), format!("{parsed:?}"));
+        }
+        assert!(matches!("".parse(), Ok(FileTypeArg::NoExt)));
+        assert!(matches!(".".parse(), Ok(FileTypeArg::NoExt)));
+    }
+}
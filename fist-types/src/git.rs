@@ -1,4 +1,10 @@
-use std::{ffi::OsStr, path::PathBuf};
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{Arc, LazyLock, Mutex},
+};
 
 pub const VCS_DIRS: &[&str] = &[
     ".git", ".jj", ".sl", ".hg", ".svn", ".bzr", "_darcs", ".pijul", "CVS",
@@ -20,3 +26,376 @@ pub fn in_git_repo(mut dir: Option<PathBuf>) -> bool {
 
     false
 }
+
+/// The nearest ancestor of `dir` (inclusive) containing a [`VCS_DIRS`] entry.
+pub fn repo_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|p| VCS_DIRS.iter().any(|vcs| p.join(vcs).exists()))
+        .map(Path::to_path_buf)
+}
+
+// ------------- STATUS -----------------
+
+/// The git status of a path, from lowest to highest precedence: a directory takes the highest
+/// status among its contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GitStatus {
+    Ignored,
+    Untracked,
+    /// Changed in the index only.
+    Staged,
+    /// Changed in the worktree.
+    Modified,
+    /// Unmerged.
+    Conflicted,
+}
+
+impl GitStatus {
+    pub fn marker(self) -> char {
+        match self {
+            Self::Ignored => '!',
+            Self::Untracked => '?',
+            Self::Staged => '+',
+            Self::Modified => 'M',
+            Self::Conflicted => 'U',
+        }
+    }
+
+    /// From the `XY` code of a `git status --porcelain` entry.
+    fn from_xy(
+        x: u8,
+        y: u8,
+    ) -> Option<Self> {
+        Some(match (x, y) {
+            (b'!', b'!') => Self::Ignored,
+            (b'?', b'?') => Self::Untracked,
+            (b'U', _) | (_, b'U') | (b'A', b'A') | (b'D', b'D') => Self::Conflicted,
+            (_, b' ') if x != b' ' => Self::Staged,
+            (_, b' ') => return None,
+            _ => Self::Modified,
+        })
+    }
+}
+
+/// Restricts a listing by git status.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display, clap::ValueEnum)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum GitFilter {
+    /// Modified, staged, untracked or conflicted.
+    Changed,
+    Modified,
+    Staged,
+    Untracked,
+    Conflicted,
+    Ignored,
+}
+
+impl GitFilter {
+    pub const ALL: [Self; 6] = [
+        Self::Changed,
+        Self::Modified,
+        Self::Staged,
+        Self::Untracked,
+        Self::Conflicted,
+        Self::Ignored,
+    ];
+
+    pub fn matches(
+        self,
+        status: Option<GitStatus>,
+    ) -> bool {
+        let Some(status) = status else {
+            return false;
+        };
+        match self {
+            Self::Changed => status != GitStatus::Ignored,
+            Self::Modified => status == GitStatus::Modified,
+            Self::Staged => status == GitStatus::Staged,
+            Self::Untracked => status == GitStatus::Untracked,
+            Self::Conflicted => status == GitStatus::Conflicted,
+            Self::Ignored => status == GitStatus::Ignored,
+        }
+    }
+
+    /// The next filter of the options overlay cycle, which ends on no filter.
+    pub fn cycle(filter: Option<Self>) -> Option<Self> {
+        match filter {
+            None => Some(Self::ALL[0]),
+            Some(f) => Self::ALL
+                .iter()
+                .position(|x| *x == f)
+                .and_then(|i| Self::ALL.get(i + 1))
+                .copied(),
+        }
+    }
+}
+
+/// The statuses of a repository, parsed from `git status --porcelain -z --ignored`.
+#[derive(Debug, Default)]
+pub struct RepoStatus {
+    /// Entries by path relative to the root. Untracked and ignored directories are listed
+    /// without their contents.
+    entries: HashMap<PathBuf, GitStatus>,
+    /// The highest status of the contents of each directory with changes.
+    dirs: HashMap<PathBuf, GitStatus>,
+}
+
+impl RepoStatus {
+    pub fn parse(porcelain: &[u8]) -> Self {
+        let mut ret = Self::default();
+        let mut records = porcelain.split(|b| *b == 0);
+
+        while let Some(record) = records.next() {
+            let [x, y, b' ', path @ ..] = record else {
+                continue;
+            };
+            // renames and copies are followed by their source
+            if matches!(x, b'R' | b'C') {
+                records.next();
+            }
+            let Some(status) = GitStatus::from_xy(*x, *y) else {
+                continue;
+            };
+            let path = PathBuf::from(String::from_utf8_lossy(path).trim_end_matches('/'));
+
+            if status != GitStatus::Ignored {
+                for dir in path.ancestors().skip(1) {
+                    let s = ret.dirs.entry(dir.to_path_buf()).or_insert(status);
+                    *s = (*s).max(status);
+                }
+            }
+            ret.entries.insert(path, status);
+        }
+        ret
+    }
+
+    /// The status of a path relative to the root.
+    pub fn get(
+        &self,
+        path: &Path,
+    ) -> Option<GitStatus> {
+        if let Some(s) = self.entries.get(path) {
+            return Some(*s);
+        }
+        if let Some(s) = self.dirs.get(path) {
+            return Some(*s);
+        }
+        // inside an untracked or ignored directory
+        path.ancestors()
+            .skip(1)
+            .find_map(|dir| self.entries.get(dir))
+            .copied()
+    }
+}
+
+struct CachedRepo {
+    status: Option<Arc<RepoStatus>>,
+    stale: bool,
+}
+
+/// Statuses by repository root, computed once per root with a single `git status`.
+static REPOS: LazyLock<Mutex<HashMap<PathBuf, CachedRepo>>> = LazyLock::new(Default::default);
+/// Repository root of each looked up directory.
+static ROOTS: LazyLock<Mutex<HashMap<PathBuf, Option<PathBuf>>>> = LazyLock::new(Default::default);
+
+/// The nearest ancestor of `dir` (inclusive) containing a `.git` entry.
+fn git_root(dir: &Path) -> Option<PathBuf> {
+    if let Some(root) = ROOTS.lock().unwrap().get(dir) {
+        return root.clone();
+    }
+    let root = dir
+        .ancestors()
+        .find(|p| p.join(".git").exists())
+        .map(Path::to_path_buf);
+    ROOTS
+        .lock()
+        .unwrap()
+        .insert(dir.to_path_buf(), root.clone());
+    root
+}
+
+fn run_git_status(root: &Path) -> Option<RepoStatus> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        // don't refresh the index: the write would wake up the watcher
+        .args([
+            "--no-optional-locks",
+            "status",
+            "--porcelain",
+            "-z",
+            "--ignored",
+        ])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| RepoStatus::parse(&output.stdout))
+}
+
+/// The status of the repository at `root`, running `git status` when it is missing or stale.
+pub fn repo_status(root: &Path) -> Option<Arc<RepoStatus>> {
+    if let Some(cached) = REPOS.lock().unwrap().get(root)
+        && !cached.stale
+    {
+        return cached.status.clone();
+    }
+    let status = run_git_status(root).map(Arc::new);
+    REPOS.lock().unwrap().insert(
+        root.to_path_buf(),
+        CachedRepo {
+            status: status.clone(),
+            stale: false,
+        },
+    );
+    status
+}
+
+/// The git status of `path`, computing the status of its repository if needed.
+pub fn status(path: &Path) -> Option<GitStatus> {
+    let root = git_root(path.parent()?)?;
+    let repo = repo_status(&root)?;
+    repo.get(path.strip_prefix(&root).ok()?)
+}
+
+/// Like [`status`], but never runs git: paths of repositories that were not computed yet have
+/// no status, and stale statuses are returned until they are recomputed.
+pub fn cached_status(path: &Path) -> Option<GitStatus> {
+    let root = git_root(path.parent()?)?;
+    let repo = REPOS.lock().unwrap().get(&root)?.status.clone()?;
+    repo.get(path.strip_prefix(&root).ok()?)
+}
+
+/// Compute the status of the repository containing `dir` ahead of [`cached_status`]. Returns
+/// whether git was run.
+pub fn prefetch(dir: &Path) -> bool {
+    let Some(root) = git_root(dir) else {
+        return false;
+    };
+    if REPOS
+        .lock()
+        .unwrap()
+        .get(&root)
+        .is_some_and(|cached| !cached.stale)
+    {
+        return false;
+    }
+    repo_status(&root);
+    true
+}
+
+/// Mark the repositories containing `paths` as stale. Returns the roots of those which were not
+/// stale yet.
+///
+/// Changes inside a `.git` directory are ignored, except to the index and HEAD.
+pub fn invalidate(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut repos = REPOS.lock().unwrap();
+    let mut ret = vec![];
+    for (root, cached) in repos.iter_mut() {
+        let affected = paths.iter().any(|p| {
+            p.strip_prefix(root).is_ok_and(|rel| {
+                let mut components = rel.components();
+                components.next().is_none_or(|c| c.as_os_str() != ".git")
+                    || matches!(components.as_path().to_str(), Some("index" | "HEAD"))
+            })
+        });
+        if affected && !cached.stale {
+            cached.stale = true;
+            ret.push(root.clone());
+        }
+    }
+    ret
+}
+
+/// Mark every repository as stale.
+pub fn invalidate_all() {
+    for cached in REPOS.lock().unwrap().values_mut() {
+        cached.stale = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_status() {
+        let status = RepoStatus::parse(
+            b" M src/lib.rs\0A  src/new.rs\0R  b.rs\0a.rs\0UU conflict.rs\0?? tmp/\0!! target/\0",
+        );
+        assert_eq!(
+            status.get(Path::new("src/lib.rs")),
+            Some(GitStatus::Modified)
+        );
+        assert_eq!(status.get(Path::new("src/new.rs")), Some(GitStatus::Staged));
+        assert_eq!(status.get(Path::new("b.rs")), Some(GitStatus::Staged));
+        assert_eq!(status.get(Path::new("a.rs")), None);
+        assert_eq!(
+            status.get(Path::new("conflict.rs")),
+            Some(GitStatus::Conflicted)
+        );
+        // directories take the highest status of their contents
+        assert_eq!(status.get(Path::new("src")), Some(GitStatus::Modified));
+        assert_eq!(status.get(Path::new("tmp/a/b")), Some(GitStatus::Untracked));
+        assert_eq!(
+            status.get(Path::new("target/debug")),
+            Some(GitStatus::Ignored)
+        );
+        assert_eq!(status.get(Path::new("README.md")), None);
+
+        assert!(GitFilter::Changed.matches(Some(GitStatus::Untracked)));
+        assert!(!GitFilter::Changed.matches(Some(GitStatus::Ignored)));
+        assert!(!GitFilter::Changed.matches(None));
+        assert_eq!(GitFilter::cycle(None), Some(GitFilter::Changed));
+        assert_eq!(GitFilter::cycle(Some(GitFilter::Ignored)), None);
+    }
+
+    #[test]
+    fn test_repo_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(root)
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .output()
+                .is_ok_and(|o| o.status.success())
+        };
+        if !git(&["init", "-q"]) {
+            return; // git is not installed
+        }
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "a").unwrap();
+        std::fs::write(root.join("clean.rs"), "a").unwrap();
+        assert!(git(&["add", "."]));
+        assert!(git(&["commit", "-qm", "init"]));
+        std::fs::write(root.join("src/lib.rs"), "b").unwrap();
+        std::fs::write(root.join("new.rs"), "a").unwrap();
+
+        assert_eq!(status(&root.join("new.rs")), Some(GitStatus::Untracked));
+        assert_eq!(status(&root.join("src")), Some(GitStatus::Modified));
+        assert_eq!(status(&root.join("clean.rs")), None);
+        assert_eq!(
+            cached_status(&root.join("src/lib.rs")),
+            Some(GitStatus::Modified)
+        );
+
+        // invalidated repositories are recomputed once
+        std::fs::write(root.join("clean.rs"), "b").unwrap();
+        let root = repo_root(root).unwrap();
+        assert_eq!(invalidate(&[root.join("clean.rs")]), std::slice::from_ref(&root));
+        assert!(invalidate(&[root.join("clean.rs")]).is_empty());
+        assert_eq!(status(&root.join("clean.rs")), Some(GitStatus::Modified));
+    }
+}
@@ -5,6 +5,7 @@ pub mod filters;
 mod ft_arg;
 pub mod git;
 pub mod icons;
+pub mod predicates;
 
 pub use categories::FileCategory;
 
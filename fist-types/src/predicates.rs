@@ -0,0 +1,565 @@
+//! Metadata predicates of a [`Visibility`](super::filters::Visibility): size,
+//! modification time, permissions and owner.
+//!
+//! Each predicate parses from (and displays as) its command line form, which
+//! is also how it is written in the config and given to fd.
+
+use std::{
+    fmt,
+    fs::Metadata,
+    path::Path,
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Predicates {
+    /// Only files within this size. Like fd's `--size`, other entries are excluded.
+    pub size: Option<SizeBound>,
+    /// Modified at most this long ago.
+    pub changed_within: Option<Age>,
+    /// Modified at least this long ago.
+    pub older_than: Option<Age>,
+    /// Accessible by the current user with these permissions.
+    pub perm: Option<Perms>,
+    pub owner: Option<Owner>,
+}
+
+impl Predicates {
+    pub const NONE: Self = Self {
+        size: None,
+        changed_within: None,
+        older_than: None,
+        perm: None,
+        owner: None,
+    };
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// Whether `path` satisfies every predicate. Symlinks are followed.
+    pub fn matches(
+        &self,
+        path: &Path,
+    ) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let Ok(meta) = path.metadata().or_else(|_| path.symlink_metadata()) else {
+            return false;
+        };
+        self.matches_metadata(path, &meta)
+    }
+
+    pub fn matches_metadata(
+        &self,
+        path: &Path,
+        meta: &Metadata,
+    ) -> bool {
+        if let Some(size) = self.size
+            && !(meta.is_file() && size.matches(meta.len()))
+        {
+            return false;
+        }
+        if self.changed_within.is_some() || self.older_than.is_some() {
+            let Ok(mtime) = meta.modified() else {
+                return false;
+            };
+            let now = SystemTime::now();
+            if self
+                .changed_within
+                .is_some_and(|age| age.cutoff(now).is_some_and(|t| mtime < t))
+            {
+                return false;
+            }
+            if self
+                .older_than
+                .is_some_and(|age| age.cutoff(now).is_none_or(|t| mtime > t))
+            {
+                return false;
+            }
+        }
+        if let Some(perm) = self.perm
+            && !perm.matches(path, meta)
+        {
+            return false;
+        }
+        if let Some(owner) = self.owner
+            && !owner.matches(meta)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+// ------------------------------------------------------------
+
+/// fd's `--size`: `+10M` is at least, `-1k` at most, otherwise exactly.
+/// Units are decimal (`k`, `M`, `G`, `T`) or binary (`Ki`, …), bytes by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeBound {
+    Min(u64),
+    Max(u64),
+    Equals(u64),
+}
+
+const SIZE_UNITS: [(&str, u64); 8] = [
+    ("T", 1000u64.pow(4)),
+    ("Ti", 1 << 40),
+    ("G", 1000u64.pow(3)),
+    ("Gi", 1 << 30),
+    ("M", 1000u64.pow(2)),
+    ("Mi", 1 << 20),
+    ("k", 1000),
+    ("Ki", 1 << 10),
+];
+
+impl SizeBound {
+    pub fn matches(
+        self,
+        len: u64,
+    ) -> bool {
+        match self {
+            Self::Min(n) => len >= n,
+            Self::Max(n) => len <= n,
+            Self::Equals(n) => len == n,
+        }
+    }
+
+    pub fn bytes(self) -> u64 {
+        match self {
+            Self::Min(n) | Self::Max(n) | Self::Equals(n) => n,
+        }
+    }
+}
+
+impl FromStr for SizeBound {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid size '{s}'");
+        let (sign, rest) = match s.as_bytes().first() {
+            Some(b'+') => (Some(true), &s[1..]),
+            Some(b'-') => (Some(false), &s[1..]),
+            _ => (None, s),
+        };
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let n: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        let unit = rest[digits..].to_lowercase();
+        let unit = unit.strip_suffix('b').unwrap_or(&unit);
+        let multiplier = if unit.is_empty() {
+            1
+        } else {
+            SIZE_UNITS
+                .iter()
+                .find(|(u, _)| u.eq_ignore_ascii_case(unit))
+                .map(|(_, m)| *m)
+                .ok_or_else(invalid)?
+        };
+        let bytes = n.checked_mul(multiplier).ok_or_else(invalid)?;
+        Ok(match sign {
+            Some(true) => Self::Min(bytes),
+            Some(false) => Self::Max(bytes),
+            None => Self::Equals(bytes),
+        })
+    }
+}
+
+/// The largest unit dividing the size, so `10M` displays as given. fd reads
+/// every form.
+impl fmt::Display for SizeBound {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Min(_) => f.write_str("+")?,
+            Self::Max(_) => f.write_str("-")?,
+            Self::Equals(_) => {}
+        }
+        let bytes = self.bytes();
+        match SIZE_UNITS
+            .iter()
+            .find(|(_, m)| bytes != 0 && bytes.is_multiple_of(*m))
+        {
+            Some((unit, m)) => write!(f, "{}{unit}", bytes / m),
+            None => write!(f, "{bytes}b"),
+        }
+    }
+}
+
+// ------------------------------------------------------------
+
+/// A duration before now: a sum of `<number><unit>` terms (`2d`, `1h 30min`).
+/// `m` is a minute and `M` a month, as in fd.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Age(pub Duration);
+
+const AGE_UNITS: [(&str, u64); 7] = [
+    // humantime's year and month, as used by fd
+    ("y", 31_557_600),
+    ("M", 2_630_016),
+    ("w", 7 * 24 * 60 * 60),
+    ("d", 24 * 60 * 60),
+    ("h", 60 * 60),
+    ("min", 60),
+    ("s", 1),
+];
+
+impl Age {
+    /// The time `self` before `now`, or `None` if that predates the epoch.
+    pub fn cutoff(
+        self,
+        now: SystemTime,
+    ) -> Option<SystemTime> {
+        now.checked_sub(self.0)
+            .filter(|t| *t >= SystemTime::UNIX_EPOCH)
+    }
+}
+
+impl FromStr for Age {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid duration '{s}'");
+        let mut total = 0u64;
+        let mut rest = s.trim();
+        if rest.is_empty() {
+            return Err(invalid());
+        }
+        while !rest.is_empty() {
+            let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+            let n: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+            rest = &rest[digits..];
+            let unit_len = rest.bytes().take_while(u8::is_ascii_alphabetic).count();
+            let secs = match &rest[..unit_len] {
+                "s" | "sec" | "secs" | "second" | "seconds" => 1,
+                "m" | "min" | "mins" | "minute" | "minutes" => 60,
+                "h" | "hr" | "hrs" | "hour" | "hours" => 60 * 60,
+                "d" | "day" | "days" => 24 * 60 * 60,
+                "w" | "week" | "weeks" => 7 * 24 * 60 * 60,
+                "M" | "month" | "months" => 2_630_016,
+                "y" | "year" | "years" => 31_557_600,
+                _ => return Err(invalid()),
+            };
+            total = n
+                .checked_mul(secs)
+                .and_then(|n| total.checked_add(n))
+                .ok_or_else(invalid)?;
+            rest = rest[unit_len..].trim_start();
+        }
+        Ok(Self(Duration::from_secs(total)))
+    }
+}
+
+impl fmt::Display for Age {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let (unit, n) = AGE_UNITS
+            .iter()
+            .find(|(_, n)| secs != 0 && secs.is_multiple_of(*n))
+            .unwrap_or(&("s", 1));
+        write!(f, "{}{unit}", secs / n)
+    }
+}
+
+// ------------------------------------------------------------
+
+/// Any of `r`, `w` and `x`, all of which the current user must be granted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Perms {
+    pub read: bool,
+    pub write: bool,
+    pub exec: bool,
+}
+
+impl Perms {
+    #[cfg(unix)]
+    pub fn matches(
+        self,
+        path: &Path,
+        _meta: &Metadata,
+    ) -> bool {
+        use std::os::unix::ffi::OsStrExt;
+        let Ok(path) = std::ffi::CString::new(path.as_os_str().as_bytes()) else {
+            return false;
+        };
+        let mode = [
+            (self.read, libc::R_OK),
+            (self.write, libc::W_OK),
+            (self.exec, libc::X_OK),
+        ]
+        .into_iter()
+        .filter(|(on, _)| *on)
+        .fold(0, |mode, (_, bit)| mode | bit);
+        unsafe { libc::access(path.as_ptr(), mode) == 0 }
+    }
+
+    #[cfg(not(unix))]
+    pub fn matches(
+        self,
+        _path: &Path,
+        meta: &Metadata,
+    ) -> bool {
+        !(self.write && meta.permissions().readonly()) && !self.exec
+    }
+}
+
+impl FromStr for Perms {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ret = Self::default();
+        for c in s.chars() {
+            match c {
+                'r' => ret.read = true,
+                'w' => ret.write = true,
+                'x' => ret.exec = true,
+                _ => {
+                    return Err(format!(
+                        "Invalid permissions '{s}': expected any of r, w, x"
+                    ));
+                }
+            }
+        }
+        if ret == Self::default() {
+            return Err("Empty permissions: expected any of r, w, x".into());
+        }
+        Ok(ret)
+    }
+}
+
+impl fmt::Display for Perms {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        for (on, c) in [(self.read, "r"), (self.write, "w"), (self.exec, "x")] {
+            if on {
+                f.write_str(c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------
+
+/// `me`, a user name or a uid. Names are resolved on parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Owner {
+    Me,
+    Uid(u32),
+}
+
+impl Owner {
+    /// `None` where users have no uid.
+    pub fn uid(self) -> Option<u32> {
+        match self {
+            Self::Uid(uid) => Some(uid),
+            #[cfg(unix)]
+            Self::Me => Some(unsafe { libc::geteuid() }),
+            #[cfg(not(unix))]
+            Self::Me => None,
+        }
+    }
+
+    #[cfg(unix)]
+    pub fn matches(
+        self,
+        meta: &Metadata,
+    ) -> bool {
+        use std::os::unix::fs::MetadataExt;
+        self.uid() == Some(meta.uid())
+    }
+
+    /// Files are always owned by the current user.
+    #[cfg(not(unix))]
+    pub fn matches(
+        self,
+        _meta: &Metadata,
+    ) -> bool {
+        self == Self::Me
+    }
+}
+
+impl FromStr for Owner {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "me" {
+            return Ok(Self::Me);
+        }
+        if let Ok(uid) = s.parse() {
+            return Ok(Self::Uid(uid));
+        }
+        user_uid(s)
+            .map(Self::Uid)
+            .ok_or_else(|| format!("Unknown user '{s}'"))
+    }
+}
+
+#[cfg(unix)]
+fn user_uid(name: &str) -> Option<u32> {
+    let name = std::ffi::CString::new(name).ok()?;
+    // SAFETY: the entry is read before any other call into the user database
+    let passwd = unsafe { libc::getpwnam(name.as_ptr()) };
+    (!passwd.is_null()).then(|| unsafe { (*passwd).pw_uid })
+}
+
+#[cfg(not(unix))]
+fn user_uid(_name: &str) -> Option<u32> {
+    None
+}
+
+impl fmt::Display for Owner {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Me => f.write_str("me"),
+            Self::Uid(uid) => write!(f, "{uid}"),
+        }
+    }
+}
+
+// ------------------------------------------------------------
+
+/// A predicate as edited in the options overlay: its row label and value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PredicateKind {
+    Size,
+    ChangedWithin,
+    OlderThan,
+    Perm,
+    Owner,
+}
+
+impl PredicateKind {
+    pub const ALL: [Self; 5] = [
+        Self::Size,
+        Self::ChangedWithin,
+        Self::OlderThan,
+        Self::Perm,
+        Self::Owner,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Size => "size",
+            Self::ChangedWithin => "within",
+            Self::OlderThan => "older",
+            Self::Perm => "perm",
+            Self::Owner => "owner",
+        }
+    }
+
+    /// The prompt title, with examples of the accepted values.
+    pub fn prompt(self) -> &'static str {
+        match self {
+            Self::Size => "Size (+10M, -1k)",
+            Self::ChangedWithin => "Changed within (2d, 1h 30min)",
+            Self::OlderThan => "Older than (1y, 2w)",
+            Self::Perm => "Permissions (r, w, x)",
+            Self::Owner => "Owner (me, name, uid)",
+        }
+    }
+
+    pub fn value(
+        self,
+        p: &Predicates,
+    ) -> Option<String> {
+        match self {
+            Self::Size => p.size.map(|v| v.to_string()),
+            Self::ChangedWithin => p.changed_within.map(|v| v.to_string()),
+            Self::OlderThan => p.older_than.map(|v| v.to_string()),
+            Self::Perm => p.perm.map(|v| v.to_string()),
+            Self::Owner => p.owner.map(|v| v.to_string()),
+        }
+    }
+
+    /// Parse `input` into the predicate, clearing it when `input` is blank.
+    pub fn set(
+        self,
+        p: &mut Predicates,
+        input: &str,
+    ) -> Result<(), String> {
+        fn parse<T: FromStr<Err = String>>(s: &str) -> Result<Option<T>, String> {
+            if s.is_empty() {
+                Ok(None)
+            } else {
+                s.parse().map(Some)
+            }
+        }
+        let input = input.trim();
+        match self {
+            Self::Size => p.size = parse(input)?,
+            Self::ChangedWithin => p.changed_within = parse(input)?,
+            Self::OlderThan => p.older_than = parse(input)?,
+            Self::Perm => p.perm = parse(input)?,
+            Self::Owner => p.owner = parse(input)?,
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------
+
+#[cfg(feature = "serde")]
+macro_rules! serde_from_str {
+    ($($t:ty),*) => {$(
+        impl serde::Serialize for $t {
+            fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                s.collect_str(self)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $t {
+            fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                String::deserialize(d)?
+                    .parse()
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "serde")]
+serde_from_str!(SizeBound, Age, Perms, Owner);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!("+10k".parse(), Ok(SizeBound::Min(10_000)));
+        assert_eq!("-1Mi".parse(), Ok(SizeBound::Max(1 << 20)));
+        assert_eq!("42".parse(), Ok(SizeBound::Equals(42)));
+        assert!("+10q".parse::<SizeBound>().is_err());
+        assert_eq!(SizeBound::Min(10_000_000).to_string(), "+10M");
+        assert_eq!(SizeBound::Max(1536).to_string(), "-1536b");
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!("2weeks".parse(), Ok(Age(Duration::from_secs(14 * 86400))));
+        assert_eq!("1h 30min".parse(), Ok(Age(Duration::from_secs(5400))));
+        assert_eq!(Age(Duration::from_secs(5400)).to_string(), "90min");
+        assert!("10".parse::<Age>().is_err());
+    }
+
+    #[test]
+    fn test_parse_perms_and_owner() {
+        assert_eq!("rx".parse::<Perms>().unwrap().to_string(), "rx");
+        assert!("rq".parse::<Perms>().is_err());
+        assert!("".parse::<Perms>().is_err());
+        assert_eq!("42".parse(), Ok(Owner::Uid(42)));
+    }
+}
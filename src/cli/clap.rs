@@ -173,6 +173,12 @@ pub enum SubCmd {
     Custom(CustomCommand),
     #[command(name = ":rg", visible_aliases = [":"])]
     Rg(SearchCommand),
+    /// Run a saved search.
+    #[command(name = ":saved", visible_aliases = [":s"])]
+    Saved(SavedCmd),
+    /// Find duplicate files.
+    #[command(name = ":dupes")]
+    Dupes(DupesCmd),
     #[command(name =  ":tool", visible_aliases = [":t"])]
     Tools(ToolsCmd),
     #[command(name = ":info")]
@@ -193,11 +199,47 @@ pub struct InfoCmd {
     /// Don't print decorations.
     #[arg(short, long)]
     pub minimal: bool,
+    /// print aliased directories and files (just the aliases with --minimal).
+    #[arg(long, conflicts_with = "table")]
+    pub aliases: bool,
+    /// print the visits since DATE (today, yesterday, a weekday, <N>d or YYYY-MM-DD).
+    #[arg(long, value_name = "DATE", conflicts_with = "aliases")]
+    pub since: Option<String>,
+    /// print the visits up to and including DATE.
+    #[arg(long, value_name = "DATE", conflicts_with = "aliases")]
+    pub until: Option<String>,
     // intro: slideshow showing some important keys in a table?
     #[arg(long, action = ArgAction::Help)]
     pub help: (),
 }
 
+/// Run a saved search (`[searches.NAME]` in the config)
+#[derive(Debug, Parser, Default, Clone)]
+pub struct SavedCmd {
+    /// saved search to run. Lists the saved searches when omitted.
+    pub name: Option<String>,
+    /// initial query, replacing the saved one.
+    #[arg(long)]
+    pub query: Option<String>,
+    #[arg(long)]
+    pub list: bool,
+    #[arg(long, action = ArgAction::Help)]
+    pub help: (),
+}
+
+#[derive(Debug, Parser, Default, Clone)]
+pub struct DupesCmd {
+    #[command(flatten)]
+    pub vis: PartialVisibility,
+    /// Directories to search (default: the current directory).
+    pub paths: Vec<PathBuf>,
+    /// Print the groups, separated by an empty line.
+    #[arg(long)]
+    pub list: bool,
+    #[arg(long, action = ArgAction::Help)]
+    pub help: (),
+}
+
 /// Launch apps and files
 #[derive(Debug, Parser, Default, Clone)]
 pub struct OpenCmd {
@@ -236,6 +278,13 @@ pub struct DirsCmd {
     #[arg(long)]
     pub cd: bool,
 
+    /// only show entries visited on DATE (today, yesterday, a weekday, <N>d or YYYY-MM-DD).
+    #[arg(long, value_name = "DATE", conflicts_with = "cd")]
+    pub on: Option<String>,
+    /// only show entries visited since DATE.
+    #[arg(long, value_name = "DATE", conflicts_with_all = ["on", "cd"])]
+    pub since: Option<String>,
+
     #[arg(long, hide = true, default_value_t)]
     pub initial_input: String,
 
@@ -260,6 +309,13 @@ pub struct FilesCmd {
     )]
     pub list: Option<ListMode>,
 
+    /// only show entries visited on DATE (today, yesterday, a weekday, <N>d or YYYY-MM-DD).
+    #[arg(long, value_name = "DATE")]
+    pub on: Option<String>,
+    /// only show entries visited since DATE.
+    #[arg(long, value_name = "DATE", conflicts_with = "on")]
+    pub since: Option<String>,
+
     /// initial query.
     #[arg(long, default_value_t)]
     pub query: String,
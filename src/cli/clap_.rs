@@ -55,6 +55,16 @@ pub enum SubTool {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<OsString>,
     },
+    /// Database statistics and maintenance.
+    Db {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<OsString>,
+    },
+    /// Export, import and list stashes.
+    Stash {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<OsString>,
+    },
     /// Validate configuration and scripts; exits non-zero on errors.
     Check,
     /// Display an error message and wait for keypress.
@@ -118,6 +118,11 @@ pub struct BumpCommand {
     #[arg(long)]
     pub prune: bool,
 
+    /// directory the paths were visited from, whose project root is credited with the visit
+    /// (defaults to the current directory).
+    #[arg(long, value_name = "DIR")]
+    pub from: Option<PathBuf>,
+
     /// table matched on by the glob.
     #[arg(last(true))]
     pub table: Option<DbTable>,
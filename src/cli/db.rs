@@ -0,0 +1,247 @@
+//! `fs :tool db` — database statistics and maintenance.
+
+use std::ffi::OsString;
+
+use cba::{_ibog, StringError, bath::PathExt, ebog, prints};
+use clap::{Parser, Subcommand};
+use comfy_table::{ContentArrangement, Row, Table, presets::UTF8_FULL};
+
+use crate::{
+    cli::paths::current_exe,
+    config::Config,
+    db::{DbTable, Pool, STAT_TABLES, TableStats},
+    display::human_size,
+    errors::CliError,
+};
+
+#[derive(Parser, Debug)]
+#[command(about = "Database statistics and maintenance")]
+pub struct DbArgs {
+    #[command(subcommand)]
+    pub cmd: DbCmd,
+
+    /// Print JSON instead of tables.
+    #[arg(long, global = true)]
+    pub json: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DbCmd {
+    /// Table sizes, row counts, event clock, score distribution and missing paths.
+    Stats,
+    /// Rebuild the database file, releasing unused space.
+    Vacuum,
+    /// Run an integrity check; exits non-zero on errors.
+    Check,
+    /// Remove history entries whose path no longer exists.
+    Prune {
+        /// Only remove entries scoring below this (0: any score).
+        #[arg(short, long, default_value_t = 0)]
+        threshold: i32,
+        /// Only list the entries which would be removed.
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+        /// Table to prune (default: all history tables).
+        table: Option<DbTable>,
+    },
+}
+
+#[derive(Debug, serde::Serialize)]
+struct DbStats {
+    path: String,
+    bytes: i64,
+    /// "ems" (event-clock) or "wall-clock" scoring.
+    mode: &'static str,
+    prune_max: usize,
+    prune_min: usize,
+    visits_max: usize,
+    tables: Vec<TableStats>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct PruneReport {
+    table: String,
+    paths: Vec<String>,
+    removed: u64,
+}
+
+/// Entry point for `fs :tool db`.
+pub async fn handle(mut args: Vec<OsString>, cfg: &Config) -> Result<(), CliError> {
+    let path = current_exe().basename();
+    args.insert(0, format!("{path} :tool db").into());
+    let DbArgs { cmd, json } = DbArgs::parse_from(args);
+
+    let pool = Pool::new_from_cfg(cfg).await?;
+    let mut conn = pool.get_conn(DbTable::dirs).await?;
+
+    match cmd {
+        DbCmd::Stats => {
+            let mut tables = Vec::with_capacity(STAT_TABLES.len());
+            for table in STAT_TABLES {
+                tables.push(conn.table_stats(table).await?);
+            }
+            let stats = DbStats {
+                path: cfg.db_path().to_string_lossy().into(),
+                bytes: conn.db_size().await?,
+                mode: if cfg.history.lambda.is_some() {
+                    "ems"
+                } else {
+                    "wall-clock"
+                },
+                prune_max: cfg.history.prune_max,
+                prune_min: cfg.history.prune_min,
+                visits_max: cfg.history.visits_max,
+                tables,
+            };
+
+            if json {
+                print_json(&stats)
+            } else {
+                display_stats(&stats);
+                Ok(())
+            }
+        }
+        DbCmd::Vacuum => {
+            let before = conn.db_size().await?;
+            conn.vacuum().await?;
+            let after = conn.db_size().await?;
+
+            if json {
+                print_json(&serde_json::json!({ "before": before, "after": after }))
+            } else {
+                _ibog!(
+                    "Vacuumed {}: {} -> {}",
+                    cfg.db_path().to_string_lossy(),
+                    human_size(before as u64, false),
+                    human_size(after as u64, false)
+                );
+                Ok(())
+            }
+        }
+        DbCmd::Check => {
+            let errors = conn.integrity_check().await?;
+
+            if json {
+                print_json(&serde_json::json!({ "ok": errors.is_empty(), "errors": errors }))?;
+            } else if errors.is_empty() {
+                _ibog!("{}: ok", cfg.db_path().to_string_lossy());
+            } else {
+                for e in &errors {
+                    ebog!("{e}");
+                }
+            }
+
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(CliError::Handled)
+            }
+        }
+        DbCmd::Prune {
+            threshold,
+            dry_run,
+            table,
+        } => {
+            let tables = match table {
+                Some(t) => vec![t],
+                None => vec![DbTable::apps, DbTable::files, DbTable::dirs],
+            };
+
+            let mut reports = Vec::with_capacity(tables.len());
+            for table in tables {
+                conn.switch_table(table);
+                let paths = conn.missing_below(threshold).await?;
+                let removed = if dry_run {
+                    0
+                } else {
+                    conn.remove_entries(&paths).await?
+                };
+                reports.push(PruneReport {
+                    table: table.to_string(),
+                    paths: paths.iter().map(|p| p.to_string_lossy().into()).collect(),
+                    removed,
+                });
+            }
+
+            if json {
+                return print_json(&reports);
+            }
+            for report in &reports {
+                if dry_run {
+                    for path in &report.paths {
+                        prints!(format!("{}\t{path}", report.table));
+                    }
+                }
+            }
+            if dry_run {
+                let total: usize = reports.iter().map(|r| r.paths.len()).sum();
+                _ibog!("Would prune {total} missing entries.");
+            } else {
+                let total: u64 = reports.iter().map(|r| r.removed).sum();
+                _ibog!("Pruned {total} missing entries.");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn print_json(value: &impl serde::Serialize) -> Result<(), CliError> {
+    let s = serde_json::to_string_pretty(value).map_err(|e| StringError::from(e.to_string()))?;
+    prints!(s);
+    Ok(())
+}
+
+fn display_stats(stats: &DbStats) {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    for (key, value) in [
+        ("Path", stats.path.clone()),
+        ("Size", human_size(stats.bytes as u64, false)),
+        ("Scoring", stats.mode.to_string()),
+        (
+            "Pruning",
+            format!("{} -> {} entries", stats.prune_max, stats.prune_min),
+        ),
+        ("Visit log", format!("{} visits", stats.visits_max)),
+    ] {
+        table.add_row(Row::from(vec![key.to_string(), value]));
+    }
+    prints!(table.to_string());
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(Row::from(vec![
+        "Table",
+        "Rows",
+        "Size",
+        if stats.mode == "ems" { "Clock" } else { "Latest" },
+        "Missing",
+        "Score (min/median/p90/max)",
+    ]));
+
+    let or_blank = |o: Option<String>| o.unwrap_or_default();
+    for t in &stats.tables {
+        table.add_row(Row::from(vec![
+            t.table.clone(),
+            t.rows.to_string(),
+            or_blank(t.bytes.map(|b| human_size(b as u64, false))),
+            or_blank(t.clock.map(|c| {
+                if stats.mode == "ems" {
+                    c.to_string()
+                } else {
+                    crate::display::display_epoch(c)
+                }
+            })),
+            or_blank(t.missing.zip(t.missing_share()).map(|(m, share)| {
+                format!("{m} ({:.1}%)", share * 100.0)
+            })),
+            or_blank(t.scores.map(|s| {
+                format!("{}/{}/{}/{}", s.min, s.median, s.p90, s.max)
+            })),
+        ]));
+    }
+    prints!(table.to_string());
+}
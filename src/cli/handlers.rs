@@ -36,8 +36,8 @@ use crate::{
     abspath::AbsPath,
     cli::{SubTool, clap_helpers::ListMode},
     config::Config,
-    db::{DbTable, Pool, zoxide::RetryStrat},
-    display::{display_entries, display_types_overview},
+    db::{DbTable, Pool, VisitRange, parse_day, zoxide::RetryStrat},
+    display::{display_entries, display_types_overview, display_visits},
     errors::{CliError, DbError},
     find::fd::{last_query_starts_with_dot, resolve_group_types},
     lessfilter::{self, LessfilterConfig},
@@ -74,6 +74,8 @@ pub async fn handle_subcommand(cli: Cli, cfg: Config) -> Result<(), CliError> {
         SubCmd::Tools(cmd) => handle_tools(cli.opts, cmd, cfg).await,
         SubCmd::Info(cmd) => handle_info(cli.opts, cmd, cfg).await,
         SubCmd::Rg(cmd) => handle_rg(cli.opts, cmd, cfg).await,
+        SubCmd::Saved(cmd) => handle_saved(cli.opts, cmd, cfg).await,
+        SubCmd::Dupes(cmd) => handle_dupes(cli.opts, cmd, cfg).await,
     }
 }
 
@@ -106,7 +108,42 @@ async fn handle_info(cli: CliOpts, cmd: InfoCmd, cfg: Config) -> Result<(), CliE
     let limit = cmd.limit.unwrap_or(if cmd.minimal { 0 } else { 50 });
     let pool = Pool::new_from_cfg(&cfg).await?;
 
-    if let Some(table) = cmd.table {
+    if cmd.aliases {
+        let mut conn = pool.get_conn(DbTable::dirs).await?;
+        let mut entries = conn.get_aliased_entries().await?;
+        conn.switch_table(DbTable::files);
+        entries.extend(conn.get_aliased_entries().await?);
+
+        for entry in entries {
+            if cmd.minimal {
+                println!("{}", entry.alias);
+            } else {
+                println!("{}\t{}", entry.alias, entry.path.display());
+            }
+        }
+    } else if cmd.since.is_some() || cmd.until.is_some() {
+        let range = visit_range(None, cmd.since.as_deref(), cmd.until.as_deref())?;
+        let mut conn = pool.get_conn(cmd.table.unwrap_or(DbTable::files)).await?;
+        let mut visits = conn.get_visits(range, cmd.table).await?;
+
+        if cmd.minimal {
+            let template = cli.output.format.clone();
+            let output_sep = cli.output.output_sep.clone().unwrap_or_else(|| "\n".into());
+            let mut seen = HashSet::new();
+            visits.retain(|v| seen.insert(v.path.clone()));
+            if limit != 0 {
+                visits.truncate(limit);
+            }
+            for visit in visits {
+                print(&visit.path, &template, &output_sep);
+            }
+        } else {
+            if limit != 0 {
+                visits.truncate(limit);
+            }
+            display_visits(&visits);
+        }
+    } else if let Some(table) = cmd.table {
         let mut conn = pool.get_conn(table).await?;
 
         conn.switch_table(table);
@@ -146,6 +183,7 @@ async fn handle_files(cli: CliOpts, cmd: FilesCmd, cfg: Config) -> Result<(), Cl
     let pane = FsPane::Files {
         sort: SortOrder::none,
         input: (cmd.query, 0),
+        range: visit_range(cmd.on.as_deref(), cmd.since.as_deref(), None)?,
     }
     .sort(cmd.sort)?;
 
@@ -236,7 +274,93 @@ async fn handle_rg(cli: CliOpts, mut cmd: SearchCommand, mut cfg: Config) -> Res
     start(pane, cfg, mm_cfg, pool, cli).await
 }
 
+async fn handle_saved(cli: CliOpts, cmd: SavedCmd, cfg: Config) -> Result<(), CliError> {
+    let Some(name) = cmd.name else {
+        let mut searches: Vec<_> = cfg.global.searches.iter().collect();
+        searches.sort_by_key(|(name, _)| *name);
+        for (name, search) in searches {
+            prints!(format!("{name}\t{}", search.kind));
+        }
+        return Ok(());
+    };
+    let Some(mut search) = cfg.global.searches.get(&name).cloned() else {
+        return Err(CliError::UnknownSearch(name));
+    };
+    if let Some(query) = cmd.query {
+        search.query = query;
+    }
+    if let Some(cwd) = search.cwd() {
+        set_current_dir(&cwd)
+            .prefix(format!("Failed to enter {}", cwd.to_string_lossy()))
+            .__ebog();
+    }
+
+    let lcfg: LessfilterConfig =
+        load_type_or_default(lessfilter_cfg_path(), |s| toml::from_str(s));
+    let pane = search.to_pane(AbsPath::initial(), &cfg.global, &lcfg);
+
+    if cmd.list {
+        return match &pane {
+            FsPane::Find {
+                vis,
+                types,
+                paths,
+                fd_args,
+                ..
+            } => super::list::fd_list(*vis, types, paths, fd_args, &cfg, &cli.output),
+            FsPane::Search {
+                sort,
+                vis,
+                paths,
+                context,
+                case,
+                patterns,
+                fixed_strings,
+                one_line,
+                rg,
+                ..
+            } => super::list::rg_list(
+                *vis,
+                *sort,
+                *context,
+                *case,
+                *one_line,
+                *fixed_strings,
+                patterns,
+                paths,
+                rg,
+                &cfg,
+                &cli.output,
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    let pool = Pool::new_from_cfg(&cfg).await?;
+    let mm_cfg = get_mm_cfg(&cli.mm_config, &cfg);
+    start(pane, cfg, mm_cfg, pool, cli).await
+}
+
+async fn handle_dupes(cli: CliOpts, cmd: DupesCmd, cfg: Config) -> Result<(), CliError> {
+    let vis = cmd
+        .vis
+        .into_resolved(cfg.global.panes.find.default_visibility);
+    let pane = FsPane::new_dupes(AbsPath::initial(), cmd.paths, vis);
+
+    if cmd.list {
+        if let FsPane::Dupes { paths, vis, .. } = &pane {
+            super::list::dupes_list(*vis, paths, &cfg, &cli.output);
+        }
+        return Ok(());
+    }
+
+    let pool = Pool::new_from_cfg(&cfg).await?;
+    let mm_cfg = get_mm_cfg(&cli.mm_config, &cfg);
+    start(pane, cfg, mm_cfg, pool, cli).await
+}
+
 async fn handle_dirs(cli: CliOpts, mut cmd: DirsCmd, mut cfg: Config) -> Result<(), CliError> {
+    let range = visit_range(cmd.on.as_deref(), cmd.since.as_deref(), None)?;
     let pool = Pool::new_from_cfg(&cfg).await?;
     if cmd.cd && cmd.list.is_some() {
         return Err(CliError::ConflictingFlags("cd", "list"));
@@ -276,7 +400,8 @@ async fn handle_dirs(cli: CliOpts, mut cmd: DirsCmd, mut cfg: Config) -> Result<
         }
         let db_filter = cfg.history.clone().with_keywords(cmd.query.clone());
 
-        return super::list::dirs_list(&mut conn, cmd.sort, &db_filter, all, &cli.output).await;
+        return super::list::dirs_list(&mut conn, cmd.sort, &db_filter, range, all, &cli.output)
+            .await;
     }
 
     let input = if !cmd.initial_input.is_empty() {
@@ -289,6 +414,7 @@ async fn handle_dirs(cli: CliOpts, mut cmd: DirsCmd, mut cfg: Config) -> Result<
     let pane = FsPane::Folders {
         sort: SortOrder::none,
         input,
+        range,
     }
     .sort(cmd.sort)?;
 
@@ -296,6 +422,27 @@ async fn handle_dirs(cli: CliOpts, mut cmd: DirsCmd, mut cfg: Config) -> Result<
     start(pane, cfg, mm_cfg, pool, cli).await
 }
 
+/// Resolve the `--on`/`--since`/`--until` date flags against the local date.
+fn visit_range(
+    on: Option<&str>,
+    since: Option<&str>,
+    until: Option<&str>,
+) -> Result<VisitRange, CliError> {
+    let today = chrono::Local::now().date_naive();
+    if let Some(on) = on {
+        return Ok(VisitRange::day(parse_day(on, today)?));
+    }
+
+    let mut range = VisitRange::default();
+    if let Some(since) = since {
+        range.since = VisitRange::day(parse_day(since, today)?).since;
+    }
+    if let Some(until) = until {
+        range.until = VisitRange::day(parse_day(until, today)?).until;
+    }
+    Ok(range)
+}
+
 async fn handle_default(
     cli: CliOpts,
     mut cmd: DefaultCommand,
@@ -606,6 +753,8 @@ async fn handle_tools(
             SubTool::Trash { args: args.clone() },
             SubTool::Types { args: args.clone() },
             SubTool::DiskSpace { args: args.clone() },
+            SubTool::Db { args: args.clone() },
+            SubTool::Stash { args: args.clone() },
         ])
         .await?
     };
@@ -732,6 +881,7 @@ async fn handle_tools(
                 table,
                 reset,
                 prune,
+                from,
             } = BumpCommand::parse_from(args);
 
             if prune {
@@ -818,7 +968,10 @@ async fn handle_tools(
                         _ibog!("Removed {}.", msg);
                     }
                 } else {
-                    conn.bump_files_and_folders_n(entry_queue, count).await?;
+                    // empty when the shell has no previous directory
+                    let from = from.filter(|d| !d.as_os_str().is_empty());
+                    conn.bump_files_and_folders_n(entry_queue, count, from.as_deref())
+                        .await?;
                 }
             } else {
                 let table = table.unwrap_or(table.unwrap_or(DbTable::dirs));
@@ -834,7 +987,7 @@ async fn handle_tools(
 
                 let db_filter = cfg.history.clone();
                 let entries = conn
-                    .get_entries(SortOrder::mtime, &db_filter, table)
+                    .get_entries(SortOrder::mtime, &db_filter, table, None)
                     .await
                     .__ebog();
 
@@ -872,6 +1025,8 @@ async fn handle_tools(
             Ok(())
         }
         SubTool::DiskSpace { args } => super::ds::handle(args),
+        SubTool::Db { args } => super::db::handle(args, &cfg).await,
+        SubTool::Stash { args } => super::stash::handle(args, &cfg).await,
         SubTool::Trash { mut args } => {
             let path = current_exe().basename();
             args.insert(0, format!("{path} :tool trash").into());
@@ -2,7 +2,7 @@
 //!
 //! The SortOrder arms for listing exist in exactly one place
 //! ([`print_sorted`]); output formatting shares [`crate::cli::handlers::print`].
-use std::{ffi::OsString, path::PathBuf, process::Command};
+use std::{ffi::OsString, path::PathBuf, process::Command, sync::Mutex};
 
 use ansi_to_tui::IntoText;
 use cba::{
@@ -21,9 +21,17 @@ use super::{OutputOpts, clap_helpers::ListMode, handlers::print};
 use crate::{
     cli::paths::__cwd,
     config::Config,
-    db::{Connection, zoxide::HistoryConfig},
+    db::{Connection, VisitRange, zoxide::HistoryConfig},
     errors::CliError,
-    find::{fd::build_fd_args, metadata, rg::build_rg_args, walker::list_dir},
+    find::{
+        fd::{build_fd_args, use_native_fd},
+        grep::NativeRg,
+        metadata,
+        native::NativeFd,
+        rg::{build_rg_args, use_native_rg},
+        walker::list_dir,
+    },
+    fs::dupes::find_duplicates,
 };
 
 /// Resolve `cli.output` into (template, separator).
@@ -55,10 +63,26 @@ fn print_sorted(mut files: Vec<PathBuf>, sort: SortOrder, template: &Option<Stri
 pub fn nav_list(cwd: &std::path::Path, vis: Visibility, sort: SortOrder, output: &OutputOpts) {
     let (template, sep) = output_parts(output);
     // cwd is abs so results can be added as unchecked
-    let files: Vec<PathBuf> = list_dir(cwd, vis, 1).collect();
+    let files: Vec<PathBuf> = list_dir(cwd, vis).collect();
     print_sorted(files, sort, &template, &sep);
 }
 
+/// Duplicates listing (`fs :dupes --list`): each group is followed by an extra separator.
+pub fn dupes_list(vis: Visibility, paths: &[PathBuf], cfg: &Config, output: &OutputOpts) {
+    let (template, sep) = output_parts(output);
+    for group in find_duplicates(paths, vis) {
+        for path in &group.paths {
+            let path = if cfg.misc.list_absolute_paths {
+                path
+            } else {
+                path.strip_prefix(__cwd()).unwrap_or(path)
+            };
+            print(path, &template, &sep);
+        }
+        print!("{sep}");
+    }
+}
+
 /// fd-backed listing (`fs [pattern] --list`): spawn fd, stream-filter, print.
 pub fn fd_list(
     vis: Visibility,
@@ -73,27 +97,42 @@ pub fn fd_list(
         build_fd_args(vis, types, paths, fd_args, &cfg.global.fd),
     );
 
+    let (template, output_sep) = output_parts(output);
+    let list_absolute_paths = cfg.misc.list_absolute_paths;
+    let emit = |path: PathBuf| {
+        let path = if list_absolute_paths {
+            __cwd().join(path)
+        } else {
+            path
+        };
+        let push = vis.post_fd_filter(&path);
+
+        if push {
+            print(&path, &template, &output_sep)
+        }
+    };
+
+    if use_native_fd(&cfg.global.fd) {
+        let native = NativeFd::parse(&args)?;
+        // print from one thread at a time so records are never interleaved
+        let printing = Mutex::new(());
+        native.walk(__cwd(), |path| {
+            let _guard = printing.lock().unwrap();
+            emit(path);
+            true
+        });
+        return Ok(());
+    }
+
     let (_child, stdout) = match Command::new(prog).args(args).spawn_piped()._ebog() {
         Some(s) => s,
         None => return Err(CliError::Handled),
     };
 
-    let (template, output_sep) = output_parts(output);
-    let list_absolute_paths = cfg.misc.list_absolute_paths;
-
     let _ = map_chunks::<CliError>(
         read_to_chunks(stdout, '\0'),
         move |line| {
-            let path = if list_absolute_paths {
-                __cwd().join(PathBuf::from(line))
-            } else {
-                PathBuf::from(line)
-            };
-            let push = vis.post_fd_filter(&path);
-
-            if push {
-                print(&path, &template, &output_sep)
-            }
+            emit(PathBuf::from(line));
             Ok(())
         },
         true,
@@ -133,14 +172,42 @@ pub fn rg_list(
         ),
     );
 
+    let (template, output_sep) = output_parts(output);
+    let list_absolute_paths = cfg.misc.list_absolute_paths;
+
+    if use_native_rg(&cfg.global.rg) {
+        let Some(native) = NativeRg::parse(&args)? else {
+            return Ok(());
+        };
+        let printing = Mutex::new(());
+        native.search(__cwd(), |file| {
+            let path = if list_absolute_paths {
+                __cwd().join(&file.path)
+            } else {
+                file.path
+            };
+            if vis.post_fd_filter(&path) {
+                // rg's one-line layout prints the path once per match
+                let times = if no_heading {
+                    file.lines.iter().filter(|l| l.is_match).count()
+                } else {
+                    1
+                };
+                let _guard = printing.lock().unwrap();
+                for _ in 0..times {
+                    print(&path, &template, &output_sep);
+                }
+            }
+            true
+        });
+        return Ok(());
+    }
+
     let (_child, stdout) = match Command::new(prog).args(args).spawn_piped()._ebog() {
         Some(s) => s,
         None => return Err(CliError::Handled),
     };
 
-    let (template, output_sep) = output_parts(output);
-    let list_absolute_paths = cfg.misc.list_absolute_paths;
-
     if no_heading {
         let mut path_buffer = String::new();
         let _ = map_reader_lines::<CliError>(
@@ -231,21 +298,28 @@ pub fn rg_list(
 /// db-backed directory listing (`fs :d --list`): rows are already ordered by
 /// the query (`sort`), so this only formats and prints them.
 ///
-/// Non-UTF8 paths are skipped unless `mode` is [`ListMode::All`].
+/// Non-UTF8 paths are skipped unless `mode` is [`ListMode::All`]. Only
+/// entries visited within `range` are listed.
 pub async fn dirs_list(
     conn: &mut Connection,
     sort: SortOrder,
     db_filter: &HistoryConfig,
+    range: VisitRange,
     mode: ListMode,
     output: &OutputOpts,
 ) -> Result<(), CliError> {
     let (template, sep) = output_parts(output);
     let all = matches!(mode, ListMode::All);
 
-    for e in conn
-        .get_entries(sort, db_filter, crate::db::DbTable::dirs)
-        .await?
-    {
+    let mut entries = conn
+        .get_entries(sort, db_filter, crate::db::DbTable::dirs, None)
+        .await?;
+    if !range.is_unbounded() {
+        let visited = conn.visited_paths(range).await?;
+        entries.retain(|e| visited.contains(&e.path));
+    }
+
+    for e in entries {
         match e.path.to_str() {
             Some(_) => print(&e.path, &template, &sep),
             None => {
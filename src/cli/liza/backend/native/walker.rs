@@ -37,7 +37,7 @@ fn needs_metadata(config: &LizaConfig) -> bool {
         || config.view_mode == Some(ViewMode::Dirs)
 }
 
-fn format_symbolic_permissions(mode: u32, is_dir: bool) -> String {
+pub(crate) fn format_symbolic_permissions(mode: u32, is_dir: bool) -> String {
     let d = if is_dir { 'd' } else { '-' };
     let r1 = if mode & 0o400 != 0 { 'r' } else { '-' };
     let w1 = if mode & 0o200 != 0 { 'w' } else { '-' };
@@ -3,10 +3,12 @@ mod clap;
 mod clap_;
 pub mod clap_helpers;
 pub mod clap_tools;
+mod db;
 mod ds;
 pub mod handlers;
 pub mod list;
 pub mod paths;
+mod stash;
 
 pub use clap::*;
 pub use clap_::*;
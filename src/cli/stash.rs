@@ -0,0 +1,100 @@
+//! `fs :tool stash` — export, import and list the stored stashes.
+
+use std::{ffi::OsString, io::Read};
+
+use cba::{StringError, bath::PathExt, prints};
+use clap::{Parser, Subcommand};
+
+use crate::{
+    cli::paths::current_exe,
+    config::{Config, StashPaneKind},
+    db::{DbTable, Pool},
+    errors::CliError,
+    run::stash::{StashFormat, StashOp, export_entries, import_entries},
+};
+
+#[derive(Parser, Debug)]
+#[command(about = "Export, import and list stashes")]
+pub struct StashArgs {
+    #[command(subcommand)]
+    pub cmd: StashCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum StashCmd {
+    /// Print the entries of a stash, newest first.
+    Export {
+        /// Stash name (default: the unnamed stash).
+        #[arg(default_value = "")]
+        name: String,
+        #[arg(short, long, default_value_t, value_enum)]
+        format: StashFormat,
+    },
+    /// Add the entries read from stdin to a stash.
+    Import {
+        /// Stash name (default: the unnamed stash).
+        #[arg(default_value = "")]
+        name: String,
+        #[arg(short, long, default_value_t, value_enum)]
+        format: StashFormat,
+        /// Replace the stash instead of adding to it.
+        #[arg(short, long)]
+        replace: bool,
+    },
+    /// List the stored stashes with their entry counts.
+    Ls,
+}
+
+/// Entry point for `fs :tool stash`.
+pub async fn handle(mut args: Vec<OsString>, cfg: &Config) -> Result<(), CliError> {
+    let path = current_exe().basename();
+    args.insert(0, format!("{path} :tool stash").into());
+    let StashArgs { cmd } = StashArgs::parse_from(args);
+
+    let pool = Pool::new_from_cfg(cfg).await?;
+    let mut conn = pool.get_conn(DbTable::stashes).await?;
+
+    match cmd {
+        StashCmd::Export { name, format } => {
+            ensure_stored(cfg, &name)?;
+            let entries = conn.get_stash_entries(&name).await?;
+            print!("{}", export_entries(&entries, format)?);
+        }
+        StashCmd::Import {
+            name,
+            format,
+            replace,
+        } => {
+            ensure_stored(cfg, &name)?;
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            let imported = import_entries(&input, format)?;
+            // imported entries take precedence, updating the tails of
+            // paths already stashed
+            let entries = if replace {
+                imported
+            } else {
+                StashOp::Union.apply(imported, conn.get_stash_entries(&name).await?)
+            };
+            conn.set_stash_entries(&name, &entries).await?;
+        }
+        StashCmd::Ls => {
+            for (name, count) in conn.stash_counts().await? {
+                prints!(format!("{name}\t{count}"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Transient stashes only live inside a running instance.
+fn ensure_stored(cfg: &Config, name: &str) -> Result<(), CliError> {
+    if cfg.global.panes.stash_setting(name).kind == StashPaneKind::Transient {
+        return Err(StringError::from(format!(
+            "Stash '{name}' is transient: set its kind to filter or prune in \
+             [panes.stashes] to store it."
+        ))
+        .into());
+    }
+    Ok(())
+}
@@ -19,10 +19,12 @@ use fist_types::When;
 mod pager;
 mod panes;
 mod partial;
+mod searches;
 mod styles;
 pub use pager::*;
 pub use panes::*;
 pub use partial::*;
+pub use searches::*;
 pub mod ui;
 use ui::StyleConfig;
 // ------ CONFIG ------
@@ -88,6 +90,10 @@ pub struct GlobalConfig {
     /// Configure various pane related settings.
     pub panes: PanesConfig,
 
+    /// Named Find and Search invocations (`[searches.NAME]`), opened by
+    /// [FsAction::SavedSearch](`crate::run::FsAction::SavedSearch`) and `fs :s NAME`.
+    pub searches: SavedSearches,
+
     /// Matchmaker styling overrides (per-pane).
     /// [Warning!]: Unstable and untested.
     pub mm: MatchmakerOverrides,
@@ -177,6 +183,8 @@ pub struct InterfaceConfig {
     pub cwd_prompt: String,
     /// Display a toast when current directory has no entries.
     pub toast_on_empty: bool,
+    /// Mark the items of Nav, Find and Custom panes with their git status in the second column.
+    pub git_status: bool,
     /// If [AutoJump](`crate::run::FsAction::AutoJump`) should accept or advance
     pub autojump_advance: bool,
 }
@@ -190,6 +198,7 @@ impl Default for InterfaceConfig {
             advance_command: Preset::Edit.to_command_string(When::Auto),
             cwd_prompt: "{} ".into(),
             toast_on_empty: true,
+            git_status: true,
             autojump_advance: false,
             prompt_locking: false,
             #[cfg(target_os = "macos")]
@@ -232,6 +241,11 @@ pub struct FdConfig {
     ///
     /// Additionally, when this setting is not Never, hidden visibility is automatically turned on when starting a nav pane in a directory containing only hidden files.
     pub dot_query_show_hidden: When,
+    /// Walk with the built-in walker instead of spawning fd.
+    /// - Auto: only when fd is not installed.
+    /// - Always: also when fd is installed. Only the common fd flags are supported.
+    /// - Never: always use fd.
+    pub native: When,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -250,6 +264,11 @@ pub struct RgConfig {
     pub empty_pattern: Option<String>,
     /// The set of arguments applied to the end of `fs :` when no `rg_args` were given.
     pub default_args: Vec<String>,
+    /// Search with the built-in searcher instead of spawning rg.
+    /// - Auto: only when rg is not installed.
+    /// - Always: also when rg is installed, saving a process spawn on each query. Only the common rg flags are supported.
+    /// - Never: always use rg.
+    pub native: When,
 }
 
 impl Default for RgConfig {
@@ -264,6 +283,7 @@ impl Default for RgConfig {
             ],
             empty_pattern: None,
             default_args: Default::default(),
+            native: When::Auto,
         }
     }
 }
@@ -276,6 +296,10 @@ pub struct FsConfig {
     /// absolute paths before the worker restart and rehydrated once the
     /// fresh listing lands (see [`crate::run::selection`]).
     pub refill_selections_after_reload: bool,
+    /// Number of operations (e.g. search-and-replace runs) whose overwritten
+    /// files are kept for [`FsAction::UndoOperation`](crate::run::FsAction::UndoOperation).
+    /// 0 keeps all.
+    pub journal_max: usize,
 }
 
 impl Default for FsConfig {
@@ -283,6 +307,7 @@ impl Default for FsConfig {
         Self {
             rename_policy: Default::default(),
             refill_selections_after_reload: true,
+            journal_max: 20,
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, path::PathBuf};
 
-use crate::run::FsPane;
+use crate::run::{FsPane, meta::MetaColumn};
 use fist_types::filters::*;
 use matchmaker::config::ShowCondition;
 
@@ -111,6 +111,8 @@ pub struct FdPaneSettings {
     /// If the execution time is less than this threshold (in milliseconds), enable the directory watcher to auto-refresh the pane on changes.
     #[serde(with = "crate::watcher::serde_duration_ms")]
     pub max_refresh_execution_time_threshold: std::time::Duration,
+    /// Metadata columns shown after the path (see [`crate::run::meta`]).
+    pub metadata: Vec<MetaColumn>,
 }
 
 impl Default for FdPaneSettings {
@@ -124,6 +126,7 @@ impl Default for FdPaneSettings {
             on_leave_unset_dirs_only: false,
             max_refresh_items_threshold: 20000,
             max_refresh_execution_time_threshold: std::time::Duration::from_millis(400), // a generous default threshold to be sure it's working
+            metadata: Vec::new(),
         }
     }
 }
@@ -198,6 +201,12 @@ pub struct NavPaneSettings {
     /// Default visibility.
     /// - When None: show hidden files and hide ignored files when inside a git repository and the inverse otherwise
     pub default_visibility: Option<PartialVisibility>,
+    /// Miller columns: the parent directory is listed in the header with the current one
+    /// highlighted, the results list the current directory, and the preview on their side shows
+    /// the child listing or the file (see [`crate::run::columns`]).
+    pub columns: bool,
+    /// Metadata columns shown after the path (see [`crate::run::meta`]).
+    pub metadata: Vec<MetaColumn>,
 }
 
 impl Default for NavPaneSettings {
@@ -210,6 +219,8 @@ impl Default for NavPaneSettings {
 
             default_sort: Some(SortOrder::mtime),
             default_visibility: Default::default(),
+            columns: false,
+            metadata: Vec::new(),
         }
     }
 }
@@ -310,8 +321,8 @@ impl PanesConfig {
     pub fn prompt(&self, pane: &FsPane) -> Option<String> {
         match pane {
             FsPane::Custom { .. } => self.custom.prompt.clone(),
-            FsPane::Find { .. } => self.find.prompt.clone(),
-            FsPane::Files { .. } | FsPane::Folders { .. } => self.history.prompt.clone(),
+            FsPane::Find { .. } | FsPane::Dupes { .. } => self.find.prompt.clone(),
+            FsPane::Files { .. } | FsPane::Folders { .. } | FsPane::Aliases { .. } => self.history.prompt.clone(),
             FsPane::Apps { .. } => self.app.prompt.clone(),
             FsPane::Nav { .. } => self.nav.prompt.clone(),
             FsPane::Search { .. } => self.search.prompt.clone(),
@@ -322,8 +333,8 @@ impl PanesConfig {
     pub fn locks_prompt(&self, pane: &FsPane) -> Option<bool> {
         match pane {
             FsPane::Custom { .. } => self.custom.lock_prompt,
-            FsPane::Find { .. } => self.find.lock_prompt,
-            FsPane::Files { .. } | FsPane::Folders { .. } => self.history.lock_prompt,
+            FsPane::Find { .. } | FsPane::Dupes { .. } => self.find.lock_prompt,
+            FsPane::Files { .. } | FsPane::Folders { .. } | FsPane::Aliases { .. } => self.history.lock_prompt,
             FsPane::Apps { .. } => self.app.lock_prompt,
             FsPane::Nav { .. } => self.nav.lock_prompt,
             FsPane::Search { .. } => self.search.lock_prompt,
@@ -334,8 +345,8 @@ impl PanesConfig {
     pub fn show_preview(&self, pane: &FsPane) -> Option<ShowCondition> {
         match pane {
             FsPane::Custom { .. } => self.custom.show_preview,
-            FsPane::Find { .. } => self.find.show_preview,
-            FsPane::Files { .. } | FsPane::Folders { .. } => self.history.show_preview,
+            FsPane::Find { .. } | FsPane::Dupes { .. } => self.find.show_preview,
+            FsPane::Files { .. } | FsPane::Folders { .. } | FsPane::Aliases { .. } => self.history.show_preview,
             FsPane::Apps { .. } => self.app.show_preview,
             FsPane::Nav { .. } => self.nav.show_preview,
             FsPane::Search { .. } => self.search.show_preview,
@@ -350,8 +361,9 @@ impl PanesConfig {
             | FsPane::Apps { .. }
             | FsPane::Files { .. }
             | FsPane::Folders { .. }
-            | FsPane::Stash { .. } => None,
-            FsPane::Find { .. } => self.find.default_visibility,
+            | FsPane::Stash { .. }
+            | FsPane::Aliases { .. } => None,
+            FsPane::Find { .. } | FsPane::Dupes { .. } => self.find.default_visibility,
             FsPane::Nav { .. } => self.nav.default_visibility,
             FsPane::Search { .. } => self.search.default_visibility,
         }
@@ -369,8 +381,8 @@ impl PanesConfig {
     pub fn preview_layout_index(&self, pane: &FsPane) -> u8 {
         match pane {
             FsPane::Custom { .. } => self.custom.preview_layout_index,
-            FsPane::Find { .. } => self.find.preview_layout_index,
-            FsPane::Files { .. } | FsPane::Folders { .. } => self.history.preview_layout_index,
+            FsPane::Find { .. } | FsPane::Dupes { .. } => self.find.preview_layout_index,
+            FsPane::Files { .. } | FsPane::Folders { .. } | FsPane::Aliases { .. } => self.history.preview_layout_index,
             FsPane::Apps { .. } => self.app.preview_layout_index,
             FsPane::Nav { .. } => self.nav.preview_layout_index,
             FsPane::Search { .. } => self.search.preview_layout_index,
@@ -31,8 +31,8 @@ impl MatchmakerOverrides {
     pub fn get(&self, pane: &FsPane) -> &PartialRenderConfig {
         match pane {
             FsPane::Custom { .. } => &self.custom,
-            FsPane::Find { .. } => &self.find,
-            FsPane::Files { .. } | FsPane::Folders { .. } => &self.history,
+            FsPane::Find { .. } | FsPane::Dupes { .. } => &self.find,
+            FsPane::Files { .. } | FsPane::Folders { .. } | FsPane::Aliases { .. } => &self.history,
             FsPane::Apps { .. } => &self.app,
             FsPane::Nav { .. } => &self.nav,
             FsPane::Search { .. } => &self.search,
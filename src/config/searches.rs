@@ -0,0 +1,264 @@
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use cba::{StringError, bath::PathExt};
+
+use super::GlobalConfig;
+use crate::{
+    abspath::AbsPath,
+    cli::paths::__home,
+    find::fd::resolve_group_types,
+    lessfilter::LessfilterConfig,
+    run::FsPane,
+};
+use fist_types::{
+    When,
+    filetypes::FileTypeArg,
+    filters::{PartialVisibility, SortOrder},
+};
+
+/// The pane a [`SavedSearch`] opens.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum SavedSearchKind {
+    /// A fd search ([`FsPane::Find`]).
+    #[default]
+    Find,
+    /// A full text rg search ([`FsPane::Search`]).
+    Search,
+}
+
+/// A named Find or Search invocation: `[searches.NAME]`.
+/// Opened by [`FsAction::SavedSearch`](crate::run::FsAction::SavedSearch) and `fs :s NAME`.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SavedSearch {
+    pub kind: SavedSearchKind,
+    /// Directory to search from (`~` expands to the home directory).
+    /// Default: the current directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// Files or directories to search in, relative to `cwd`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<PathBuf>,
+    /// Find: the fd pattern, multiple patterns are matched as alternatives
+    /// (default: everything). Search: the rg patterns.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+    /// Find only: file types, categories and extensions, as given to `-t`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+    /// Args passed on verbatim to fd or rg.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Visibility, on top of the pane's `default_visibility`.
+    #[serde(skip_serializing_if = "PartialVisibility::is_default")]
+    pub vis: PartialVisibility,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<SortOrder>,
+    /// Initial query. For Search, this starts the pane filtering the matches.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub query: String,
+}
+
+pub type SavedSearches = HashMap<String, SavedSearch>;
+
+impl SavedSearch {
+    /// The configured `cwd`, with `~` expanded.
+    pub fn cwd(&self) -> Option<AbsPath> {
+        let cwd = self.cwd.as_ref()?;
+        let cwd = match cwd.strip_prefix("~") {
+            Ok(rest) => __home().join(rest),
+            Err(_) => cwd.clone(),
+        };
+        Some(AbsPath::new(cwd))
+    }
+
+    /// The fd types, with custom groups resolved against `lcfg`.
+    pub fn types(&self, lcfg: &LessfilterConfig) -> Vec<FileTypeArg> {
+        let types: Vec<FileTypeArg> = self.types.iter().filter_map(|t| t.parse().ok()).collect();
+        if types.iter().any(|t| matches!(t, FileTypeArg::Group(_))) {
+            resolve_group_types(&types, lcfg)
+        } else {
+            types
+        }
+    }
+
+    /// fd's paths argument of [`FsPane::Find`]: the search paths followed by the pattern.
+    pub fn fd_paths(&self) -> Vec<OsString> {
+        let mut paths: Vec<OsString> = self.paths.iter().map(|p| p.into()).collect();
+        paths.push(match self.patterns.as_slice() {
+            [] => ".".into(),
+            [pattern] => pattern.into(),
+            patterns => format!("({})", patterns.join("|")).into(),
+        });
+        paths
+    }
+
+    /// The pane running this search, from the configured `cwd` or else `cwd`.
+    pub fn to_pane(&self, cwd: AbsPath, cfg: &GlobalConfig, lcfg: &LessfilterConfig) -> FsPane {
+        let cwd = self.cwd().unwrap_or(cwd);
+        let args = self.args.iter().map(OsString::from).collect();
+        match self.kind {
+            SavedSearchKind::Find => {
+                let vis = self.vis.into_resolved(cfg.panes.find.default_visibility);
+                let mut pane = FsPane::new_fd_full(
+                    cwd,
+                    vis,
+                    self.sort,
+                    self.types(lcfg),
+                    self.fd_paths(),
+                    args,
+                    None,
+                );
+                if let FsPane::Find { input, .. } = &mut pane {
+                    input.0 = self.query.clone();
+                }
+                pane
+            }
+            SavedSearchKind::Search => {
+                let search = &cfg.panes.search;
+                let vis = self.vis.into_resolved(search.default_visibility);
+                let paths = self.paths.iter().map(|p| p.abs(&cwd)).collect();
+                FsPane::new_rg(
+                    cwd,
+                    self.sort.or(search.default_sort).unwrap_or_default(),
+                    vis,
+                    paths,
+                    self.query.clone(),
+                    self.patterns.clone(),
+                    !self.query.is_empty(),
+                    [0, 0],
+                    When::Auto,
+                    search.one_line,
+                    search.fixed_strings,
+                    args,
+                )
+            }
+        }
+    }
+
+    /// Capture a Find or Search pane, with `query` as the initial query.
+    pub fn from_pane(pane: &FsPane, query: String) -> Option<Self> {
+        let os_strings = |v: &[OsString]| -> Vec<String> {
+            v.iter().map(|s| s.to_string_lossy().into_owned()).collect()
+        };
+        match pane {
+            FsPane::Find {
+                cwd,
+                sort,
+                vis,
+                types,
+                paths,
+                fd_args,
+                ..
+            } => {
+                let (pattern, paths) = match paths.split_last() {
+                    Some((pattern, paths)) => (pattern.to_string_lossy(), paths),
+                    None => (Default::default(), &[][..]),
+                };
+                Some(Self {
+                    kind: SavedSearchKind::Find,
+                    cwd: Some(cwd.inner()),
+                    paths: paths.iter().map(PathBuf::from).collect(),
+                    patterns: if pattern.is_empty() || pattern == "." {
+                        vec![]
+                    } else {
+                        vec![pattern.into_owned()]
+                    },
+                    types: types.iter().map(ToString::to_string).collect(),
+                    args: os_strings(fd_args),
+                    vis: (*vis).into(),
+                    sort: Some(*sort),
+                    query,
+                })
+            }
+            FsPane::Search {
+                cwd,
+                sort,
+                vis,
+                paths,
+                patterns,
+                rg,
+                ..
+            } => Some(Self {
+                kind: SavedSearchKind::Search,
+                cwd: Some(cwd.inner()),
+                paths: paths.clone(),
+                patterns: patterns.clone(),
+                types: vec![],
+                args: os_strings(rg),
+                vis: (*vis).into(),
+                sort: Some(*sort),
+                query,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The `[searches.NAME]` table of this search.
+    pub fn to_toml(&self, name: &str) -> Result<String, StringError> {
+        let table = HashMap::from([("searches", HashMap::from([(name, self)]))]);
+        toml::to_string(&table).map_err(|e| e.to_string().into())
+    }
+
+    /// Append this search as `[searches.NAME]` to the config file at `path`.
+    pub fn append_to(&self, name: &str, path: &Path) -> Result<(), StringError> {
+        let table = self.to_toml(name)?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| StringError::from(e.to_string()))?;
+        write!(file, "\n{table}").map_err(|e| e.to_string().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_search_toml_roundtrip() {
+        let search = SavedSearch {
+            kind: SavedSearchKind::Search,
+            cwd: Some("~/src".into()),
+            patterns: vec!["TODO".into()],
+            args: vec!["--glob=!vendor".into()],
+            sort: Some(SortOrder::mtime),
+            ..Default::default()
+        };
+
+        let s = search.to_toml("todo list").unwrap();
+        assert!(s.starts_with("[searches.\"todo list\"]"), "{s}");
+
+        let parsed: HashMap<String, SavedSearches> = toml::from_str(&s).unwrap();
+        assert_eq!(parsed["searches"]["todo list"], search);
+        assert_eq!(search.cwd(), Some(AbsPath::new(__home().join("src"))));
+    }
+
+    #[test]
+    fn test_saved_search_fd_paths() {
+        let mut search = SavedSearch {
+            paths: vec!["src".into()],
+            ..Default::default()
+        };
+        assert_eq!(search.fd_paths(), ["src", "."]);
+
+        search.patterns = vec!["a".into(), "b".into()];
+        assert_eq!(search.fd_paths(), ["src", "(a|b)"]);
+    }
+}
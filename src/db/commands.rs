@@ -0,0 +1,140 @@
+//! History of the commands run from the interface (`Execute`, `ExecPaged`, `ExecTTY` and
+//! `MenuAction`), backing the [`crate::run::FsAction::CommandHistory`] palette.
+
+use sqlx::prelude::FromRow;
+
+use super::{Connection, DbTable, Epoch, Pool};
+use crate::{abspath::AbsPath, errors::DbError, run::state::TASKS};
+
+/// How a recorded command was run: determines how it is re-run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum CommandKind {
+    Execute,
+    Paged,
+    Tty,
+    /// A menu action: the template is the action key.
+    Menu,
+}
+
+/// Row of the `commands` table.
+#[derive(Debug, Clone, FromRow)]
+pub struct CommandRecord {
+    pub id: i64,
+    /// The template (or menu action key) as invoked.
+    pub template: String,
+    /// The command which ran: the formatted template, or the menu action's lua command.
+    pub command: String,
+    /// See [`CommandKind`].
+    pub kind: String,
+    pub cwd: Option<AbsPath>,
+    /// Exit code. For paged commands, 0 or 1 as reported by the pager; `None` when unknown.
+    pub status: Option<i32>,
+    pub duration_ms: i64,
+    pub time: Epoch,
+}
+
+impl CommandRecord {
+    pub fn new(template: String, command: String, kind: CommandKind) -> Self {
+        Self {
+            id: 0,
+            template,
+            command,
+            kind: kind.to_string(),
+            cwd: None,
+            status: None,
+            duration_ms: 0,
+            time: chrono::Local::now().timestamp(),
+        }
+    }
+
+    pub fn kind(&self) -> Option<CommandKind> {
+        self.kind.parse().ok()
+    }
+}
+
+impl Pool {
+    /// Spawn a background task that appends a command to the command history.
+    pub fn record_command(&'static self, record: CommandRecord) {
+        TASKS::spawn("db record command", async move {
+            // the commands table is shared across history tables
+            match self.get_conn(DbTable::files).await {
+                Ok(mut conn) => {
+                    if let Err(e) = conn.log_command(&record).await {
+                        log::error!("Error recording command: {}", e);
+                    }
+                }
+                Err(e) => {
+                    log::error!("Error getting connection: {}", e);
+                }
+            }
+        });
+    }
+}
+
+impl Connection {
+    pub async fn log_command(&mut self, record: &CommandRecord) -> Result<(), DbError> {
+        sqlx::query(
+            "INSERT INTO commands (template, command, kind, cwd, status, duration_ms, time)
+            VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&record.template)
+        .bind(&record.command)
+        .bind(&record.kind)
+        .bind(&record.cwd)
+        .bind(record.status)
+        .bind(record.duration_ms)
+        .bind(record.time)
+        .execute(&mut *self.conn)
+        .await?;
+        Ok(())
+    }
+
+    /// The latest run of each distinct (template, kind), most recent first.
+    /// `limit`: 0 for all.
+    pub async fn get_command_history(
+        &mut self,
+        limit: usize,
+    ) -> Result<Vec<CommandRecord>, DbError> {
+        let limit = if limit == 0 { -1 } else { limit as i64 };
+        Ok(sqlx::query_as(
+            "SELECT * FROM commands
+            WHERE id IN (SELECT MAX(id) FROM commands GROUP BY template, kind)
+            ORDER BY id DESC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&mut *self.conn)
+        .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_command_history_dedup() {
+        let pool = Pool::new("sqlite::memory:", None).await.unwrap();
+        let mut conn = pool.get_conn(DbTable::files).await.unwrap();
+
+        let record = |command: &str, kind| CommandRecord::new("wc -l {}".into(), command.into(), kind);
+
+        let mut first = record("wc -l a", CommandKind::Execute);
+        first.status = Some(0);
+        conn.log_command(&first).await.unwrap();
+        conn.log_command(&record("wc -l a", CommandKind::Paged)).await.unwrap();
+        let mut again = record("wc -l b", CommandKind::Execute);
+        again.cwd = Some(AbsPath::new("/tmp"));
+        conn.log_command(&again).await.unwrap();
+
+        let history = conn.get_command_history(0).await.unwrap();
+        assert_eq!(history.len(), 2);
+        // the latest run of each (template, kind)
+        assert_eq!(history[0].command, "wc -l b");
+        assert_eq!(history[0].kind(), Some(CommandKind::Execute));
+        assert_eq!(history[0].cwd, Some(AbsPath::new("/tmp")));
+        assert_eq!(history[1].kind(), Some(CommandKind::Paged));
+
+        assert_eq!(conn.get_command_history(1).await.unwrap().len(), 1);
+    }
+}
@@ -14,6 +14,8 @@ pub struct Pool {
     pub pool: SqlitePool,
     /// EMS decay constant. `None` for wall-clock atime mode, `Some(λ)` for EMS tick scoring.
     pub lambda: Option<f64>,
+    /// See [`crate::db::zoxide::HistoryConfig::visits_max`]. 0 disables pruning.
+    pub visits_max: usize,
 }
 
 pub struct Connection {
@@ -22,6 +24,8 @@ pub struct Connection {
     pub table: DbTable,
     /// EMS decay constant. `None` for wall-clock atime mode, `Some(λ)` for EMS tick scoring.
     pub lambda: Option<f64>,
+    /// Maximum number of visits kept, enforced by [`Connection::log_visit`].
+    pub visits_max: usize,
 }
 
 #[allow(non_camel_case_types)]
@@ -38,7 +42,8 @@ impl Pool {
     pub async fn new_from_cfg(cfg: &crate::config::Config) -> Result<Self, DbError> {
         let path = cfg.db_path();
         let existed = path.exists();
-        let pool = Pool::new(&path, cfg.history.lambda).await?;
+        let mut pool = Pool::new(&path, cfg.history.lambda).await?;
+        pool.visits_max = cfg.history.visits_max;
 
         // Seed default directories only if this is a fresh database
         if !existed {
@@ -94,7 +99,11 @@ impl Pool {
         let options: SqliteConnectOptions = url.parse()?;
         let pool = SqlitePoolOptions::new().connect_with(options).await?;
 
-        let ret = Self { pool, lambda };
+        let ret = Self {
+            pool,
+            lambda,
+            visits_max: 0,
+        };
         ret.init_tables().await?;
         Ok(ret)
     }
@@ -133,6 +142,76 @@ impl Pool {
         .execute(&mut *conn.conn)
         .await?;
 
+        // visit counts per originating project root, see `HistoryConfig::context_boost`
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS contexts (
+                path BLOB NOT NULL,
+                kind TEXT NOT NULL,
+                root BLOB NOT NULL,
+                count INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (path, kind, root)
+            )",
+        )
+        .execute(&mut *conn.conn)
+        .await?;
+
+        // append-only visit log, see `visits.rs`
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS visits (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                path BLOB NOT NULL,
+                kind TEXT NOT NULL,
+                pane TEXT NOT NULL DEFAULT '',
+                time INTEGER NOT NULL
+            )",
+        )
+        .execute(&mut *conn.conn)
+        .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS visits_time ON visits (time)")
+            .execute(&mut *conn.conn)
+            .await?;
+
+        // commands run from the interface, see `commands.rs`
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS commands (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                template TEXT NOT NULL,
+                command TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                cwd BLOB,
+                status INTEGER,
+                duration_ms INTEGER NOT NULL DEFAULT 0,
+                time INTEGER NOT NULL
+            )",
+        )
+        .execute(&mut *conn.conn)
+        .await?;
+
+        // directory marks, see `marks.rs`
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS marks (
+                mark TEXT PRIMARY KEY NOT NULL,
+                path BLOB NOT NULL,
+                time INTEGER NOT NULL
+            )",
+        )
+        .execute(&mut *conn.conn)
+        .await?;
+
+        // file contents overwritten by an operation, see `journal.rs`
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS journal (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                op INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                path BLOB NOT NULL,
+                content BLOB NOT NULL,
+                time INTEGER NOT NULL
+            )",
+        )
+        .execute(&mut *conn.conn)
+        .await?;
+
         Ok(())
     }
 
@@ -142,6 +221,7 @@ impl Pool {
             conn,
             table,
             lambda: self.lambda,
+            visits_max: self.visits_max,
         };
 
         log::trace!("db connected");
@@ -149,6 +149,31 @@ impl Connection {
         Ok(())
     }
 
+    /// The entry whose alias is exactly `alias`, most recently accessed first.
+    pub async fn get_entry_by_alias(&mut self, alias: &str) -> Result<Option<Entry>, DbError> {
+        let sql = format!(
+            "SELECT * FROM {} WHERE alias = ? ORDER BY atime DESC LIMIT 1",
+            self.table
+        );
+        let entry = sqlx::query_as::<_, Entry>(sqlx::AssertSqlSafe(sql))
+            .bind(alias)
+            .fetch_optional(&mut *self.conn)
+            .await?;
+        Ok(entry)
+    }
+
+    /// All entries with a non-empty alias, ordered by alias.
+    pub async fn get_aliased_entries(&mut self) -> Result<Vec<Entry>, DbError> {
+        let sql = format!(
+            "SELECT * FROM {} WHERE alias != '' ORDER BY alias",
+            self.table
+        );
+        sqlx::query_as::<_, Entry>(sqlx::AssertSqlSafe(sql))
+            .fetch_all(&mut *self.conn)
+            .await
+            .cast()
+    }
+
     pub async fn set_cmd(&mut self, path: &AbsPath, cmd: &OsStringWrapper) -> Result<(), DbError> {
         let sql = format!("UPDATE {} SET cmd = ? WHERE path = ?", self.table);
         sqlx::query(sqlx::AssertSqlSafe(sql))
@@ -175,25 +200,34 @@ impl Connection {
         trace!("Bumping {}", entry.path.display());
 
         if let Some(lambda) = self.lambda {
-            let mut query = sqlx::QueryBuilder::new("UPDATE ");
-            query.push(self.table);
-            query.push(" SET score = ");
-            query.push_bind(entry.score);
-            query.push(" * exp(-");
-            query.push_bind(lambda);
-            query.push(" * (COALESCE((SELECT MAX(atime) FROM ");
-            query.push(self.table);
-            query.push("), 0) + 1 - ");
-            query.push_bind(entry.atime);
-            query.push(")) + ");
-            query.push_bind(count as f64);
-            query.push(", atime = COALESCE((SELECT MAX(atime) FROM ");
-            query.push(self.table);
-            query.push("), 0) + 1, count = ");
-            query.push_bind(entry.count + count);
-            query.push(" WHERE path = ");
-            query.push_bind(&entry.path);
-            query.build().execute(&mut *self.conn).await?;
+            // decayed in rust: the bundled sqlite has no math functions. The stored row and the
+            // tick are read in the write transaction, so concurrent bumps don't lose updates.
+            let mut tx = sqlx::Connection::begin_with(&mut *self.conn, "BEGIN IMMEDIATE").await?;
+            let sql = format!(
+                "SELECT score, atime, count, (SELECT MAX(atime) FROM {0}) FROM {0} WHERE path = ?",
+                self.table
+            );
+            let row: Option<(f64, Epoch, i32, Option<Epoch>)> =
+                sqlx::query_as(sqlx::AssertSqlSafe(sql))
+                    .bind(&entry.path)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if let Some((score, atime, stored, max)) = row {
+                let tick = max.unwrap_or(0) + 1;
+                let score = score * (-lambda * (tick - atime) as f64).exp() + count as f64;
+                let sql = format!(
+                    "UPDATE {} SET score = ?, atime = ?, count = ? WHERE path = ?",
+                    self.table
+                );
+                sqlx::query(sqlx::AssertSqlSafe(sql))
+                    .bind(score)
+                    .bind(tick)
+                    .bind(stored + count)
+                    .bind(&entry.path)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            tx.commit().await?;
         } else {
             let now = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
@@ -262,11 +296,8 @@ impl Connection {
             SortOrder::none => {
                 // Frecency = decayed score
                 if let Some(lambda) = self.lambda {
-                    // EMS
-                    format!(
-                        "ORDER BY score * exp(-{} * ((SELECT MAX(atime) FROM {}) - atime)) DESC",
-                        lambda, self.table
-                    )
+                    // EMS: sorted in rust, see [`Self::get_entries_by_ems`]
+                    return self.get_entries_by_ems(start, end, lambda).await;
                 } else {
                     // zoxide: bucketed scoring based on age
                     format!(
@@ -299,6 +330,35 @@ impl Connection {
             .await
             .cast()
     }
+
+    /// Entries ordered by their EMS score decayed to the latest tick.
+    /// The decay needs `exp`, which the bundled sqlite does not provide, so the whole table is
+    /// sorted here before the range is taken.
+    async fn get_entries_by_ems(
+        &mut self,
+        start: u32,
+        end: u32,
+        lambda: f64,
+    ) -> Result<Vec<Entry>, DbError> {
+        let now = self.get_max_atime().await?;
+        let sql = format!("SELECT * FROM {}", self.table);
+        let mut entries: Vec<Entry> = sqlx::query_as::<_, Entry>(sqlx::AssertSqlSafe(sql))
+            .fetch_all(&mut *self.conn)
+            .await?;
+
+        entries.sort_by_cached_key(|e| std::cmp::Reverse(zoxide::score(now, e, Some(lambda))));
+
+        let take = if end == 0 {
+            usize::MAX
+        } else {
+            end.saturating_sub(start) as usize
+        };
+        Ok(entries
+            .into_iter()
+            .skip(start as usize)
+            .take(take)
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -478,7 +538,6 @@ mod tests {
 
         // Artificially advance the tick by inserting a dummy entry and bumping many times
         for i in 0..100 {
-            let dummy_path = AbsPath::new(format!("/dummy_{i}"));
             db.set_entry(&entry_at(&format!("/dummy_{i}"), (2 + i) as Epoch))
                 .await
                 .unwrap();
@@ -493,6 +552,47 @@ mod tests {
         assert!(e2.score < score_after_first + 10.0); // not a huge jump
     }
 
+    #[tokio::test]
+    async fn test_ems_bump_reads_stored_row() {
+        let mut db = setup_db_ems().await;
+        let path = AbsPath::new("/test_ems_stale");
+
+        let entry = entry_at("/test_ems_stale", 0);
+        db.set_entry(&entry).await.unwrap();
+
+        // both bumps see the same (stale) entry, as concurrent bumps would
+        db.bump_entry(&entry, 1).await.unwrap();
+        db.bump_entry(&entry, 1).await.unwrap();
+        let e = db.get_entry(&path).await.unwrap().unwrap();
+        assert_eq!(e.count, 3);
+        assert_eq!(e.atime, 3);
+        assert!(e.score > 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_ems_ranking_decays_old_scores() {
+        let mut db = setup_db_ems().await;
+
+        let mut old = entry_at("/old", 0);
+        old.score = 5.0;
+        db.set_entry(&old).await.unwrap();
+        db.update_score_and_atime(&old.path, 5.0, 0).await.unwrap();
+
+        let mut recent = entry_at("/recent", 0);
+        recent.score = 2.0;
+        db.set_entry(&recent).await.unwrap();
+        db.update_score_and_atime(&recent.path, 2.0, 1000).await.unwrap();
+
+        // 5 * exp(-8e-3 * 1000) < 2
+        let ranked = db.get_entries_range(0, 0, SortOrder::none).await.unwrap();
+        let paths: Vec<_> = ranked.iter().map(|e| e.path.clone()).collect();
+        assert_eq!(paths, [recent.path.clone(), old.path.clone()]);
+
+        // the limit applies after ranking
+        let top = db.get_entries_range(0, 1, SortOrder::none).await.unwrap();
+        assert_eq!(top[0].path, recent.path);
+    }
+
     #[tokio::test]
     async fn test_ems_get_max_atime() {
         let mut db = setup_db_ems().await;
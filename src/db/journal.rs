@@ -0,0 +1,124 @@
+//! Operation journal: the contents of files before an operation overwrote them, backing
+//! [`crate::run::FsAction::UndoOperation`].
+
+use sqlx::Acquire;
+
+use super::{Connection, Epoch};
+use crate::{abspath::AbsPath, errors::DbError};
+
+/// The files backed up by one journaled operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalOp {
+    pub op: i64,
+    /// What overwrote the files, e.g. `replace`.
+    pub kind: String,
+    pub time: Epoch,
+    /// The paths with their original contents.
+    pub files: Vec<(AbsPath, Vec<u8>)>,
+}
+
+impl Connection {
+    /// Record the original contents of `files` as a new operation, then drop all
+    /// but the `keep` latest operations (0 keeps all). Returns the operation id.
+    pub async fn journal_backup(
+        &mut self,
+        kind: &str,
+        files: &[(AbsPath, Vec<u8>)],
+        keep: usize,
+    ) -> Result<i64, DbError> {
+        let time = chrono::Local::now().timestamp();
+        let mut tx = self.conn.begin().await?;
+
+        let (op,): (i64,) = sqlx::query_as("SELECT COALESCE(MAX(op), 0) + 1 FROM journal")
+            .fetch_one(&mut *tx)
+            .await?;
+        for (path, content) in files {
+            sqlx::query(
+                "INSERT INTO journal (op, kind, path, content, time) VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(op)
+            .bind(kind)
+            .bind(path)
+            .bind(content)
+            .bind(time)
+            .execute(&mut *tx)
+            .await?;
+        }
+        if keep > 0 {
+            sqlx::query("DELETE FROM journal WHERE op <= ?")
+                .bind(op - keep as i64)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(op)
+    }
+
+    /// The latest journaled operation.
+    pub async fn journal_last(&mut self) -> Result<Option<JournalOp>, DbError> {
+        let rows: Vec<(i64, String, Epoch, AbsPath, Vec<u8>)> = sqlx::query_as(
+            "SELECT op, kind, time, path, content FROM journal
+            WHERE op = (SELECT MAX(op) FROM journal) ORDER BY id",
+        )
+        .fetch_all(&mut *self.conn)
+        .await?;
+
+        let Some((op, kind, time, ..)) = rows.first().cloned() else {
+            return Ok(None);
+        };
+        Ok(Some(JournalOp {
+            op,
+            kind,
+            time,
+            files: rows.into_iter().map(|(.., path, content)| (path, content)).collect(),
+        }))
+    }
+
+    /// Remove an operation from the journal, once it is undone.
+    pub async fn journal_drop(&mut self, op: i64) -> Result<(), DbError> {
+        sqlx::query("DELETE FROM journal WHERE op = ?")
+            .bind(op)
+            .execute(&mut *self.conn)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{DbTable, Pool};
+
+    #[tokio::test]
+    async fn test_journal_backup_and_prune() {
+        let pool = Pool::new("sqlite::memory:", None).await.unwrap();
+        let mut conn = pool.get_conn(DbTable::files).await.unwrap();
+        assert_eq!(conn.journal_last().await.unwrap(), None);
+
+        let a = AbsPath::new("/tmp/a");
+        let b = AbsPath::new("/tmp/b");
+        let first = conn
+            .journal_backup("replace", &[(a.clone(), b"one".to_vec())], 2)
+            .await
+            .unwrap();
+        let files = [(a.clone(), b"two".to_vec()), (b.clone(), b"".to_vec())];
+        conn.journal_backup("replace", &files, 2).await.unwrap();
+        let third = conn
+            .journal_backup("replace", &[(b.clone(), b"three".to_vec())], 2)
+            .await
+            .unwrap();
+        assert_eq!(third, first + 2);
+
+        let last = conn.journal_last().await.unwrap().unwrap();
+        assert_eq!(last.op, third);
+        assert_eq!(last.files, [(b.clone(), b"three".to_vec())]);
+
+        // undoing exposes the previous operation; the first one was pruned
+        conn.journal_drop(third).await.unwrap();
+        let last = conn.journal_last().await.unwrap().unwrap();
+        assert_eq!(last.files, files);
+        conn.journal_drop(last.op).await.unwrap();
+        assert_eq!(conn.journal_last().await.unwrap(), None);
+    }
+}
@@ -0,0 +1,222 @@
+//! Health and upkeep of the database, backing `fs :tool db`.
+
+use super::{Connection, DbTable, Epoch, zoxide};
+use crate::{abspath::AbsPath, errors::DbError};
+use fist_types::filters::SortOrder;
+
+/// Statistics of a single table.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct TableStats {
+    pub table: String,
+    pub rows: i64,
+    /// Bytes used by the table and its indices, when the `dbstat` virtual table is available.
+    pub bytes: Option<i64>,
+    /// History tables only: entries whose path no longer exists.
+    pub missing: Option<usize>,
+    /// History tables only: the event-clock position (EMS mode) or the latest atime.
+    pub clock: Option<Epoch>,
+    /// History tables only: the distribution of the live scores used for ranking.
+    pub scores: Option<ScoreStats>,
+}
+
+impl TableStats {
+    /// Fraction of the rows which are missing.
+    pub fn missing_share(&self) -> Option<f64> {
+        self.missing
+            .map(|m| if self.rows == 0 { 0.0 } else { m as f64 / self.rows as f64 })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize)]
+pub struct ScoreStats {
+    pub min: i32,
+    pub median: i32,
+    pub p90: i32,
+    pub max: i32,
+    pub mean: f64,
+}
+
+impl ScoreStats {
+    /// `None` if `scores` is empty.
+    pub fn new(mut scores: Vec<i32>) -> Option<Self> {
+        if scores.is_empty() {
+            return None;
+        }
+        scores.sort_unstable();
+        let at = |q: f64| scores[((scores.len() - 1) as f64 * q).round() as usize];
+        Some(Self {
+            min: scores[0],
+            median: at(0.5),
+            p90: at(0.9),
+            max: scores[scores.len() - 1],
+            mean: scores.iter().map(|&s| s as f64).sum::<f64>() / scores.len() as f64,
+        })
+    }
+}
+
+/// Every table, in display order. History tables come first.
+pub const STAT_TABLES: [&str; 9] = [
+    "apps", "files", "dirs", "stashes", "contexts", "visits", "commands", "marks", "journal",
+];
+
+impl Connection {
+    /// Row count, size and (for the history tables) health of the named table.
+    pub async fn table_stats(&mut self, name: &str) -> Result<TableStats, DbError> {
+        let sql = format!("SELECT COUNT(*) FROM {name}");
+        let (rows,): (i64,) = sqlx::query_as(sqlx::AssertSqlSafe(sql))
+            .fetch_one(&mut *self.conn)
+            .await?;
+
+        // the bundled SQLite enables dbstat, but a system one might not
+        let bytes: Option<(Option<i64>,)> = sqlx::query_as(
+            "SELECT SUM(pgsize) FROM dbstat
+            WHERE name IN (SELECT name FROM sqlite_schema WHERE tbl_name = ?)",
+        )
+        .bind(name)
+        .fetch_optional(&mut *self.conn)
+        .await
+        .ok()
+        .flatten();
+
+        let mut stats = TableStats {
+            table: name.to_string(),
+            rows,
+            bytes: bytes.and_then(|(b,)| b),
+            ..Default::default()
+        };
+
+        let table = match name {
+            "apps" => DbTable::apps,
+            "files" => DbTable::files,
+            "dirs" => DbTable::dirs,
+            _ => return Ok(stats),
+        };
+        self.switch_table(table);
+
+        let entries = self.get_entries_range(0, 0, SortOrder::mtime).await?;
+        let now = self.score_time().await?;
+        stats.missing = Some(entries.iter().filter(|e| !e.path.exists()).count());
+        stats.clock = entries.iter().map(|e| e.atime).max();
+        stats.scores = ScoreStats::new(
+            entries
+                .iter()
+                .map(|e| zoxide::score(now, e, self.lambda))
+                .collect(),
+        );
+
+        Ok(stats)
+    }
+
+    /// Reference time for [`zoxide::score`]: the event clock in EMS mode, else the wall clock.
+    async fn score_time(&mut self) -> Result<Epoch, DbError> {
+        if self.lambda.is_some() {
+            self.get_max_atime().await
+        } else {
+            Ok(chrono::Utc::now().timestamp())
+        }
+    }
+
+    /// Missing entries of the current table with score below `score_threshold`
+    /// (any score if 0): the entries removed by [`Self::prune_missing`].
+    pub async fn missing_below(&mut self, score_threshold: i32) -> Result<Vec<AbsPath>, DbError> {
+        let entries = self.get_entries_range(0, 0, SortOrder::mtime).await?;
+        let now = self.score_time().await?;
+
+        Ok(entries
+            .into_iter()
+            .filter(|e| {
+                (score_threshold == 0 || zoxide::score(now, e, self.lambda) < score_threshold)
+                    && !e.path.exists()
+            })
+            .map(|e| e.path)
+            .collect())
+    }
+
+    /// The problems reported by `PRAGMA integrity_check`, empty if the database is sound.
+    pub async fn integrity_check(&mut self) -> Result<Vec<String>, DbError> {
+        let rows: Vec<(String,)> = sqlx::query_as("PRAGMA integrity_check")
+            .fetch_all(&mut *self.conn)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(r,)| r)
+            .filter(|r| r != "ok")
+            .collect())
+    }
+
+    /// Size of the database in bytes, as reported by its page count.
+    pub async fn db_size(&mut self) -> Result<i64, DbError> {
+        let (size,): (i64,) = sqlx::query_as(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+        )
+        .fetch_one(&mut *self.conn)
+        .await?;
+        Ok(size)
+    }
+
+    /// Rebuild the database file, releasing free pages.
+    pub async fn vacuum(&mut self) -> Result<(), DbError> {
+        sqlx::query("VACUUM").execute(&mut *self.conn).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Pool;
+
+    #[test]
+    fn test_score_stats() {
+        assert_eq!(ScoreStats::new(vec![]), None);
+        let stats = ScoreStats::new(vec![5, 1, 3, 2, 4]).unwrap();
+        assert_eq!((stats.min, stats.median, stats.max), (1, 3, 5));
+        assert_eq!(stats.mean, 3.0);
+    }
+
+    #[tokio::test]
+    async fn test_table_stats_and_prune_candidates() {
+        let pool = Pool::new("sqlite::memory:", Some(8e-3)).await.unwrap();
+        let mut conn = pool.get_conn(DbTable::dirs).await.unwrap();
+
+        let tmp = tempfile::tempdir().unwrap();
+        let present = AbsPath::new(tmp.path());
+        let missing = AbsPath::new("/this/path/does/not/exist");
+        conn.bump_path(present.clone(), 1).await.unwrap();
+        conn.bump_path(missing.clone(), 1).await.unwrap();
+
+        let stats = conn.table_stats("dirs").await.unwrap();
+        assert_eq!(stats.rows, 2);
+        assert_eq!(stats.missing, Some(1));
+        assert_eq!(stats.missing_share(), Some(0.5));
+        assert!(stats.scores.is_some());
+        assert!(stats.bytes.is_some_and(|b| b > 0));
+
+        let stats = conn.table_stats("visits").await.unwrap();
+        assert_eq!(stats.rows, 0);
+        assert_eq!(stats.missing, None);
+
+        conn.set_mark('a', &present).await.unwrap();
+        let stats = conn.table_stats("marks").await.unwrap();
+        assert_eq!(stats.rows, 1);
+        assert_eq!(stats.missing, None);
+
+        // every table is listed, and exists
+        let tables: Vec<(String,)> = sqlx::query_as(
+            "SELECT name FROM sqlite_schema WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+        )
+        .fetch_all(&mut *conn.conn)
+        .await
+        .unwrap();
+        for (table,) in &tables {
+            assert!(STAT_TABLES.contains(&table.as_str()), "{table} is not listed");
+        }
+        for table in STAT_TABLES {
+            conn.table_stats(table).await.unwrap();
+        }
+
+        conn.switch_table(DbTable::dirs);
+        assert_eq!(conn.missing_below(0).await.unwrap(), vec![missing]);
+        assert!(conn.integrity_check().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+//! Single-character directory marks, backing [`crate::run::FsAction::SetMark`] and
+//! [`crate::run::FsAction::JumpMark`].
+
+use sqlx::prelude::FromRow;
+
+use super::{Connection, Epoch};
+use crate::{abspath::AbsPath, errors::DbError};
+
+/// Row of the `marks` table.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct Mark {
+    pub mark: String,
+    pub path: AbsPath,
+    /// When the mark was last set.
+    pub time: Epoch,
+}
+
+impl Connection {
+    /// Point `mark` at `path`, replacing its previous target.
+    pub async fn set_mark(
+        &mut self,
+        mark: char,
+        path: &AbsPath,
+    ) -> Result<(), DbError> {
+        sqlx::query(
+            "INSERT INTO marks (mark, path, time) VALUES (?, ?, ?)
+            ON CONFLICT (mark) DO UPDATE SET path = excluded.path, time = excluded.time",
+        )
+        .bind(mark.to_string())
+        .bind(path)
+        .bind(chrono::Local::now().timestamp())
+        .execute(&mut *self.conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_mark(
+        &mut self,
+        mark: char,
+    ) -> Result<Option<AbsPath>, DbError> {
+        let row: Option<(AbsPath,)> = sqlx::query_as("SELECT path FROM marks WHERE mark = ?")
+            .bind(mark.to_string())
+            .fetch_optional(&mut *self.conn)
+            .await?;
+        Ok(row.map(|(path,)| path))
+    }
+
+    /// All marks, ordered by mark.
+    pub async fn get_marks(&mut self) -> Result<Vec<Mark>, DbError> {
+        Ok(sqlx::query_as("SELECT * FROM marks ORDER BY mark")
+            .fetch_all(&mut *self.conn)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{DbTable, Pool};
+
+    #[tokio::test]
+    async fn test_marks() {
+        let pool = Pool::new("sqlite::memory:", None).await.unwrap();
+        let mut conn = pool.get_conn(DbTable::dirs).await.unwrap();
+
+        assert_eq!(conn.get_mark('a').await.unwrap(), None);
+
+        conn.set_mark('b', &AbsPath::new("/usr")).await.unwrap();
+        conn.set_mark('a', &AbsPath::new("/tmp")).await.unwrap();
+        conn.set_mark('a', &AbsPath::new("/var")).await.unwrap();
+
+        assert_eq!(
+            conn.get_mark('a').await.unwrap(),
+            Some(AbsPath::new("/var"))
+        );
+        // marks are case-sensitive
+        assert_eq!(conn.get_mark('A').await.unwrap(), None);
+
+        let marks: Vec<(String, AbsPath)> = conn
+            .get_marks()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| (m.mark, m.path))
+            .collect();
+        assert_eq!(
+            marks,
+            [
+                ("a".to_string(), AbsPath::new("/var")),
+                ("b".to_string(), AbsPath::new("/usr"))
+            ]
+        );
+    }
+}
@@ -2,23 +2,49 @@ mod entry;
 pub use entry::*;
 mod connection;
 pub use connection::*;
+mod commands;
+pub use commands::*;
 mod crud;
+mod journal;
+pub use journal::*;
+mod marks;
+pub use marks::*;
+mod maintenance;
+pub use maintenance::*;
+mod visits;
+pub use visits::*;
 
 pub mod zoxide;
 
-use crate::{abspath::AbsPath, errors::DbError, run::state::TASKS};
+use crate::{
+    abspath::AbsPath,
+    errors::DbError,
+    run::{
+        FsAction,
+        state::{GLOBAL, STACK, TASKS},
+    },
+};
 use cba::{bait::ResultExt, bath::PathExt};
 use fist_types::filters::SortOrder;
+use sqlx::Acquire;
 
 use crud::MAX_PLACEHOLDERS;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::{
+    collections::HashMap,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 pub type Epoch = i64;
 
 impl Pool {
     /// Spawn a background task that records a directory or file visit.
     /// `folder`: `true` for dirs table, `false` for files.
+    /// The visit is also recorded against the project root of the current directory, and
+    /// appended to the visit log.
     pub fn bump_path(&'static self, folder: bool, path: AbsPath) {
+        let origin = STACK::cwd();
+        let pane = STACK::pane_name().unwrap_or("cli");
         TASKS::spawn("db bump path", async move {
             let table = if folder {
                 DbTable::dirs
@@ -28,9 +54,17 @@ impl Pool {
 
             match self.get_conn(table).await {
                 Ok(mut conn) => {
-                    if let Err(e) = conn.bump_path(path, 1).await {
+                    if let Err(e) = conn.bump_path(path.clone(), 1).await {
                         log::error!("Error bumping entry: {}", e);
                     }
+                    if let Some(root) = context_root(origin.as_deref().map(AsRef::as_ref))
+                        && let Err(e) = conn.record_context(&path, &root).await
+                    {
+                        log::error!("Error recording context: {}", e);
+                    }
+                    if let Err(e) = conn.log_visit(&path, pane).await {
+                        log::error!("Error logging visit: {}", e);
+                    }
                 }
                 Err(e) => {
                     log::error!("Error getting connection: {}", e);
@@ -53,6 +87,8 @@ impl Pool {
                     if let Err(e) = conn.set_alias(&path, &alias).await {
                         log::error!("Error setting alias: {}", e);
                     }
+                    // the history and aliases panes display it
+                    GLOBAL::send_action(FsAction::Reload);
                 }
                 Err(e) => {
                     log::error!("Error getting connection: {}", e);
@@ -68,13 +104,16 @@ impl Connection {
         paths: impl IntoIterator<Item = AbsPath>,
     ) -> Result<(), DbError> {
         // Delegate to the new function with an amount of 1
-        self.bump_files_and_folders_n(paths, 1).await
+        self.bump_files_and_folders_n(paths, 1, None).await
     }
 
+    /// Visits (`count > 0`) are also recorded against the project root of `origin` (the current
+    /// directory when `None`), like [`Pool::bump_path`].
     pub async fn bump_files_and_folders_n(
         &mut self,
         paths: impl IntoIterator<Item = AbsPath>,
         count: i32,
+        origin: Option<&Path>,
     ) -> Result<(), DbError> {
         let mut files = Vec::new();
         let mut dirs = Vec::new();
@@ -93,14 +132,30 @@ impl Connection {
             // }
         }
 
+        // demotions are not visits
+        let log = count > 0;
+        let root = if log { context_root(origin) } else { None };
+
         self.switch_table(DbTable::dirs);
         for f in dirs {
+            if log {
+                self.log_visit(&f, "cli").await?;
+            }
+            if let Some(root) = &root {
+                self.record_context(&f, root).await?;
+            }
             self.bump_path(f, count).await?;
             // todo: maybe should also update the abspath?
         }
 
         self.switch_table(DbTable::files);
         for f in files {
+            if log {
+                self.log_visit(&f, "cli").await?;
+            }
+            if let Some(root) = &root {
+                self.record_context(&f, root).await?;
+            }
             self.bump_path(f, count).await?;
         }
 
@@ -135,11 +190,13 @@ impl Connection {
         }
     }
 
+    /// `context`: the directory the entries are requested from, see [`Self::rank_by_context`].
     pub async fn get_entries(
         &mut self,
         sort: SortOrder,
         config: &zoxide::HistoryConfig,
         table: DbTable,
+        context: Option<&Path>,
     ) -> Result<Vec<Entry>, DbError> {
         let mut entries = self.get_entries_range(0, 0, sort).await.elog()?;
         entries.retain(|e| config.filter(e, table));
@@ -151,6 +208,12 @@ impl Connection {
             entries.truncate(config.prune_min);
         }
 
+        if matches!(sort, SortOrder::none)
+            && let Some(ranked) = self.rank_by_context(&entries, context, config).await
+        {
+            entries = ranked;
+        }
+
         Ok(entries)
     }
 
@@ -199,6 +262,58 @@ impl Connection {
     }
 }
 
+/// Project root (nearest VCS root) of `dir`, or of the process cwd when `None`.
+/// Visits are recorded against, and boosted by, this root: see
+/// [`zoxide::HistoryConfig::context_boost`].
+pub fn context_root(dir: Option<&Path>) -> Option<AbsPath> {
+    let dir = match dir {
+        Some(d) => d.to_path_buf(),
+        None => std::env::current_dir().ok()?,
+    };
+    fist_types::git::repo_root(&dir).map(AbsPath::new)
+}
+
+// ----------------------------------------------------------------
+// contexts table — visit counts per originating project root
+// ----------------------------------------------------------------
+
+impl Connection {
+    /// Count a visit to `path` (in the current table) as originating from `root`.
+    pub async fn record_context(&mut self, path: &AbsPath, root: &AbsPath) -> Result<(), DbError> {
+        sqlx::query(
+            "INSERT INTO contexts (path, kind, root, count) VALUES (?, ?, ?, 1)
+            ON CONFLICT (path, kind, root) DO UPDATE SET count = count + 1",
+        )
+        .bind(path)
+        .bind(self.table.to_string())
+        .bind(root)
+        .execute(&mut *self.conn)
+        .await?;
+        Ok(())
+    }
+
+    /// For each path of the current table visited from `root`, the fraction of its recorded
+    /// visits which originated there.
+    pub async fn get_context_shares(
+        &mut self,
+        root: &AbsPath,
+    ) -> Result<HashMap<AbsPath, f64>, DbError> {
+        let rows: Vec<(AbsPath, i64, i64)> = sqlx::query_as(
+            "SELECT path, SUM(CASE WHEN root = ? THEN count ELSE 0 END) AS here, SUM(count)
+            FROM contexts WHERE kind = ? GROUP BY path HAVING here > 0",
+        )
+        .bind(root)
+        .bind(self.table.to_string())
+        .fetch_all(&mut *self.conn)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(path, here, total)| (path, here as f64 / total.max(1) as f64))
+            .collect())
+    }
+}
+
 // ----------------------------------------------------------------
 // stashes table — backing store of the [`crate::run::FsPane::Stash`] pane
 // ----------------------------------------------------------------
@@ -260,6 +375,40 @@ impl Connection {
         Ok(total)
     }
 
+    /// Replace the entries of the named stash with `entries`, keeping their
+    /// tails and add times. An empty `entries` clears the stash.
+    pub async fn set_stash_entries(
+        &mut self,
+        name: &str,
+        entries: &[StashEntry],
+    ) -> Result<(), DbError> {
+        let mut tx = self.conn.begin().await?;
+        sqlx::query("DELETE FROM stashes WHERE name = ?")
+            .bind(name)
+            .execute(&mut *tx)
+            .await?;
+        for e in entries {
+            sqlx::query("INSERT INTO stashes (name, stash, tail, add_time) VALUES (?, ?, ?, ?)")
+                .bind(name)
+                .bind(&e.stash)
+                .bind(&e.tail)
+                .bind(e.add_time)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// The names of the stored stashes with their entry counts, by name.
+    pub async fn stash_counts(&mut self) -> Result<Vec<(String, i64)>, DbError> {
+        Ok(
+            sqlx::query_as("SELECT name, COUNT(*) FROM stashes GROUP BY name ORDER BY name")
+                .fetch_all(&mut *self.conn)
+                .await?,
+        )
+    }
+
     /// Whether the named stash already contains an entry for `path`.
     pub async fn stash_has_entry(&mut self, name: &str, path: &AbsPath) -> Result<bool, DbError> {
         let row: Option<(i64,)> =
@@ -317,4 +466,135 @@ mod tests {
         assert_eq!(removed, 1);
         assert!(!conn.stash_has_entry(name, &a).await.unwrap());
     }
+
+    #[tokio::test]
+    async fn test_set_stash_entries() {
+        let mut conn = setup_stash_conn().await;
+        conn.add_stash_entry("other", &AbsPath::new("/stash/a")).await.unwrap();
+        let entries = vec![StashEntry {
+            id: 0,
+            name: "other".into(),
+            stash: AbsPath::new("/stash/b"),
+            tail: "note".into(),
+            add_time: 7,
+        }];
+
+        conn.set_stash_entries("mine", &entries).await.unwrap();
+        let stored = conn.get_stash_entries("mine").await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!((stored[0].name.as_str(), stored[0].tail.as_str()), ("mine", "note"));
+        assert_eq!(stored[0].add_time, 7);
+
+        conn.set_stash_entries("mine", &[]).await.unwrap();
+        assert!(conn.get_stash_entries("mine").await.unwrap().is_empty());
+        // other stashes are untouched
+        assert_eq!(conn.get_stash_entries("other").await.unwrap().len(), 1);
+        assert_eq!(conn.stash_counts().await.unwrap(), [("other".to_string(), 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_rank_by_context_prefers_root() {
+        let pool = Pool::new("sqlite::memory:", Some(8e-3)).await.unwrap();
+        let mut conn = pool.get_conn(DbTable::dirs).await.unwrap();
+
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        let root = AbsPath::new(repo.path());
+
+        let global = AbsPath::new("/ctx/global/foo");
+        let local = AbsPath::new("/ctx/repo/foo");
+        for _ in 0..3 {
+            conn.bump_path(global.clone(), 1).await.unwrap();
+        }
+        conn.bump_path(local.clone(), 1).await.unwrap();
+        conn.record_context(&local, &root).await.unwrap();
+
+        let entries = conn.get_entries_range(0, 0, SortOrder::none).await.unwrap();
+        assert_eq!(entries[0].path, global);
+
+        let mut config = zoxide::HistoryConfig::default();
+        assert!(
+            conn.rank_by_context(&entries, Some(repo.path()), &config)
+                .await
+                .is_none()
+        );
+
+        config.context_boost = 10.0;
+        let ranked = conn
+            .rank_by_context(&entries, Some(repo.path()), &config)
+            .await
+            .unwrap();
+        assert_eq!(ranked[0].path, local);
+    }
+
+    #[tokio::test]
+    async fn test_cli_bump_records_context() {
+        let pool = Pool::new("sqlite::memory:", Some(8e-3)).await.unwrap();
+        let mut conn = pool.get_conn(DbTable::dirs).await.unwrap();
+
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        let dirs = tempfile::tempdir().unwrap();
+        let global = AbsPath::new(dirs.path().join("global"));
+        let local = AbsPath::new(dirs.path().join("local"));
+        std::fs::create_dir(&global).unwrap();
+        std::fs::create_dir(&local).unwrap();
+
+        for _ in 0..3 {
+            conn.bump_path(global.clone(), 1).await.unwrap();
+        }
+        // a jump made from inside the repo, as by the shell hook
+        conn.bump_files_and_folders_n([local.clone()], 1, Some(repo.path()))
+            .await
+            .unwrap();
+        // demotions are not visits
+        conn.bump_files_and_folders_n([global.clone()], -1, Some(repo.path()))
+            .await
+            .unwrap();
+
+        conn.switch_table(DbTable::dirs);
+        let entries = conn.get_entries_range(0, 0, SortOrder::none).await.unwrap();
+        assert_eq!(entries[0].path, global);
+
+        let mut config = zoxide::HistoryConfig::default();
+        config.context_boost = 10.0;
+        let ranked = conn
+            .rank_by_context(&entries, Some(repo.path()), &config)
+            .await
+            .unwrap();
+        assert_eq!(ranked[0].path, local);
+        let shares = conn.get_context_shares(&AbsPath::new(repo.path())).await.unwrap();
+        assert_eq!(shares.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_best_by_alias() {
+        let pool = Pool::new("sqlite::memory:", Some(8e-3)).await.unwrap();
+        let mut conn = pool.get_conn(DbTable::dirs).await.unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = AbsPath::new(dir.path());
+        conn.bump_path(path.clone(), 1).await.unwrap();
+        conn.set_alias(&path, "work").await.unwrap();
+
+        let config = zoxide::HistoryConfig::default();
+        let by_alias = |kw: &str| config.clone().with_keywords(vec![kw.to_string()]);
+
+        let found = conn.best_by_alias(&by_alias("work")).await.unwrap();
+        assert_eq!(found.map(|e| e.path), Some(path.clone()));
+        let found = conn.best_by_alias(&by_alias("@work")).await.unwrap();
+        assert_eq!(found.map(|e| e.path), Some(path.clone()));
+        assert!(conn.best_by_alias(&by_alias("play")).await.unwrap().is_none());
+
+        // the alias hit goes through the same filter as frecency matches
+        let mut elsewhere = by_alias("work");
+        elsewhere.base_dir = Some("/fist-elsewhere".into());
+        assert!(conn.best_by_alias(&elsewhere).await.unwrap().is_none());
+        drop(dir);
+        assert!(conn.best_by_alias(&by_alias("work")).await.unwrap().is_none());
+
+        // the sigil alone names no alias, rather than every unaliased entry
+        assert_eq!(by_alias("@").alias_query(), None);
+        assert!(conn.best_by_alias(&by_alias("@")).await.unwrap().is_none());
+    }
 }
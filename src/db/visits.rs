@@ -0,0 +1,295 @@
+//! Append-only visit log.
+//!
+//! The history tables only keep the latest atime and a count per path; the
+//! `visits` table additionally records every visit with its wall-clock time
+//! and originating pane, so that history can be queried by date.
+
+use std::collections::HashSet;
+
+use cba::StringError;
+use chrono::{Datelike, Local, NaiveDate, TimeDelta};
+use sqlx::prelude::FromRow;
+
+use super::{Connection, Epoch};
+use crate::{abspath::AbsPath, errors::DbError};
+
+/// Row of the `visits` table.
+#[derive(Debug, Clone, FromRow)]
+pub struct Visit {
+    pub id: i64,
+    pub path: AbsPath,
+    /// The history table the visit was recorded against (files/dirs).
+    pub kind: String,
+    /// Name of the pane the visit originated from, or `cli`.
+    pub pane: String,
+    pub time: Epoch,
+}
+
+/// A half-open interval `[since, until)` of wall-clock seconds.
+/// Unbounded on the sides which are `None`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct VisitRange {
+    pub since: Option<Epoch>,
+    pub until: Option<Epoch>,
+}
+
+impl VisitRange {
+    /// The local calendar day `date`.
+    pub fn day(date: NaiveDate) -> Self {
+        Self {
+            since: Some(local_midnight(date)),
+            until: date.succ_opt().map(local_midnight),
+        }
+    }
+
+    /// From the local start of `date` onwards.
+    pub fn since_day(date: NaiveDate) -> Self {
+        Self {
+            since: Some(local_midnight(date)),
+            until: None,
+        }
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.since.is_none() && self.until.is_none()
+    }
+
+    pub fn contains(&self, time: Epoch) -> bool {
+        self.since.is_none_or(|s| time >= s) && self.until.is_none_or(|u| time < u)
+    }
+}
+
+/// Ranges offered by the history panes' options overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangePreset {
+    Any,
+    Today,
+    Yesterday,
+    Week,
+    Month,
+}
+
+impl RangePreset {
+    pub const ALL: [Self; 5] = [
+        Self::Any,
+        Self::Today,
+        Self::Yesterday,
+        Self::Week,
+        Self::Month,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Any => "Any time",
+            Self::Today => "Today",
+            Self::Yesterday => "Yesterday",
+            Self::Week => "Week",
+            Self::Month => "Month",
+        }
+    }
+
+    /// Week and month are the last 7 and 30 days, including today.
+    pub fn range(self) -> VisitRange {
+        let today = Local::now().date_naive();
+        match self {
+            Self::Any => VisitRange::default(),
+            Self::Today => VisitRange::since_day(today),
+            Self::Yesterday => VisitRange::day(today - TimeDelta::days(1)),
+            Self::Week => VisitRange::since_day(today - TimeDelta::days(6)),
+            Self::Month => VisitRange::since_day(today - TimeDelta::days(29)),
+        }
+    }
+}
+
+fn local_midnight(date: NaiveDate) -> Epoch {
+    let midnight = date.and_time(chrono::NaiveTime::MIN);
+    midnight
+        .and_local_timezone(Local)
+        .earliest()
+        .map(|dt| dt.timestamp())
+        // midnight skipped by a DST transition
+        .unwrap_or_else(|| midnight.and_utc().timestamp())
+}
+
+/// Parse a day relative to `today`: `today`, `yesterday`, a weekday name (the most recent
+/// one before today), `<N>d` (N days ago), or an ISO date (`YYYY-MM-DD`).
+pub fn parse_day(s: &str, today: NaiveDate) -> Result<NaiveDate, StringError> {
+    let s = s.trim().to_lowercase();
+    let days_ago = |n: u64| {
+        today
+            .checked_sub_days(chrono::Days::new(n))
+            .ok_or_else(|| StringError::from(format!("Date out of range: {s}")))
+    };
+
+    match s.as_str() {
+        "today" => return Ok(today),
+        "yesterday" => return days_ago(1),
+        _ => {}
+    }
+
+    if let Ok(weekday) = s.parse::<chrono::Weekday>() {
+        let back = (today.weekday().num_days_from_monday() + 7
+            - weekday.num_days_from_monday()
+            - 1)
+            % 7
+            + 1;
+        return days_ago(back.into());
+    }
+
+    if let Some(n) = s.strip_suffix('d').and_then(|n| n.parse().ok()) {
+        return days_ago(n);
+    }
+
+    NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(|_| {
+        format!(
+            "Invalid date '{s}': expected today, yesterday, a weekday, <N>d or YYYY-MM-DD"
+        )
+        .into()
+    })
+}
+
+impl Connection {
+    /// Append a visit to `path` in the current table, dropping the oldest beyond
+    /// [`Connection::visits_max`].
+    pub async fn log_visit(&mut self, path: &AbsPath, pane: &str) -> Result<(), DbError> {
+        sqlx::query("INSERT INTO visits (path, kind, pane, time) VALUES (?, ?, ?, ?)")
+            .bind(path)
+            .bind(self.table.to_string())
+            .bind(pane)
+            .bind(Local::now().timestamp())
+            .execute(&mut *self.conn)
+            .await?;
+        if self.visits_max != 0 {
+            self.prune_visits(self.visits_max).await?;
+        }
+        Ok(())
+    }
+
+    /// Drop the oldest visits so that at most `max` remain (across all tables).
+    /// Returns the number of rows removed.
+    pub async fn prune_visits(&mut self, max: usize) -> Result<u64, DbError> {
+        let removed = sqlx::query(
+            "DELETE FROM visits WHERE id <= (SELECT MAX(id) FROM visits) - ?",
+        )
+        .bind(max as i64)
+        .execute(&mut *self.conn)
+        .await?
+        .rows_affected();
+        Ok(removed)
+    }
+
+    /// Visits within `range`, most recent first.
+    /// `kind`: restrict to visits recorded against this table.
+    pub async fn get_visits(
+        &mut self,
+        range: VisitRange,
+        kind: Option<super::DbTable>,
+    ) -> Result<Vec<Visit>, DbError> {
+        let mut query = sqlx::QueryBuilder::new("SELECT * FROM visits WHERE 1");
+        if let Some(kind) = kind {
+            query.push(" AND kind = ").push_bind(kind.to_string());
+        }
+        if let Some(since) = range.since {
+            query.push(" AND time >= ").push_bind(since);
+        }
+        if let Some(until) = range.until {
+            query.push(" AND time < ").push_bind(until);
+        }
+        query.push(" ORDER BY time DESC, id DESC");
+
+        Ok(query
+            .build_query_as::<Visit>()
+            .fetch_all(&mut *self.conn)
+            .await?)
+    }
+
+    /// The distinct paths of the current table visited within `range`.
+    pub async fn visited_paths(&mut self, range: VisitRange) -> Result<HashSet<AbsPath>, DbError> {
+        Ok(self
+            .get_visits(range, Some(self.table))
+            .await?
+            .into_iter()
+            .map(|v| v.path)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{DbTable, Pool};
+
+    #[test]
+    fn test_parse_day() {
+        // a Sunday
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(parse_day("today", today).unwrap(), today);
+        assert_eq!(parse_day("Yesterday", today).unwrap(), date(2026, 10, 17));
+        assert_eq!(parse_day("3d", today).unwrap(), date(2026, 10, 15));
+        assert_eq!(parse_day("tuesday", today).unwrap(), date(2026, 10, 13));
+        // the same weekday resolves to the previous week
+        assert_eq!(parse_day("sun", today).unwrap(), date(2026, 10, 11));
+        assert_eq!(parse_day("2026-10-14", today).unwrap(), date(2026, 10, 14));
+        assert!(parse_day("14/10/2026", today).is_err());
+    }
+
+    #[test]
+    fn test_visit_range_day() {
+        let range = VisitRange::day(NaiveDate::from_ymd_opt(2026, 10, 14).unwrap());
+        let (since, until) = (range.since.unwrap(), range.until.unwrap());
+        assert!(range.contains(since));
+        assert!(!range.contains(until));
+        assert!(!range.contains(since - 1));
+        assert!(VisitRange::default().is_unbounded());
+    }
+
+    #[tokio::test]
+    async fn test_visits_range_and_prune() {
+        let pool = Pool::new("sqlite::memory:", None).await.unwrap();
+        let mut conn = pool.get_conn(DbTable::files).await.unwrap();
+        let a = AbsPath::new("/visits/a");
+        let b = AbsPath::new("/visits/b");
+
+        conn.log_visit(&a, "files").await.unwrap();
+        conn.log_visit(&b, "nav").await.unwrap();
+        conn.log_visit(&a, "cli").await.unwrap();
+        conn.switch_table(DbTable::dirs);
+        conn.log_visit(&b, "nav").await.unwrap();
+        conn.switch_table(DbTable::files);
+
+        let all = conn.get_visits(VisitRange::default(), None).await.unwrap();
+        assert_eq!(all.len(), 4);
+        let files = conn
+            .get_visits(VisitRange::default(), Some(DbTable::files))
+            .await
+            .unwrap();
+        assert_eq!(files.len(), 3);
+        // most recent first
+        assert_eq!(files[0].pane, "cli");
+
+        let today = VisitRange::since_day(Local::now().date_naive());
+        let visited = conn.visited_paths(today).await.unwrap();
+        assert_eq!(visited, HashSet::from([a.clone(), b.clone()]));
+
+        let future = VisitRange {
+            since: Some(Local::now().timestamp() + 3600),
+            until: None,
+        };
+        assert!(conn.visited_paths(future).await.unwrap().is_empty());
+
+        // the oldest visits are dropped first
+        assert_eq!(conn.prune_visits(2).await.unwrap(), 2);
+        let kept = conn.get_visits(VisitRange::default(), None).await.unwrap();
+        assert_eq!(kept.len(), 2);
+        assert!(kept.iter().all(|v| v.pane != "files"));
+
+        // logging prunes beyond visits_max
+        conn.visits_max = 2;
+        conn.log_visit(&a, "menu").await.unwrap();
+        let kept = conn.get_visits(VisitRange::default(), None).await.unwrap();
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].pane, "menu");
+    }
+}
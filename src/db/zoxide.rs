@@ -24,6 +24,16 @@ pub struct HistoryConfig {
     /// Default: `Some(8e-3)`. When `None`, uses wall-clock atime scoring
     /// (compatible with zoxide databases).
     pub lambda: Option<f64>,
+    /// Weight given to entries usually visited from the current project root
+    /// (the nearest ancestor containing a VCS directory). Each score is scaled
+    /// by `1 + context_boost × share`, where `share` is the fraction of the
+    /// entry's recorded visits which originated from that root.
+    /// Default: 0 (global scoring).
+    pub context_boost: f64,
+    /// A single-keyword query exactly matching an alias resolves to the aliased entry before
+    /// frecency matching. Prefixing the keyword with this sigil restricts the query to aliases.
+    /// Default: `@`.
+    pub alias_sigil: String,
 
     #[serde(skip)]
     keywords: Vec<String>,
@@ -36,6 +46,9 @@ pub struct HistoryConfig {
     pub prune_max: usize,
     /// Number of entries retained after a pruning pass.
     pub prune_min: usize,
+    /// Maximum number of visits kept in the visit log, which backs the date-range queries
+    /// (`fs :info --since`, `fs :file --on`). The oldest are dropped first. 0 disables pruning.
+    pub visits_max: usize,
 
     // --- other ---
     /// What to do when the best match by [`Connection::print_best_by_frecency`] is the current directory
@@ -64,11 +77,14 @@ impl Default for HistoryConfig {
             case_sensitive: Default::default(),
             query_strategy: Default::default(),
             lambda: Some(8e-3),
+            context_boost: 0.0,
+            alias_sigil: "@".into(),
             keywords: Default::default(),
 
             exclude: Default::default(),
             prune_max: 10000,
             prune_min: 8000,
+            visits_max: 100_000,
 
             refind: Default::default(),
 
@@ -96,6 +112,20 @@ impl HistoryConfig {
         self
     }
 
+    /// The alias named by a single-keyword query, and whether it was marked by
+    /// [`Self::alias_sigil`] (restricting the query to aliases).
+    /// `None` if the alias is empty, as every unaliased entry would match it.
+    pub fn alias_query(&self) -> Option<(&str, bool)> {
+        let [keyword] = self.keywords.as_slice() else {
+            return None;
+        };
+        let query = match keyword.strip_prefix(self.alias_sigil.as_str()) {
+            Some(alias) if !self.alias_sigil.is_empty() => (alias, true),
+            _ => (keyword.as_str(), false),
+        };
+        (!query.0.is_empty()).then_some(query)
+    }
+
     /// Filter an entry.
     /// Returns `true` to keep, `false` to exclude.
     pub fn filter(&self, entry: &Entry, table: DbTable) -> bool {
@@ -106,7 +136,7 @@ impl HistoryConfig {
             return false;
         }
 
-        if !self.filter_by_keywords(path) {
+        if !self.filter_by_alias(entry) && !self.filter_by_keywords(path) {
             log::debug!("filtered by: kw");
             return false;
         }
@@ -119,6 +149,12 @@ impl HistoryConfig {
         true
     }
 
+    /// Whether the query names the alias of the entry, which stands in for matching the keywords.
+    fn filter_by_alias(&self, entry: &Entry) -> bool {
+        self.alias_query()
+            .is_some_and(|(alias, _)| entry.alias == alias)
+    }
+
     fn filter_by_base_dir(&self, path: &Path) -> bool {
         match &self.base_dir {
             Some(base) => path.starts_with(base),
@@ -288,6 +324,22 @@ fn is_monotonic_substring(s1: &str, s2: &str) -> bool {
 // Fetching with the filter
 
 impl Connection {
+    /// Resolve a single-keyword query as an exact alias (see [`HistoryConfig::alias_query`]).
+    ///
+    /// The aliased entry is subject to the same [`HistoryConfig::filter`] as frecency matches.
+    /// `None` if the query is not an alias or the alias matched nothing.
+    pub async fn best_by_alias(
+        &mut self,
+        config: &HistoryConfig,
+    ) -> Result<Option<Entry>, DbError> {
+        let Some((alias, _)) = config.alias_query() else {
+            return Ok(None);
+        };
+        let table = self.table;
+        let found = self.get_entry_by_alias(alias).await?;
+        Ok(found.filter(|e| config.filter(e, table)))
+    }
+
     /// some optimizations on the [`Self::get_entries`] for faster printing
     ///
     /// Abuses RetryStrat as a signal:
@@ -299,13 +351,24 @@ impl Connection {
         config: &HistoryConfig,
         table: DbTable,
     ) -> RetryStrat {
+        // an alias hit goes first, and is the only candidate if the query was restricted to aliases
+        let alias = self.best_by_alias(config).await._elog().flatten();
+        let forced = config.alias_query().is_some_and(|(_, forced)| forced);
+
         let mut found = None;
 
-        let entries = self.get_entries_range(0, 0, SortOrder::none).await.__ebog();
+        let entries = if forced {
+            Vec::new()
+        } else {
+            self.get_entries_range(0, 0, SortOrder::none).await.__ebog()
+        };
 
         let maybe_cwd = std::env::current_dir().ok();
+        let ranked = self
+            .rank_by_context(&entries, maybe_cwd.as_deref(), config)
+            .await;
 
-        for e in &entries {
+        for e in alias.iter().chain(ranked.as_deref().unwrap_or(&entries)) {
             if !config.filter(e, table) {
                 continue;
             }
@@ -350,13 +413,24 @@ impl Connection {
         config: &HistoryConfig,
         table: DbTable,
     ) -> Option<AbsPath> {
+        // an alias hit goes first, and is the only candidate if the query was restricted to aliases
+        let alias = self.best_by_alias(config).await._elog().flatten();
+        let forced = config.alias_query().is_some_and(|(_, forced)| forced);
+
         let mut found = None;
 
-        let entries = self.get_entries_range(0, 0, SortOrder::none).await.__ebog();
+        let entries = if forced {
+            Vec::new()
+        } else {
+            self.get_entries_range(0, 0, SortOrder::none).await.__ebog()
+        };
 
         let maybe_cwd = std::env::current_dir().ok();
+        let ranked = self
+            .rank_by_context(&entries, maybe_cwd.as_deref(), config)
+            .await;
 
-        for e in &entries {
+        for e in alias.iter().chain(ranked.as_deref().unwrap_or(&entries)) {
             if !config.filter(e, table) {
                 continue;
             }
@@ -399,33 +473,49 @@ impl Connection {
         found.map(|e| e.path)
     }
 
-    /// Sweep all entries and remove missing entries with score below threshold.
-    /// This is a full sweep that checks filesystem existence for every entry.
-    pub async fn prune_missing(&mut self, score_threshold: i32) -> Result<u64, DbError> {
-        use crate::db::zoxide;
-        use chrono::Utc;
-
-        // Get all entries
-        let entries = self.get_entries_range(0, 0, SortOrder::mtime).await?;
+    /// Re-rank frecency-sorted `entries` by their share of visits from the project root of
+    /// `cwd` (see [`HistoryConfig::context_boost`]).
+    /// Returns `None` when the order is unchanged: the boost is disabled, or there is no root or
+    /// no visit was recorded from it.
+    pub async fn rank_by_context(
+        &mut self,
+        entries: &[Entry],
+        cwd: Option<&Path>,
+        config: &HistoryConfig,
+    ) -> Option<Vec<Entry>> {
+        if config.context_boost <= 0.0 || entries.is_empty() {
+            return None;
+        }
+        let root = crate::db::context_root(cwd)?;
+        let shares = self.get_context_shares(&root).await._elog()?;
+        if shares.is_empty() {
+            return None;
+        }
 
-        // Compute 'now' for scoring
         let now = if self.lambda.is_some() {
-            self.get_max_atime().await?
+            self.get_max_atime().await._elog()?
         } else {
-            Utc::now().timestamp()
+            chrono::Utc::now().timestamp()
         };
 
-        // Filter missing entries below threshold
-        let mut to_remove = Vec::new();
-        for entry in entries {
-            if score_threshold == 0 || zoxide::score(now, &entry, self.lambda) < score_threshold {
-                if !entry.path.exists() {
-                    to_remove.push(entry.path);
-                }
-            }
-        }
+        let mut keyed: Vec<_> = entries
+            .iter()
+            .map(|e| {
+                let share = shares.get(&e.path).copied().unwrap_or_default();
+                let key = score(now, e, self.lambda) as f64 * (1.0 + config.context_boost * share);
+                (key, e)
+            })
+            .collect();
+        // stable: ties keep the frecency order
+        keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        Some(keyed.into_iter().map(|(_, e)| e.clone()).collect())
+    }
 
-        // Remove entries
+    /// Sweep all entries and remove missing entries with score below threshold.
+    /// This is a full sweep that checks filesystem existence for every entry.
+    pub async fn prune_missing(&mut self, score_threshold: i32) -> Result<u64, DbError> {
+        let to_remove = self.missing_below(score_threshold).await?;
         self.remove_entries(&to_remove).await
     }
 }
@@ -6,7 +6,7 @@ use std::str::FromStr;
 use strum::{EnumMessage, IntoEnumIterator};
 
 use crate::{
-    db::{Entry, Epoch, zoxide},
+    db::{Entry, Epoch, Visit, zoxide},
     lessfilter::Categories,
     menu::{MenuAction, MenuActions, MenuCondition, MenuStrategy},
 };
@@ -62,7 +62,7 @@ pub fn display_entries(entries: &[Entry], lambda: Option<f64>, now: Epoch) {
         ];
         if lambda.is_some() {
             // Use the live, decayed score used to sort entries — matches the
-            // SQL `score * exp(-λ * (MAX(atime) - atime))` order-by in
+            // `score * exp(-λ * (MAX(atime) - atime))` ordering of
             // `Connection::get_entries_range`.
             row_cells.push(zoxide::score(now, entry, lambda).to_string());
         }
@@ -75,6 +75,26 @@ pub fn display_entries(entries: &[Entry], lambda: Option<f64>, now: Epoch) {
     prints!(table.to_string());
 }
 
+/// Print visits from the visit log as a formatted table to stdout.
+pub fn display_visits(visits: &[Visit]) {
+    let mut table = Table::new();
+
+    table.load_preset(UTF8_FULL);
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(Row::from(vec!["Time", "Kind", "Pane", "Path"]));
+
+    for visit in visits {
+        table.add_row(Row::from(vec![
+            display_epoch(visit.time),
+            visit.kind.clone(),
+            visit.pane.clone(),
+            visit.path.to_string_lossy().to_string(),
+        ]));
+    }
+
+    prints!(table.to_string());
+}
+
 /// Print an overview of every value the `-t`/`--types` flag accepts.
 ///
 /// File types and categories are enumerated from `fist-types` (variants,
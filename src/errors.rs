@@ -23,6 +23,8 @@ pub enum CliError {
     ConflictingFlags(&'static str, &'static str),
     #[error("Invalid sort order '{0}' for the {1} pane")]
     UnsupportedSort(String, &'static str),
+    #[error("No saved search named '{0}'")]
+    UnknownSearch(String),
     #[error(transparent)]
     MatchError(#[from] matchmaker::MatchError),
     #[error(transparent)]
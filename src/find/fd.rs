@@ -1,4 +1,10 @@
-use std::{collections::BTreeSet, ffi::OsString, path::PathBuf};
+use std::{
+    collections::BTreeSet,
+    ffi::OsString,
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::OnceLock,
+};
 
 use cba::{vec_, wbog};
 
@@ -7,7 +13,7 @@ use crate::{
     config::FdConfig,
     lessfilter::LessfilterConfig,
 };
-use fist_types::{filetypes::FileTypeArg, filters::Visibility};
+use fist_types::{When, filetypes::FileTypeArg, filters::Visibility};
 
 // probably we can avoid clones + turn into const but this is easier
 pub fn default_exclusions() -> Vec<String> {
@@ -59,6 +65,28 @@ pub fn default_home_exclusions() -> Vec<String> {
         }
     }
 }
+/// Whether fd can be spawned, checked once.
+pub fn is_fd_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        Command::new("fd")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    })
+}
+
+/// Whether to walk with [`super::native::NativeFd`] instead of fd, see `fd.native`.
+pub fn use_native_fd(cfg: &FdConfig) -> bool {
+    match cfg.native {
+        When::Always => true,
+        When::Never => false,
+        When::Auto => !is_fd_available(),
+    }
+}
+
 pub fn build_fd_args(
     mut vis: Visibility,
     types: &[FileTypeArg],
@@ -166,6 +194,21 @@ pub fn build_fd_args(
         ret.push("f".into());
     }
 
+    // add predicates: perm has no fd flag and is left to the post filter
+    let predicates = vis.predicates;
+    if let Some(size) = predicates.size {
+        ret.push(format!("--size={size}").into());
+    }
+    if let Some(age) = predicates.changed_within {
+        ret.push(format!("--changed-within={age}").into());
+    }
+    if let Some(age) = predicates.older_than {
+        ret.push(format!("--changed-before={age}").into());
+    }
+    if let Some(uid) = predicates.owner.and_then(|o| o.uid()) {
+        ret.push(format!("--owner={uid}").into());
+    }
+
     // add -E
     if !vis.all() {
         let exclusions = {
@@ -273,6 +316,7 @@ pub fn resolve_group_types(types: &[FileTypeArg], lcfg: &LessfilterConfig) -> Ve
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fist_types::predicates::{Owner, Predicates};
 
     #[test]
     fn test_build_fd_args_multiple_search_paths() {
@@ -292,4 +336,27 @@ mod tests {
 
         assert_eq!(search_paths, vec!["/path/one", "/path/two"]);
     }
+
+    #[test]
+    fn test_build_fd_args_predicates() {
+        let mut vis = Visibility::default();
+        vis.predicates = Predicates {
+            size: "+10M".parse().ok(),
+            changed_within: "2d".parse().ok(),
+            older_than: "1h 30min".parse().ok(),
+            perm: "x".parse().ok(),
+            owner: Some(Owner::Uid(1000)),
+        };
+        let args = build_fd_args(vis, &[], &[], &[], &FdConfig::default());
+        for arg in [
+            "--size=+10M",
+            "--changed-within=2d",
+            "--changed-before=90min",
+            "--owner=1000",
+        ] {
+            assert!(args.contains(&arg.into()), "missing {arg}");
+        }
+        // the native walker reads them back
+        assert!(super::super::native::NativeFd::parse(&args).is_ok());
+    }
 }
@@ -0,0 +1,668 @@
+//! In-process stand-in for rg, used when it is not installed or when
+//! `rg.native` asks for it.
+//!
+//! Like [`super::native::NativeFd`], [`NativeRg::parse`] reads the arguments
+//! produced by [`build_rg_args`](super::rg::build_rg_args), so both backends
+//! share the same query semantics. Results are reported per file as
+//! [`FileMatches`], which the Search pane turns into items without parsing
+//! rg's output.
+
+use std::{
+    ffi::OsString,
+    io,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::SystemTime,
+};
+
+use cba::{StringError, bait::ResultExt, wbog};
+use grep_matcher::Matcher;
+use grep_regex::{RegexMatcher, RegexMatcherBuilder};
+use grep_searcher::{BinaryDetection, Searcher, SearcherBuilder, Sink, SinkContext, SinkMatch};
+use ignore::{WalkBuilder, WalkState, overrides::OverrideBuilder};
+
+use super::native::display_path;
+
+/// A search built from rg arguments.
+#[derive(Debug, Clone)]
+pub struct NativeRg {
+    matcher: RegexMatcher,
+    invert: bool,
+    before: usize,
+    after: usize,
+    max_count: Option<u64>,
+    binary: BinaryDetection,
+    /// `--trim`: strip leading whitespace from the reported lines.
+    trim: bool,
+    /// Whether rg would color its output, see [`GrepLine::matches`].
+    pub color: bool,
+    /// Whether rg would print `--` between non-contiguous lines.
+    pub context_separator: bool,
+
+    paths: Vec<PathBuf>,
+    hidden: bool,
+    no_ignore: bool,
+    no_ignore_vcs: bool,
+    follow: bool,
+    max_depth: Option<usize>,
+    /// `--max-filesize`, in bytes.
+    max_filesize: Option<u64>,
+    /// `(glob, case_insensitive)`, in the order given.
+    globs: Vec<(String, bool)>,
+    sort: Option<SortBy>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortBy {
+    Path,
+    /// Most recent first, like `--sortr`.
+    Modified,
+    Accessed,
+}
+
+/// The lines rg would report for one file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileMatches {
+    /// As rg prints it: relative to the cwd for relative search paths.
+    pub path: PathBuf,
+    pub lines: Vec<GrepLine>,
+}
+
+/// A matching or context line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrepLine {
+    /// 1-based.
+    pub number: u64,
+    /// The 1-based byte column of the first match, 0 for context lines and
+    /// inverted matches (as rg omits it for those).
+    pub column: u32,
+    pub is_match: bool,
+    /// Without its line terminator.
+    pub text: String,
+    /// The byte ranges of the matches within `text`.
+    pub matches: Vec<Range<usize>>,
+}
+
+/// Flags taking a value which the native backend does not implement.
+const SKIPPED_WITH_VALUE: &[&str] = &[
+    "-t",
+    "--type",
+    "-T",
+    "--type-not",
+    "--type-add",
+    "-j",
+    "--threads",
+    "-r",
+    "--replace",
+    "-E",
+    "--encoding",
+    "-M",
+    "--max-columns",
+    "--ignore-file",
+    "--pre",
+    "--pre-glob",
+    "--engine",
+    "--path-separator",
+    "--colors",
+    "--field-match-separator",
+    "--context-separator",
+];
+
+impl NativeRg {
+    /// `None` when `args` is the empty command [`build_rg_args`](super::rg::build_rg_args)
+    /// uses for a query without patterns.
+    pub fn parse(args: &[OsString]) -> Result<Option<Self>, StringError> {
+        if args.iter().all(|a| a.is_empty()) {
+            return Ok(None);
+        }
+
+        let mut patterns: Vec<String> = vec![];
+        let mut positional: Vec<PathBuf> = vec![];
+        let mut fixed = false;
+        let mut ignore_case = false;
+        let mut smart_case = false;
+        let mut word = false;
+        let mut line_regexp = false;
+        let mut unrestricted = 0;
+        let mut text = false;
+        let mut binary = false;
+        let mut skipped = vec![];
+
+        let mut ret = Self {
+            matcher: RegexMatcher::new("").prefix("Invalid pattern")?,
+            invert: false,
+            before: 0,
+            after: 0,
+            max_count: None,
+            binary: BinaryDetection::quit(b'\x00'),
+            trim: false,
+            color: false,
+            context_separator: true,
+            paths: vec![],
+            hidden: false,
+            no_ignore: false,
+            no_ignore_vcs: false,
+            follow: false,
+            max_depth: None,
+            max_filesize: None,
+            globs: vec![],
+            sort: None,
+        };
+
+        let mut args = args.iter().flat_map(split_arg);
+        let mut only_positional = false;
+        while let Some(arg) = args.next() {
+            let flag = arg.to_str().filter(|a| a.starts_with('-') && a.len() > 1);
+            let Some(flag) = flag.filter(|_| !only_positional) else {
+                positional.push(arg.into());
+                continue;
+            };
+            let mut value = || {
+                args.next()
+                    .map(|v| v.to_string_lossy().into_owned())
+                    .ok_or_else(|| StringError(format!("{flag}: missing value")))
+            };
+
+            match flag {
+                "--" => only_positional = true,
+                "-e" | "--regexp" => patterns.push(value()?),
+                "-v" | "--invert-match" => ret.invert = true,
+                "-F" | "--fixed-strings" => fixed = true,
+                "--no-fixed-strings" => fixed = false,
+                "-i" | "--ignore-case" => (ignore_case, smart_case) = (true, false),
+                "-s" | "--case-sensitive" => (ignore_case, smart_case) = (false, false),
+                "-S" | "--smart-case" => smart_case = true,
+                "-w" | "--word-regexp" => word = true,
+                "-x" | "--line-regexp" => line_regexp = true,
+                "-B" | "--before-context" => ret.before = parse_number(value()?, "context")?,
+                "-A" | "--after-context" => ret.after = parse_number(value()?, "context")?,
+                "-C" | "--context" => {
+                    let n = parse_number(value()?, "context")?;
+                    (ret.before, ret.after) = (n, n);
+                }
+                "-m" | "--max-count" => {
+                    ret.max_count = Some(parse_number(value()?, "max count")? as u64)
+                }
+                "-d" | "--max-depth" | "--maxdepth" => {
+                    ret.max_depth = Some(parse_number(value()?, "depth")?)
+                }
+                "--max-filesize" => ret.max_filesize = Some(parse_filesize(&value()?)?),
+                "--trim" => ret.trim = true,
+                "--no-trim" => ret.trim = false,
+                "--color" => ret.color = value()? != "never",
+                "--no-context-separator" => ret.context_separator = false,
+                "-." | "--hidden" => ret.hidden = true,
+                "--no-hidden" => ret.hidden = false,
+                "--no-ignore" => ret.no_ignore = true,
+                "--no-ignore-vcs" => ret.no_ignore_vcs = true,
+                "-u" | "--unrestricted" => unrestricted += 1,
+                "-L" | "--follow" => ret.follow = true,
+                "--no-follow" => ret.follow = false,
+                "-a" | "--text" => text = true,
+                "--binary" => binary = true,
+                "-g" | "--glob" => ret.globs.push((value()?, false)),
+                "--iglob" => ret.globs.push((value()?, true)),
+                "--sort" | "--sortr" => {
+                    let key = value()?;
+                    ret.sort = match key.as_str() {
+                        "path" => Some(SortBy::Path),
+                        "modified" => Some(SortBy::Modified),
+                        "accessed" => Some(SortBy::Accessed),
+                        "none" => None,
+                        _ => {
+                            skipped.push(format!("{flag}={key}"));
+                            None
+                        }
+                    };
+                }
+                // output formatting, handled by the Search pane
+                "-n" | "--line-number" | "--column" | "--heading" | "--no-heading" | "--null"
+                | "-0" | "--hyperlink-format" => {}
+                "--field-context-separator" => {
+                    value()?;
+                }
+                _ if SKIPPED_WITH_VALUE.contains(&flag) => {
+                    let _ = value();
+                    skipped.push(flag.to_string());
+                }
+                _ => skipped.push(flag.to_string()),
+            }
+        }
+
+        // rg [OPTIONS] PATTERN [PATH ...] when no -e is given
+        let mut positional = positional.into_iter();
+        if patterns.is_empty()
+            && let Some(p) = positional.next()
+        {
+            patterns.push(p.to_string_lossy().into_owned());
+        }
+        ret.paths = positional.collect();
+
+        if unrestricted >= 1 {
+            ret.no_ignore = true;
+        }
+        if unrestricted >= 2 {
+            ret.hidden = true;
+        }
+        ret.binary = if text {
+            BinaryDetection::none()
+        } else if binary || unrestricted >= 3 {
+            BinaryDetection::convert(b'\x00')
+        } else {
+            BinaryDetection::quit(b'\x00')
+        };
+
+        if !skipped.is_empty() {
+            wbog!("Searching without rg, ignoring: {}", skipped.join(" "));
+        }
+
+        ret.matcher = RegexMatcherBuilder::new()
+            .case_insensitive(ignore_case)
+            .case_smart(smart_case)
+            .fixed_strings(fixed)
+            .word(word)
+            .whole_line(line_regexp)
+            .build_many(&patterns)
+            .prefix("Invalid pattern")?;
+
+        Ok(Some(ret))
+    }
+
+    /// The lines of context before and after each match.
+    pub fn context(&self) -> [usize; 2] {
+        [self.before, self.after]
+    }
+
+    pub fn is_inverted(&self) -> bool {
+        self.invert
+    }
+
+    /// Search the files under the search paths, calling `f` on every file with
+    /// matches until it returns false. Files are searched in parallel, unless a
+    /// sort order was given. Returns the number of files with matches.
+    pub fn search(
+        &self,
+        cwd: &Path,
+        f: impl Fn(FileMatches) -> bool + Sync,
+    ) -> usize {
+        let roots: Vec<(PathBuf, &Path)> = if self.paths.is_empty() {
+            vec![(cwd.to_path_buf(), Path::new("."))]
+        } else {
+            self.paths
+                .iter()
+                .map(|p| {
+                    let root = if p == Path::new(".") {
+                        cwd.to_path_buf()
+                    } else {
+                        cwd.join(p)
+                    };
+                    (root, p.as_path())
+                })
+                .collect()
+        };
+        let builder = self.walk_builder(&roots);
+        let searcher = SearcherBuilder::new()
+            .line_number(true)
+            .invert_match(self.invert)
+            .before_context(self.before)
+            .after_context(self.after)
+            .max_matches(self.max_count)
+            .binary_detection(self.binary.clone())
+            .build();
+
+        let count = AtomicUsize::new(0);
+        let stop = AtomicBool::new(false);
+        let visit = |searcher: &mut Searcher, path: PathBuf| {
+            let Some(lines) = self.search_file(searcher, &path) else {
+                return true;
+            };
+            count.fetch_add(1, Ordering::Relaxed);
+            let path = display_path(path, &roots);
+            let go_on = f(FileMatches { path, lines });
+            if !go_on {
+                stop.store(true, Ordering::Relaxed);
+            }
+            go_on
+        };
+
+        if let Some(sort) = self.sort {
+            // sorted output needs every file up front, as with rg's --sort
+            let files = Mutex::new(vec![]);
+            builder.build_parallel().run(|| {
+                let files = &files;
+                Box::new(move |entry| {
+                    if let Some(path) = file_of(entry) {
+                        files.lock().unwrap().push(path);
+                    }
+                    WalkState::Continue
+                })
+            });
+            let mut files = files.into_inner().unwrap();
+            sort_files(&mut files, sort);
+
+            let mut searcher = searcher;
+            for path in files {
+                if !visit(&mut searcher, path) {
+                    break;
+                }
+            }
+        } else {
+            builder.build_parallel().run(|| {
+                let (visit, stop) = (&visit, &stop);
+                let mut searcher = searcher.clone();
+                Box::new(move |entry| {
+                    if stop.load(Ordering::Relaxed) {
+                        return WalkState::Quit;
+                    }
+                    match file_of(entry) {
+                        Some(path) => {
+                            if visit(&mut searcher, path) {
+                                WalkState::Continue
+                            } else {
+                                WalkState::Quit
+                            }
+                        }
+                        None => WalkState::Continue,
+                    }
+                })
+            });
+        }
+
+        count.into_inner()
+    }
+
+    fn walk_builder(
+        &self,
+        roots: &[(PathBuf, &Path)],
+    ) -> WalkBuilder {
+        let mut builder = WalkBuilder::new(&roots[0].0);
+        for (root, _) in &roots[1..] {
+            builder.add(root);
+        }
+        builder
+            .hidden(!self.hidden)
+            .ignore(!self.no_ignore)
+            .parents(!self.no_ignore)
+            .git_ignore(!self.no_ignore && !self.no_ignore_vcs)
+            .git_global(!self.no_ignore && !self.no_ignore_vcs)
+            .git_exclude(!self.no_ignore && !self.no_ignore_vcs)
+            .follow_links(self.follow)
+            .max_depth(self.max_depth);
+        if !self.no_ignore {
+            builder.add_custom_ignore_filename(".rgignore");
+        }
+
+        if !self.globs.is_empty() {
+            let mut overrides = OverrideBuilder::new(&roots[0].0);
+            let added = self.globs.iter().try_for_each(|(glob, case_insensitive)| {
+                overrides.case_insensitive(*case_insensitive)?;
+                overrides.add(glob).map(|_| ())
+            });
+            match added.and_then(|_| overrides.build()) {
+                Ok(overrides) => {
+                    builder.overrides(overrides);
+                }
+                Err(e) => wbog!("Malformed glob: {e}"),
+            }
+        }
+        builder
+    }
+
+    /// The reported lines of `path`, or `None` if nothing matched.
+    fn search_file(
+        &self,
+        searcher: &mut Searcher,
+        path: &Path,
+    ) -> Option<Vec<GrepLine>> {
+        if let Some(max) = self.max_filesize
+            && path.metadata().is_ok_and(|m| m.len() > max)
+        {
+            return None;
+        }
+        let mut sink = Collect {
+            rg: self,
+            lines: vec![],
+            matched: false,
+        };
+        if let Err(e) = searcher.search_path(&self.matcher, path, &mut sink) {
+            log::debug!("Failed to search {}: {e}", path.display());
+        }
+        sink.matched.then_some(sink.lines)
+    }
+
+    fn line(
+        &self,
+        number: Option<u64>,
+        bytes: &[u8],
+        is_match: bool,
+    ) -> GrepLine {
+        let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+        let bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+
+        let mut matches = vec![];
+        if is_match && !self.invert {
+            let _ = self.matcher.find_iter(bytes, |m| {
+                if !m.is_empty() {
+                    matches.push(m.start()..m.end());
+                }
+                true
+            });
+        }
+        let column = matches.first().map_or(0, |m| m.start as u32 + 1);
+
+        let trimmed = if self.trim {
+            bytes.iter().take_while(|b| b.is_ascii_whitespace()).count()
+        } else {
+            0
+        };
+        let (text, matches) = match std::str::from_utf8(&bytes[trimmed..]) {
+            Ok(text) => (
+                text.to_string(),
+                matches
+                    .into_iter()
+                    .map(|m| m.start.saturating_sub(trimmed)..m.end.saturating_sub(trimmed))
+                    .filter(|m| !m.is_empty())
+                    .collect(),
+            ),
+            // offsets would not survive the lossy conversion
+            Err(_) => (
+                String::from_utf8_lossy(&bytes[trimmed..]).into_owned(),
+                vec![],
+            ),
+        };
+
+        GrepLine {
+            number: number.unwrap_or_default(),
+            column: if self.invert { 0 } else { column.max(1) },
+            is_match,
+            text,
+            matches,
+        }
+    }
+}
+
+struct Collect<'a> {
+    rg: &'a NativeRg,
+    lines: Vec<GrepLine>,
+    matched: bool,
+}
+
+impl Sink for Collect<'_> {
+    type Error = io::Error;
+
+    fn matched(
+        &mut self,
+        _: &Searcher,
+        mat: &SinkMatch<'_>,
+    ) -> Result<bool, io::Error> {
+        self.matched = true;
+        self.lines
+            .push(self.rg.line(mat.line_number(), mat.bytes(), true));
+        Ok(true)
+    }
+
+    fn context(
+        &mut self,
+        _: &Searcher,
+        ctx: &SinkContext<'_>,
+    ) -> Result<bool, io::Error> {
+        let mut line = self.rg.line(ctx.line_number(), ctx.bytes(), false);
+        line.column = 0;
+        self.lines.push(line);
+        Ok(true)
+    }
+}
+
+/// The path of a searchable walk entry: files, and links to files when following.
+fn file_of(entry: Result<ignore::DirEntry, ignore::Error>) -> Option<PathBuf> {
+    let entry = entry.ok()?;
+    entry
+        .file_type()
+        .is_some_and(|t| t.is_file())
+        .then(|| entry.into_path())
+}
+
+fn sort_files(
+    files: &mut [PathBuf],
+    sort: SortBy,
+) {
+    let time = |p: &PathBuf| {
+        std::fs::metadata(p)
+            .and_then(|m| match sort {
+                SortBy::Accessed => m.accessed(),
+                _ => m.modified(),
+            })
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    };
+    match sort {
+        SortBy::Path => files.sort(),
+        SortBy::Modified | SortBy::Accessed => {
+            files.sort_by_cached_key(|p| std::cmp::Reverse(time(p)))
+        }
+    }
+}
+
+fn parse_number(
+    value: String,
+    what: &str,
+) -> Result<usize, StringError> {
+    value.parse().prefix(format!("Invalid {what}"))
+}
+
+/// rg's `--max-filesize`: bytes, or a number suffixed with K, M or G (powers of 1024).
+fn parse_filesize(value: &str) -> Result<u64, StringError> {
+    let invalid = || StringError(format!("Invalid file size '{value}'"));
+    let (n, shift) = match value.as_bytes().last() {
+        Some(b'K') => (&value[..value.len() - 1], 10),
+        Some(b'M') => (&value[..value.len() - 1], 20),
+        Some(b'G') => (&value[..value.len() - 1], 30),
+        _ => (value, 0),
+    };
+    let n: u64 = n.parse().map_err(|_| invalid())?;
+    n.checked_mul(1 << shift).ok_or_else(invalid)
+}
+
+/// Split `--flag=value` and short flags with their value attached (`-C2`).
+fn split_arg(arg: &OsString) -> Vec<OsString> {
+    let Some(s) = arg.to_str() else {
+        return vec![arg.clone()];
+    };
+    if let Some((flag, value)) = s.split_once('=')
+        && flag.starts_with("--")
+    {
+        return vec![flag.into(), value.into()];
+    }
+    if let Some(rest) = s.strip_prefix('-')
+        && rest.len() > 1
+        && !rest.starts_with('-')
+    {
+        // -uuu
+        if rest.chars().all(|c| c == 'u') {
+            return rest.chars().map(|_| "-u".into()).collect();
+        }
+        if rest.starts_with([
+            'A', 'B', 'C', 'e', 'g', 'm', 'd', 't', 'T', 'j', 'r', 'E', 'M',
+        ]) {
+            return vec![s[..2].into(), s[2..].into()];
+        }
+    }
+    vec![arg.clone()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(
+        args: &[&str],
+        cwd: &Path,
+    ) -> Vec<FileMatches> {
+        let args: Vec<OsString> = args.iter().map(OsString::from).collect();
+        let found = Mutex::new(vec![]);
+        NativeRg::parse(&args).unwrap().unwrap().search(cwd, |m| {
+            found.lock().unwrap().push(m);
+            true
+        });
+        let mut found = found.into_inner().unwrap();
+        found.sort_by(|a, b| a.path.cmp(&b.path));
+        found
+    }
+
+    #[test]
+    fn test_native_search() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.rs"), "fn main() {\n    let Foo = 1;\n}\n").unwrap();
+        std::fs::write(root.join("b.txt"), "foo\nbar\nFOO bar foo\n").unwrap();
+
+        // smart case with several patterns, columns and match ranges
+        let found = search(&["--smart-case", "-e", "foo", "-e", "bar", "--trim"], root);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].path, Path::new("b.txt"));
+        let lines = &found[0].lines;
+        assert_eq!(lines.len(), 3);
+        assert_eq!((lines[2].number, lines[2].column), (3, 1));
+        assert_eq!(lines[2].matches, [0..3, 4..7, 8..11]);
+        assert_eq!(found[1].path, Path::new("src/a.rs"));
+        assert_eq!(
+            found[1].lines,
+            [GrepLine {
+                number: 2,
+                column: 9,
+                is_match: true,
+                text: "let Foo = 1;".into(),
+                matches: std::iter::once(4..7).collect(),
+            }]
+        );
+
+        // context, fixed strings, case sensitivity and a search path
+        let found = search(&["-s", "-F", "-e", "()", "-A", "1", "src"], root);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, Path::new("src/a.rs"));
+        let numbers: Vec<_> = found[0]
+            .lines
+            .iter()
+            .map(|l| (l.number, l.is_match))
+            .collect();
+        assert_eq!(numbers, [(1, true), (2, false)]);
+
+        // inverted matches and globs
+        let found = search(&["-v", "-e", "o", "--iglob", "*.TXT"], root);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].lines[0].text, "bar");
+        assert_eq!(found[0].lines[0].column, 0);
+
+        // b.txt is 20 bytes, a.rs 31
+        let found = search(&["-e", "o", "--max-filesize=20"], root);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, Path::new("b.txt"));
+        assert_eq!(parse_filesize("2K").unwrap(), 2048);
+
+        assert!(NativeRg::parse(&["".into()]).unwrap().is_none());
+    }
+}
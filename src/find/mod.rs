@@ -1,5 +1,7 @@
 pub mod apps;
 pub mod fd;
+pub mod grep;
 pub mod metadata;
+pub mod native;
 pub mod rg;
 pub mod walker;
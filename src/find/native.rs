@@ -0,0 +1,556 @@
+//! In-process stand-in for fd, used when it is not installed or when
+//! `fd.native` asks for it ([`use_native_fd`]).
+//!
+//! [`NativeFd::parse`] reads the arguments produced by [`build_fd_args`], so
+//! visibility, exclusions and file types are resolved in one place for both
+//! backends. The common passthrough flags are supported, others are skipped
+//! with a warning.
+//!
+//! [`use_native_fd`]: super::fd::use_native_fd
+//! [`build_fd_args`]: super::fd::build_fd_args
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, SystemTime},
+};
+
+use cba::{StringError, bait::ResultExt, wbog};
+use globset::GlobBuilder;
+use ignore::{DirEntry, WalkBuilder, WalkState};
+use regex::bytes::{Regex, RegexBuilder};
+
+use super::walker::build_overrides;
+use fist_types::{
+    When,
+    filetypes::FileType,
+    predicates::{Age, Owner, SizeBound},
+};
+
+/// A search built from fd arguments.
+#[derive(Debug, Default)]
+pub struct NativeFd {
+    /// As given, relative paths are resolved against the cwd of [`Self::walk`].
+    search_paths: Vec<PathBuf>,
+    /// All must match the file name, or the full path with `full_path`.
+    patterns: Vec<Regex>,
+    full_path: bool,
+    hidden: bool,
+    no_ignore: bool,
+    no_ignore_vcs: bool,
+    follow: bool,
+    /// Any must match, like the extensions.
+    types: Vec<FileType>,
+    extensions: Vec<String>,
+    excludes: Vec<String>,
+    min_depth: usize,
+    max_depth: Option<usize>,
+    sizes: Vec<SizeBound>,
+    owner: Option<Owner>,
+    changed_within: Option<SystemTime>,
+    changed_before: Option<SystemTime>,
+    max_results: Option<usize>,
+}
+
+/// Flags taking a value which the native backend does not implement.
+const SKIPPED_WITH_VALUE: &[&str] = &[
+    "-j",
+    "--threads",
+    "--base-directory",
+    "--path-separator",
+    "--format",
+    "--batch-size",
+    "--max-buffer-time",
+    "--ignore-file",
+];
+
+impl NativeFd {
+    pub fn parse(args: &[OsString]) -> Result<Self, StringError> {
+        let mut ret = Self::default();
+        let mut raw_patterns = vec![];
+        let mut positional = vec![];
+        let mut glob = false;
+        let mut fixed = false;
+        let mut case = When::Auto;
+        let mut skipped = vec![];
+
+        let mut args = args.iter().flat_map(split_arg);
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.to_str().filter(|a| a.starts_with('-') && a.len() > 1) else {
+                positional.push(arg);
+                continue;
+            };
+            let mut value = || {
+                args.next()
+                    .map(|v| v.to_string_lossy().into_owned())
+                    .ok_or_else(|| StringError(format!("{flag}: missing value")))
+            };
+
+            match flag {
+                "--and" => raw_patterns.push(value()?),
+                "--search-path" => ret.search_paths.push(value()?.into()),
+                "-H" | "--hidden" => ret.hidden = true,
+                "-I" | "--no-ignore" => ret.no_ignore = true,
+                "--no-ignore-vcs" => ret.no_ignore_vcs = true,
+                "-u" | "--unrestricted" => (ret.hidden, ret.no_ignore) = (true, true),
+                "-L" | "--follow" => ret.follow = true,
+                "--no-follow" => ret.follow = false,
+                "-p" | "--full-path" => ret.full_path = true,
+                "-g" | "--glob" => glob = true,
+                "--regex" => glob = false,
+                "-F" | "--fixed-strings" => fixed = true,
+                "-s" | "--case-sensitive" => case = When::Always,
+                "-i" | "--ignore-case" => case = When::Never,
+                "-t" | "--type" => ret.types.push(parse_type(&value()?)?),
+                "-e" | "--extension" => {
+                    let ext = value()?;
+                    ret.extensions
+                        .push(ext.trim_start_matches('.').to_lowercase());
+                }
+                "-E" | "--exclude" => ret.excludes.push(value()?),
+                "-d" | "--max-depth" | "--maxdepth" => {
+                    ret.max_depth = Some(value()?.parse().prefix("Invalid depth")?)
+                }
+                "--min-depth" => ret.min_depth = value()?.parse().prefix("Invalid depth")?,
+                "--exact-depth" => {
+                    let depth = value()?.parse().prefix("Invalid depth")?;
+                    (ret.min_depth, ret.max_depth) = (depth, Some(depth));
+                }
+                "-S" | "--size" => ret.sizes.push(value()?.parse().map_err(StringError)?),
+                "-o" | "--owner" => {
+                    let owner = value()?;
+                    // groups and negations are left to fd
+                    if owner.contains([':', '!']) {
+                        skipped.push(format!("{flag}={owner}"));
+                    } else {
+                        ret.owner = Some(owner.parse().map_err(StringError)?);
+                    }
+                }
+                "--changed-within" | "--change-newer-than" | "--newer" => {
+                    ret.changed_within = Some(parse_time(&value()?)?)
+                }
+                "--changed-before" | "--change-older-than" | "--older" => {
+                    ret.changed_before = Some(parse_time(&value()?)?)
+                }
+                "--max-results" => {
+                    ret.max_results = Some(value()?.parse().prefix("Invalid --max-results")?)
+                }
+                "-1" => ret.max_results = Some(1),
+                // output formatting, handled by the callers
+                "-0" | "--print0" | "-a" | "--absolute-path" | "--no-hidden" | "--ignore" => {}
+                "-c" | "--color" => {
+                    value()?;
+                }
+                _ if SKIPPED_WITH_VALUE.contains(&flag) => {
+                    let _ = value();
+                    skipped.push(flag.to_string());
+                }
+                _ => skipped.push(flag.to_string()),
+            }
+        }
+
+        // fd's own positionals: the pattern, then the search paths
+        let mut positional = positional.into_iter();
+        if let Some(pattern) = positional.next() {
+            raw_patterns.push(pattern.to_string_lossy().into_owned());
+        }
+        ret.search_paths.extend(positional.map(PathBuf::from));
+
+        if !skipped.is_empty() {
+            wbog!("fd is not installed, ignoring: {}", skipped.join(" "));
+        }
+
+        for p in raw_patterns.iter().filter(|p| !p.is_empty()) {
+            let source = if glob {
+                GlobBuilder::new(p)
+                    .literal_separator(ret.full_path)
+                    .build()
+                    .prefix("Invalid glob")?
+                    .regex()
+                    .to_string()
+            } else if fixed {
+                regex::escape(p)
+            } else {
+                p.clone()
+            };
+            let case_insensitive = match case {
+                When::Always => false,
+                When::Never => true,
+                When::Auto => !p.chars().any(char::is_uppercase),
+            };
+            let regex = RegexBuilder::new(&source)
+                .case_insensitive(case_insensitive)
+                .build()
+                .prefix("Invalid pattern")?;
+            ret.patterns.push(regex);
+        }
+
+        Ok(ret)
+    }
+
+    /// Walk the search paths in parallel, calling `f` on every result until it
+    /// returns false. Results are relative to `cwd` for relative search paths,
+    /// as fd prints them. Returns the number of results.
+    pub fn walk(
+        &self,
+        cwd: &Path,
+        f: impl Fn(PathBuf) -> bool + Sync,
+    ) -> usize {
+        let roots: Vec<(PathBuf, &Path)> = if self.search_paths.is_empty() {
+            vec![(cwd.to_path_buf(), Path::new("."))]
+        } else {
+            self.search_paths
+                .iter()
+                .map(|p| {
+                    let root = if p == Path::new(".") {
+                        cwd.to_path_buf()
+                    } else {
+                        cwd.join(p)
+                    };
+                    (root, p.as_path())
+                })
+                .collect()
+        };
+
+        let mut builder = WalkBuilder::new(&roots[0].0);
+        for (root, _) in &roots[1..] {
+            builder.add(root);
+        }
+        builder
+            .hidden(!self.hidden)
+            .ignore(!self.no_ignore)
+            .parents(!self.no_ignore)
+            .git_ignore(!self.no_ignore && !self.no_ignore_vcs)
+            .git_global(!self.no_ignore && !self.no_ignore_vcs)
+            .git_exclude(!self.no_ignore && !self.no_ignore_vcs)
+            .follow_links(self.follow)
+            .max_depth(self.max_depth);
+        if !self.no_ignore {
+            builder.add_custom_ignore_filename(".fdignore");
+        }
+        if !self.excludes.is_empty() {
+            // anchored excludes are relative to the root of each entry
+            let excludes: Vec<String> = self.excludes.iter().map(|e| format!("!{e}")).collect();
+            let overrides: Result<Vec<_>, _> = roots
+                .iter()
+                .map(|(root, _)| {
+                    build_overrides(
+                        &[&root.to_string_lossy()],
+                        excludes.iter().map(String::as_str),
+                    )
+                    .map(|o| (root.clone(), o))
+                })
+                .collect();
+            match overrides {
+                Ok(overrides) => {
+                    builder.filter_entry(move |entry| {
+                        let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                        overrides
+                            .iter()
+                            .filter(|(root, _)| entry.path().starts_with(root))
+                            .max_by_key(|(root, _)| root.as_os_str().len())
+                            .is_none_or(|(_, o)| !o.matched(entry.path(), is_dir).is_ignore())
+                    });
+                }
+                Err(e) => wbog!("{e}"),
+            }
+        }
+
+        let count = AtomicUsize::new(0);
+        builder.build_parallel().run(|| {
+            let (f, count, roots) = (&f, &count, &roots);
+            Box::new(move |entry| {
+                let Ok(entry) = entry else {
+                    return WalkState::Continue;
+                };
+                if entry.depth() == 0 || entry.depth() < self.min_depth || !self.matches(&entry) {
+                    return WalkState::Continue;
+                }
+
+                let n = count.fetch_add(1, Ordering::Relaxed) + 1;
+                if self.max_results.is_some_and(|max| n > max) {
+                    return WalkState::Quit;
+                }
+                if !f(display_path(entry.into_path(), roots)) {
+                    return WalkState::Quit;
+                }
+                if self.max_results.is_some_and(|max| n == max) {
+                    return WalkState::Quit;
+                }
+                WalkState::Continue
+            })
+        });
+
+        let count = count.into_inner();
+        self.max_results.map_or(count, |max| count.min(max))
+    }
+
+    fn matches(
+        &self,
+        entry: &DirEntry,
+    ) -> bool {
+        let path = entry.path();
+
+        if !self.patterns.is_empty() {
+            let haystack = if self.full_path {
+                path.as_os_str()
+            } else {
+                entry.file_name()
+            };
+            let haystack = haystack.as_encoded_bytes();
+            if !self.patterns.iter().all(|r| r.is_match(haystack)) {
+                return false;
+            }
+        }
+
+        if !self.extensions.is_empty() {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            if !self.extensions.iter().any(|ext| {
+                name.strip_suffix(ext.as_str())
+                    .is_some_and(|s| s.ends_with('.'))
+            }) {
+                return false;
+            }
+        }
+
+        if !self.types.is_empty() && !self.types.iter().any(|t| matches_type(*t, entry)) {
+            return false;
+        }
+
+        if self.sizes.is_empty()
+            && self.changed_within.is_none()
+            && self.changed_before.is_none()
+            && self.owner.is_none()
+        {
+            return true;
+        }
+        let Ok(meta) = entry.metadata() else {
+            return false;
+        };
+        // like fd, size filters exclude everything but files
+        if !self.sizes.is_empty() {
+            if !meta.is_file() || !self.sizes.iter().all(|s| s.matches(meta.len())) {
+                return false;
+            }
+        }
+        if self.changed_within.is_some() || self.changed_before.is_some() {
+            let Ok(mtime) = meta.modified() else {
+                return false;
+            };
+            if self.changed_within.is_some_and(|t| mtime < t)
+                || self.changed_before.is_some_and(|t| mtime > t)
+            {
+                return false;
+            }
+        }
+        if self.owner.is_some_and(|o| !o.matches(&meta)) {
+            return false;
+        }
+        true
+    }
+}
+
+/// `path` as fd would print it for the search path it was found under.
+pub(super) fn display_path(
+    path: PathBuf,
+    roots: &[(PathBuf, &Path)],
+) -> PathBuf {
+    for (root, given) in roots {
+        if given.is_absolute() {
+            continue;
+        }
+        if let Ok(rel) = path.strip_prefix(root) {
+            return if *given == Path::new(".") {
+                rel.to_path_buf()
+            } else {
+                given.join(rel)
+            };
+        }
+    }
+    path
+}
+
+/// Split `--flag=value` and clusters of short flags (`-HI`).
+fn split_arg(arg: &OsString) -> Vec<OsString> {
+    let Some(s) = arg.to_str() else {
+        return vec![arg.clone()];
+    };
+    if let Some((flag, value)) = s.split_once('=')
+        && flag.starts_with("--")
+    {
+        return vec![flag.into(), value.into()];
+    }
+    if let Some(cluster) = s.strip_prefix('-')
+        && cluster.len() > 1
+        && !cluster.starts_with('-')
+        && cluster.chars().all(|c| "HIuLpgFsia0".contains(c))
+    {
+        return cluster.chars().map(|c| format!("-{c}").into()).collect();
+    }
+    // short flag with its value attached (`-tf`, `-d2`)
+    if let Some(cluster) = s.strip_prefix('-')
+        && cluster.len() > 1
+        && cluster.starts_with(['t', 'e', 'E', 'd', 'S', 'j', 'o', 'c'])
+    {
+        return vec![s[..2].into(), s[2..].into()];
+    }
+    vec![arg.clone()]
+}
+
+fn parse_type(s: &str) -> Result<FileType, StringError> {
+    Ok(match s {
+        "file" => FileType::File,
+        "dir" | "directory" => FileType::Directory,
+        "symlink" => FileType::Symlink,
+        "executable" => FileType::Executable,
+        "empty" => FileType::Empty,
+        "socket" => FileType::Socket,
+        "pipe" => FileType::Pipe,
+        "block-device" => FileType::BlockDevice,
+        "char-device" => FileType::CharDevice,
+        _ => s.parse().prefix(format!("Invalid type '{s}'"))?,
+    })
+}
+
+fn matches_type(
+    t: FileType,
+    entry: &DirEntry,
+) -> bool {
+    let Some(ft) = entry.file_type() else {
+        return false;
+    };
+    match t {
+        FileType::File => ft.is_file(),
+        FileType::Directory => ft.is_dir(),
+        FileType::Symlink => entry.path_is_symlink(),
+        FileType::Executable => ft.is_file() && entry.metadata().is_ok_and(|m| is_executable(&m)),
+        FileType::Empty => {
+            if ft.is_dir() {
+                std::fs::read_dir(entry.path()).is_ok_and(|mut d| d.next().is_none())
+            } else {
+                ft.is_file() && entry.metadata().is_ok_and(|m| m.len() == 0)
+            }
+        }
+        _ => FileType::get(entry.path()) == t,
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(meta: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_meta: &std::fs::Metadata) -> bool {
+    false
+}
+
+/// fd's time arguments: a duration before now (`2weeks`, `1h 30min`), a date
+/// (`2024-01-31`, `2024-01-31 10:00:00`) or `@UNIX_TIMESTAMP`.
+fn parse_time(s: &str) -> Result<SystemTime, StringError> {
+    let s = s.trim();
+    if let Some(ts) = s.strip_prefix('@') {
+        let secs: u64 = ts.parse().prefix("Invalid timestamp")?;
+        return Ok(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+    }
+    if let Ok(t) = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
+        return local_time(t);
+    }
+    if let Ok(d) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return local_time(d.and_time(chrono::NaiveTime::MIN));
+    }
+    let age: Age = s
+        .parse()
+        .map_err(|_| StringError(format!("Invalid time '{s}'")))?;
+    age.cutoff(SystemTime::now())
+        .ok_or_else(|| StringError(format!("Invalid time '{s}'")))
+}
+
+fn local_time(t: chrono::NaiveDateTime) -> Result<SystemTime, StringError> {
+    t.and_local_timezone(chrono::Local)
+        .earliest()
+        .map(SystemTime::from)
+        .ok_or_else(|| StringError(format!("Invalid local time '{t}'")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    fn run(
+        args: &[&str],
+        cwd: &Path,
+    ) -> Vec<String> {
+        let args: Vec<OsString> = args.iter().map(OsString::from).collect();
+        let found = Mutex::new(vec![]);
+        NativeFd::parse(&args).unwrap().walk(cwd, |p| {
+            found.lock().unwrap().push(p.to_string_lossy().into_owned());
+            true
+        });
+        let mut found = found.into_inner().unwrap();
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn test_native_walk() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/nested")).unwrap();
+        std::fs::create_dir_all(root.join(".hidden")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(root.join("src/nested/Lib.RS"), "").unwrap();
+        std::fs::write(root.join("README.md"), "readme").unwrap();
+        std::fs::write(root.join(".hidden/notes.md"), "").unwrap();
+
+        assert_eq!(
+            run(&["-t", "f"], root),
+            ["README.md", "src/main.rs", "src/nested/Lib.RS"]
+        );
+        assert_eq!(
+            run(&["-tf", "-H", "-e", "md"], root),
+            [".hidden/notes.md", "README.md"]
+        );
+        // smart case, and every --and pattern must match
+        assert_eq!(run(&["--and", "lib"], root), ["src/nested/Lib.RS"]);
+        assert_eq!(
+            run(&["--and", "Lib", "--and", "^[^.]+$"], root),
+            [] as [&str; 0]
+        );
+        assert_eq!(
+            run(&["-g", "*.rs", "--search-path", "src"], root),
+            ["src/main.rs", "src/nested/Lib.RS"]
+        );
+        assert_eq!(run(&["-s", "-g", "*.rs", "src"], root), ["src/main.rs"]);
+        assert_eq!(run(&["-d", "1", "-E", "README.md"], root), ["src"]);
+        assert_eq!(
+            run(&["-tf", "--size=+1b", "-E", "src"], root),
+            ["README.md"]
+        );
+        // anchored excludes apply to every search path
+        assert_eq!(
+            run(
+                &[
+                    "-tf", "-H", "-E", "/notes.md", "-E", "/nested", "--search-path", "src",
+                    "--search-path", ".hidden",
+                ],
+                root
+            ),
+            ["src/main.rs"]
+        );
+        assert_eq!(
+            run(&["-t", "e", "-H"], root),
+            [".hidden/notes.md", "src/nested/Lib.RS"]
+        );
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert!(parse_time("2024-01-31").is_ok());
+        assert!(parse_time("yesterday").is_err());
+    }
+}
@@ -1,4 +1,9 @@
-use std::{ffi::OsString, path::PathBuf};
+use std::{
+    ffi::OsString,
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::OnceLock,
+};
 
 use cba::vec_;
 
@@ -7,7 +12,10 @@ use crate::{
     config::RgConfig,
 };
 use fist_types::When;
-use fist_types::filters::{SortOrder, Visibility};
+use fist_types::{
+    filters::{SortOrder, Visibility},
+    predicates::SizeBound,
+};
 
 // pub fn is_inverted(
 //     patterns: &[String],
@@ -35,6 +43,28 @@ use fist_types::filters::{SortOrder, Visibility};
 //     false
 // }
 
+/// Whether rg can be spawned, checked once.
+pub fn is_rg_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        Command::new("rg")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    })
+}
+
+/// Whether to search with [`super::grep::NativeRg`] instead of rg, see `rg.native`.
+pub fn use_native_rg(cfg: &RgConfig) -> bool {
+    match cfg.native {
+        When::Always => true,
+        When::Never => false,
+        When::Auto => !is_rg_available(),
+    }
+}
+
 pub fn is_inverted(args: &[OsString]) -> bool {
     args.iter().take_while(|v| *v != "--").any(|v| v == "-v")
 }
@@ -79,6 +109,11 @@ pub fn build_rg_args(
         }
     }
 
+    // rg only bounds sizes from above, the other predicates are post filters
+    if let Some(SizeBound::Max(bytes)) = vis.predicates.size {
+        ret.push(format!("--max-filesize={bytes}").into());
+    }
+
     match sort {
         SortOrder::mtime => {
             ret.push("--sortr=modified".into());
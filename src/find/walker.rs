@@ -6,13 +6,13 @@ use ignore::{
     overrides::{Override, OverrideBuilder},
 };
 
+use crate::utils::tree::connectors;
 use fist_types::{filters::Visibility, git::is_vcs_dir};
 
 // paths are relative to root
 pub fn list_dir(
     cwd: &Path,
     mut vis: Visibility,
-    _depth: usize, // todo
 ) -> impl Iterator<Item = PathBuf> {
     let mut builder = WalkBuilder::new(cwd);
     if vis.all() {
@@ -50,6 +50,65 @@ pub fn list_dir(
         .map(|e| e.into_path())
 }
 
+/// An entry of a [`list_tree`] listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeEntry {
+    pub path: PathBuf,
+    /// The indentation guide drawn before the name, empty at the top level.
+    pub guide: String,
+}
+
+/// List `cwd` as a tree, in display order.
+///
+/// Each directory is listed by [`list_dir`] and its entries are ordered by `sort`, so filtering
+/// and sorting apply per level. A directory `level` levels below `cwd` (1 for its entries) is
+/// descended into when `expanded(dir, level)` holds.
+pub fn list_tree(
+    cwd: &Path,
+    vis: Visibility,
+    expanded: impl Fn(&Path, usize) -> bool,
+    mut sort: impl FnMut(&mut Vec<PathBuf>),
+) -> Vec<TreeEntry> {
+    let mut ret = Vec::new();
+    list_level(cwd, vis, 1, "", &expanded, &mut sort, &mut ret);
+    ret
+}
+
+fn list_level(
+    dir: &Path,
+    vis: Visibility,
+    level: usize,
+    prefix: &str,
+    expanded: &impl Fn(&Path, usize) -> bool,
+    sort: &mut impl FnMut(&mut Vec<PathBuf>),
+    ret: &mut Vec<TreeEntry>,
+) {
+    let mut paths: Vec<PathBuf> = list_dir(dir, vis).collect();
+    sort(&mut paths);
+
+    let len = paths.len();
+    for (i, path) in paths.into_iter().enumerate() {
+        // the top level is drawn without connectors
+        let (guide, child_prefix) = if level == 1 {
+            (String::new(), String::new())
+        } else {
+            let (connector, continuation) = connectors(i + 1 == len);
+            (
+                format!("{prefix}{connector}"),
+                format!("{prefix}{continuation}"),
+            )
+        };
+        let descend = path.is_dir() && expanded(&path, level);
+        ret.push(TreeEntry {
+            path: path.clone(),
+            guide,
+        });
+        if descend {
+            list_level(&path, vis, level + 1, &child_prefix, expanded, sort, ret);
+        }
+    }
+}
+
 pub fn build_overrides<'a>(
     paths: &[&'a str],
     exclusions: impl IntoIterator<Item = &'a str>,
@@ -62,3 +121,93 @@ pub fn build_overrides<'a>(
 
     builder.build().prefix("Malformed exclude pattern")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fist_types::predicates::Predicates;
+
+    #[test]
+    fn test_list_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for d in ["a/x", "a/y", "b"] {
+            std::fs::create_dir_all(root.join(d)).unwrap();
+        }
+        for f in ["a/x/1", "a/2", "b/3", "c"] {
+            std::fs::write(root.join(f), "").unwrap();
+        }
+
+        let render = |entries: Vec<TreeEntry>| {
+            entries
+                .into_iter()
+                .map(|e| format!("{}{}", e.guide, e.path.file_name().unwrap().display()))
+                .collect::<Vec<_>>()
+        };
+
+        // two levels, reverse name order, with `a/x` collapsed
+        let entries = list_tree(
+            root,
+            Visibility::default(),
+            |dir, level| level < 2 && !dir.ends_with("a/x"),
+            |paths| paths.sort_by(|a, b| b.cmp(a)),
+        );
+        assert_eq!(
+            render(entries),
+            ["c", "b", "└── 3", "a", "├── y", "├── x", "└── 2"]
+        );
+
+        // levels nest their guides
+        let entries = list_tree(
+            root,
+            Visibility::default(),
+            |dir, _| dir.starts_with(root.join("a")),
+            |paths| paths.sort(),
+        );
+        assert_eq!(
+            render(entries),
+            ["a", "├── 2", "├── x", "│   └── 1", "└── y", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn test_list_dir_predicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir(root.join("dir")).unwrap();
+        std::fs::write(root.join("big"), [0; 2048]).unwrap();
+        std::fs::write(root.join("small"), "").unwrap();
+
+        let listed = |predicates: Predicates| {
+            let mut vis = Visibility::default();
+            vis.predicates = predicates;
+            let mut names: Vec<_> = list_dir(root, vis)
+                .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(listed(Predicates::NONE), ["big", "dir", "small"]);
+        // size only matches files
+        let size = |s: &str| Predicates {
+            size: s.parse().ok(),
+            ..Default::default()
+        };
+        assert_eq!(listed(size("+1k")), ["big"]);
+        assert_eq!(listed(size("-1Ki")), ["small"]);
+        let age = |within: &str, older: &str| Predicates {
+            changed_within: within.parse().ok(),
+            older_than: older.parse().ok(),
+            ..Default::default()
+        };
+        assert_eq!(listed(age("1h", "")), ["big", "dir", "small"]);
+        assert!(listed(age("", "1h")).is_empty());
+        let owner = Predicates {
+            owner: "me".parse().ok(),
+            perm: "rw".parse().ok(),
+            ..Default::default()
+        };
+        assert_eq!(listed(owner), ["big", "dir", "small"]);
+    }
+}
@@ -0,0 +1,96 @@
+//! Comparison of two directories, for [`crate::run::FsAction::ComparePanels`].
+
+use std::{fs::Metadata, path::Path, time::UNIX_EPOCH};
+
+/// Whether `path`, below `base`, differs from its counterpart below `other`: the counterpart is
+/// missing or of another type, or, for files, has another size or modification time (to the
+/// second). Directories present on both sides are not descended into.
+pub fn differs(
+    path: &Path,
+    base: &Path,
+    other: &Path,
+) -> bool {
+    let Ok(rel) = path.strip_prefix(base) else {
+        return false;
+    };
+    let Ok(a) = path.metadata() else {
+        return false;
+    };
+    let Ok(b) = other.join(rel).metadata() else {
+        return true;
+    };
+    if a.is_dir() || b.is_dir() {
+        return a.is_dir() != b.is_dir();
+    }
+    a.len() != b.len() || mtime_secs(&a) != mtime_secs(&b)
+}
+
+fn mtime_secs(meta: &Metadata) -> Option<u64> {
+    meta.modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, time::Duration};
+
+    /// Give `path` the modification time of `from`.
+    fn copy_mtime(
+        from: &Path,
+        path: &Path,
+    ) {
+        let mtime = from.metadata().unwrap().modified().unwrap();
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_differs() {
+        let dir = tempfile::tempdir().unwrap();
+        let (left, right) = (dir.path().join("left"), dir.path().join("right"));
+        for d in [&left, &right] {
+            fs::create_dir_all(d.join("sub")).unwrap();
+        }
+        for (name, l, r) in [
+            ("same", "abc", "abc"),
+            ("size", "abc", "abcd"),
+            ("time", "abc", "xyz"),
+        ] {
+            fs::write(left.join(name), l).unwrap();
+            fs::write(right.join(name), r).unwrap();
+        }
+        copy_mtime(&left.join("same"), &right.join("same"));
+        copy_mtime(&left.join("size"), &right.join("size"));
+        let old = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        fs::File::options()
+            .write(true)
+            .open(right.join("time"))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        fs::write(left.join("only"), "").unwrap();
+        fs::create_dir(left.join("kind")).unwrap();
+        fs::write(right.join("kind"), "").unwrap();
+        fs::write(left.join("sub/nested"), "").unwrap();
+
+        let differs = |name: &str| differs(&left.join(name), &left, &right);
+        assert!(!differs("same"));
+        assert!(differs("size"));
+        assert!(differs("time"));
+        assert!(differs("only"));
+        assert!(differs("kind"));
+        assert!(!differs("sub"));
+        assert!(differs("sub/nested"));
+        // the other side is compared the same way
+        assert!(!super::differs(&right.join("same"), &right, &left));
+        assert!(!super::differs(&right.join("sub"), &right, &left));
+    }
+}
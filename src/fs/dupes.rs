@@ -0,0 +1,302 @@
+//! Duplicate file detection.
+//!
+//! Candidates are narrowed in three passes: files are grouped by size, then by a hash of their
+//! first block, and finally by a hash of their full contents. The hashing passes run on the
+//! global rayon pool, which is shared with `fist-size`.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use cba::bath::PathExt;
+use ignore::WalkBuilder;
+use rayon::prelude::*;
+
+use fist_types::{filters::Visibility, git::is_vcs_dir};
+
+/// Bytes hashed by the partial pass.
+const PARTIAL_LEN: u64 = 16 * 1024;
+
+/// Files with identical contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DupeGroup {
+    /// The size of each file, in bytes.
+    pub size: u64,
+    /// Sorted paths.
+    pub paths: Vec<PathBuf>,
+}
+
+/// Which file of each group [`crate::run::FsAction::SelectDuplicates`] leaves unselected.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::EnumString,
+)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum DupeKeep {
+    /// The most recently modified file.
+    #[default]
+    Newest,
+    /// The least recently modified file.
+    Oldest,
+    /// The file with the shortest path.
+    Shortest,
+}
+
+impl DupeGroup {
+    /// Bytes freed by keeping only one of the files.
+    pub fn wasted(&self) -> u64 {
+        self.size * (self.paths.len() as u64).saturating_sub(1)
+    }
+}
+
+/// Find the groups of duplicate files under `roots`, most wasted space first.
+///
+/// Directories are walked according to `vis` (hidden, ignore and predicates). Empty files,
+/// symlinks and further hard links to an already seen file are skipped.
+pub fn find_duplicates(
+    roots: &[PathBuf],
+    vis: Visibility,
+) -> Vec<DupeGroup> {
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    for (size, path) in walk(roots, vis) {
+        by_size.entry(size).or_default().push(path);
+    }
+    let candidates = by_size
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .collect();
+
+    let (small, large): (Vec<_>, Vec<_>) = regroup(candidates, Some(PARTIAL_LEN))
+        .into_iter()
+        .partition(|(size, _)| *size <= PARTIAL_LEN);
+
+    // the partial hash already covered the small files entirely
+    let mut groups: Vec<DupeGroup> = small
+        .into_iter()
+        .chain(regroup(large, None))
+        .map(|(size, mut paths)| {
+            paths.sort();
+            DupeGroup { size, paths }
+        })
+        .collect();
+
+    groups.sort_by(|a, b| {
+        b.wasted()
+            .cmp(&a.wasted())
+            .then_with(|| a.paths.cmp(&b.paths))
+    });
+    groups
+}
+
+/// The indices of every file but the kept one of each group, from `(index, group id, path)`
+/// triples.
+pub fn all_but_kept<'a>(
+    files: impl IntoIterator<Item = (u32, &'a str, &'a Path)>,
+    keep: DupeKeep,
+) -> Vec<u32> {
+    let mut groups: HashMap<&str, Vec<(u32, &Path)>> = HashMap::new();
+    for (i, group, path) in files {
+        groups.entry(group).or_default().push((i, path));
+    }
+
+    let mut ret = Vec::new();
+    for files in groups.into_values() {
+        let kept = match keep {
+            DupeKeep::Newest => files.iter().max_by_key(|(_, p)| mtime(p)),
+            DupeKeep::Oldest => files.iter().min_by_key(|(_, p)| mtime(p)),
+            DupeKeep::Shortest => files.iter().min_by_key(|(_, p)| (p.as_os_str().len(), *p)),
+        }
+        .map(|(i, _)| *i);
+        ret.extend(files.iter().map(|(i, _)| *i).filter(|i| Some(*i) != kept));
+    }
+    ret.sort_unstable();
+    ret
+}
+
+fn mtime(path: &Path) -> Option<std::time::SystemTime> {
+    path.metadata().and_then(|m| m.modified()).ok()
+}
+
+/// Split each group by the hash of (the first `len` bytes of) its files, dropping singletons
+/// and unreadable files.
+fn regroup(
+    groups: Vec<(u64, Vec<PathBuf>)>,
+    len: Option<u64>,
+) -> Vec<(u64, Vec<PathBuf>)> {
+    let hashed: Vec<_> = groups
+        .into_par_iter()
+        .flat_map_iter(|(size, paths)| paths.into_iter().map(move |p| (size, p)))
+        .filter_map(|(size, path)| {
+            let hash = hash_file(&path, len)
+                .inspect_err(|e| log::debug!("Failed to hash {}: {e}", path.display()))
+                .ok()?;
+            Some(((size, hash), path))
+        })
+        .collect();
+
+    let mut by_hash: HashMap<(u64, blake3::Hash), Vec<PathBuf>> = HashMap::new();
+    for (key, path) in hashed {
+        by_hash.entry(key).or_default().push(path);
+    }
+
+    by_hash
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|((size, _), paths)| (size, paths))
+        .collect()
+}
+
+fn hash_file(
+    path: &Path,
+    len: Option<u64>,
+) -> io::Result<blake3::Hash> {
+    let file = File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    match len {
+        Some(len) => io::copy(&mut file.take(len), &mut hasher)?,
+        None => io::copy(&mut io::BufReader::new(file), &mut hasher)?,
+    };
+    Ok(hasher.finalize())
+}
+
+/// The non-empty regular files under `roots` with their sizes.
+fn walk(
+    roots: &[PathBuf],
+    mut vis: Visibility,
+) -> Vec<(u64, PathBuf)> {
+    let Some((first, rest)) = roots.split_first() else {
+        return Vec::new();
+    };
+    if vis.all() {
+        vis.hidden = true;
+        vis.ignore = false;
+    }
+
+    let mut builder = WalkBuilder::new(first);
+    for root in rest {
+        builder.add(root);
+    }
+    builder
+        .standard_filters(true)
+        .hidden(!(vis.hidden || vis.hidden_only))
+        .git_ignore(vis.ignore)
+        .git_global(vis.ignore)
+        .git_exclude(vis.ignore)
+        .require_git(false)
+        .filter_entry(move |e| !(vis.ignore && is_vcs_dir(e.file_name())));
+
+    let mut seen = HashSet::new();
+    builder
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            let path = e.into_path();
+            if meta.len() == 0
+                || (vis.hidden_only && !path.is_hidden())
+                || !vis.predicates.matches_metadata(&path, &meta)
+                || !seen.insert(file_id(&path, &meta))
+            {
+                return None;
+            }
+            Some((meta.len(), path))
+        })
+        .collect()
+}
+
+/// Identifies the file behind a path, so that hard links and overlapping roots are only counted
+/// once.
+#[cfg(unix)]
+fn file_id(
+    _path: &Path,
+    meta: &std::fs::Metadata,
+) -> (u64, u64) {
+    use std::os::unix::fs::MetadataExt;
+    (meta.dev(), meta.ino())
+}
+
+#[cfg(not(unix))]
+fn file_id(
+    path: &Path,
+    _meta: &std::fs::Metadata,
+) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir(root.join("sub")).unwrap();
+
+        let big: Vec<u8> = (0..PARTIAL_LEN * 2).map(|i| (i % 251) as u8).collect();
+        let mut big_tail = big.clone();
+        *big_tail.last_mut().unwrap() ^= 1; // same size and first block
+
+        std::fs::write(root.join("a.txt"), "hello").unwrap();
+        std::fs::write(root.join("sub/b.txt"), "hello").unwrap();
+        std::fs::write(root.join("c.txt"), "world").unwrap(); // same size, different hash
+        std::fs::write(root.join("big1"), &big).unwrap();
+        std::fs::write(root.join("sub/big2"), &big).unwrap();
+        std::fs::write(root.join("big3"), &big_tail).unwrap();
+        std::fs::write(root.join("empty1"), "").unwrap();
+        std::fs::write(root.join("empty2"), "").unwrap();
+
+        let groups = find_duplicates(&[root.to_path_buf()], Visibility::default());
+        assert_eq!(
+            groups,
+            vec![
+                DupeGroup {
+                    size: big.len() as u64,
+                    paths: vec![root.join("big1"), root.join("sub/big2")],
+                },
+                DupeGroup {
+                    size: 5,
+                    paths: vec![root.join("a.txt"), root.join("sub/b.txt")],
+                },
+            ]
+        );
+
+        // overlapping roots don't report a file as its own duplicate
+        let groups = find_duplicates(
+            &[root.join("sub"), root.join("sub/big2")],
+            Visibility::default(),
+        );
+        assert!(groups.is_empty());
+
+        #[cfg(unix)]
+        {
+            std::fs::hard_link(root.join("c.txt"), root.join("sub/c-link")).unwrap();
+            let groups = find_duplicates(&[root.to_path_buf()], Visibility::default());
+            assert_eq!(groups.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_all_but_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let [a, b, c, d] = ["a", "bb", "c", "d"].map(|name| root.join(name));
+        for (path, age) in [(&a, 30), (&b, 10), (&c, 20), (&d, 0)] {
+            let file = File::create(path).unwrap();
+            let mtime = std::time::SystemTime::now() - std::time::Duration::from_secs(age * 60);
+            file.set_modified(mtime).unwrap();
+        }
+        let files = || {
+            [(0, "#1", &a), (1, "#1", &b), (2, "#1", &c), (5, "#2", &d)]
+                .map(|(i, g, p)| (i, g, p.as_path()))
+        };
+
+        assert_eq!(all_but_kept(files(), DupeKeep::Newest), vec![0, 2]);
+        assert_eq!(all_but_kept(files(), DupeKeep::Oldest), vec![1, 2]);
+        assert_eq!(all_but_kept(files(), DupeKeep::Shortest), vec![1, 2]);
+        assert_eq!("OLDEST".parse(), Ok(DupeKeep::Oldest));
+    }
+}
@@ -1,3 +1,7 @@
+pub mod compare;
+pub mod dupes;
+pub mod replace;
+
 use std::{
     ffi::OsStr,
     io,
@@ -0,0 +1,276 @@
+//! Search and replace over the files matched by [`crate::run::FsPane::Search`].
+
+use std::{
+    borrow::Cow,
+    collections::BTreeSet,
+    io::{self, Write},
+    path::Path,
+};
+
+use fist_types::When;
+use regex::{Regex, RegexBuilder};
+
+/// The Search pane's patterns, compiled together with a replacement.
+#[derive(Debug, Clone)]
+pub struct Replacer {
+    regex: Regex,
+    /// The replacement, with `$1` / `${name}` referring to capture groups and `$$`
+    /// standing for `$`.
+    pub replacement: String,
+}
+
+impl Replacer {
+    /// Compile `patterns` with rg's semantics: any of them matches, literally when
+    /// `fixed_strings`. `case` as in the Search pane: [`When::Auto`] is smart case.
+    pub fn new(
+        patterns: &[String],
+        fixed_strings: bool,
+        case: When,
+        replacement: String,
+    ) -> Result<Self, regex::Error> {
+        let pattern = patterns
+            .iter()
+            .map(|p| {
+                if fixed_strings {
+                    format!("(?:{})", regex::escape(p))
+                } else {
+                    format!("(?:{p})")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("|");
+        let case_insensitive = match case {
+            When::Always => false,
+            When::Never => true,
+            When::Auto => !patterns.iter().any(|p| p.chars().any(char::is_uppercase)),
+        };
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(case_insensitive)
+            .multi_line(true)
+            .build()?;
+        Ok(Self { regex, replacement })
+    }
+
+    /// The line with all matches replaced, if any matched.
+    pub fn replace_line<'a>(&self, line: &'a str) -> Option<Cow<'a, str>> {
+        self.regex
+            .is_match(line)
+            .then(|| self.regex.replace_all(line, self.replacement.as_str()))
+    }
+
+    /// The line as runs of unchanged text and replaced matches, if any matched.
+    pub fn diff_line<'a>(&self, line: &'a str) -> Option<Vec<Segment<'a>>> {
+        let mut segments = vec![];
+        let mut last = 0;
+        for caps in self.regex.captures_iter(line) {
+            let m = caps.get(0).unwrap();
+            if m.start() > last {
+                segments.push(Segment::Keep(&line[last..m.start()]));
+            }
+            let mut new = String::new();
+            caps.expand(&self.replacement, &mut new);
+            segments.push(Segment::Replace(m.as_str(), new));
+            last = m.end();
+        }
+        if segments.is_empty() {
+            return None;
+        }
+        if last < line.len() {
+            segments.push(Segment::Keep(&line[last..]));
+        }
+        Some(segments)
+    }
+
+    /// Replace the matches on the given (1-based) `lines` of `content`, or in all of
+    /// it when `None`. Line endings are preserved. Returns the new content and the
+    /// number of changed lines, or `None` if nothing changed.
+    pub fn apply(&self, content: &str, lines: Option<&BTreeSet<u32>>) -> Option<(String, usize)> {
+        let mut ret = String::with_capacity(content.len());
+        let mut changed = 0;
+        for (i, line) in content.split_inclusive('\n').enumerate() {
+            if lines.is_some_and(|l| !l.contains(&(i as u32 + 1))) {
+                ret.push_str(line);
+                continue;
+            }
+            let (text, ending) = split_ending(line);
+            match self.replace_line(text) {
+                Some(new) if new != text => {
+                    changed += 1;
+                    ret.push_str(&new);
+                    ret.push_str(ending);
+                }
+                _ => ret.push_str(line),
+            }
+        }
+        (changed > 0).then_some((ret, changed))
+    }
+}
+
+/// A run of [`Replacer::diff_line`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment<'a> {
+    Keep(&'a str),
+    /// The matched text and its replacement.
+    Replace(&'a str, String),
+}
+
+fn split_ending(line: &str) -> (&str, &str) {
+    let text = line.trim_end_matches(['\n', '\r']);
+    (text, &line[text.len()..])
+}
+
+/// Replace the contents of `path` by writing to a temporary file in the same
+/// directory and renaming it over the original, keeping its permissions,
+/// owner and extended attributes. A symlink is followed, replacing its target.
+/// Files whose metadata can't be carried over (or with other hard links) are
+/// written in place instead.
+pub fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    let path = &std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let meta = std::fs::metadata(path).ok();
+    if meta.as_ref().is_some_and(has_links) {
+        return std::fs::write(path, content);
+    }
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = dir.join(format!(".{name}.{}.tmp", std::process::id()));
+
+    // Ok(false): the metadata could not be carried over
+    let renamed = (|| {
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        if let Some(meta) = &meta {
+            std::fs::set_permissions(&tmp, meta.permissions())?;
+            if keep_metadata(path, &tmp, meta).is_err() {
+                return Ok(false);
+            }
+        }
+        std::fs::rename(&tmp, path).map(|()| true)
+    })();
+    match renamed {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            let _ = std::fs::remove_file(&tmp);
+            std::fs::write(path, content)
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&tmp);
+            Err(e)
+        }
+    }
+}
+
+#[cfg(unix)]
+fn has_links(meta: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    meta.nlink() > 1
+}
+
+#[cfg(not(unix))]
+fn has_links(_: &std::fs::Metadata) -> bool {
+    false
+}
+
+/// Give `to` the owner and extended attributes of `from`.
+#[cfg(unix)]
+fn keep_metadata(from: &Path, to: &Path, meta: &std::fs::Metadata) -> io::Result<()> {
+    use std::os::unix::fs::MetadataExt;
+    std::os::unix::fs::chown(to, Some(meta.uid()), Some(meta.gid()))?;
+    for attr in xattr::list(from)? {
+        if let Some(value) = xattr::get(from, &attr)? {
+            xattr::set(to, &attr, &value)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn keep_metadata(_: &Path, _: &Path, _: &std::fs::Metadata) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replacer(patterns: &[&str], fixed: bool, case: When, replacement: &str) -> Replacer {
+        let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+        Replacer::new(&patterns, fixed, case, replacement.into()).unwrap()
+    }
+
+    #[test]
+    fn test_replace_line() {
+        let r = replacer(&[r"fn (\w+)"], false, When::Auto, "fn ${1}_v2");
+        assert_eq!(r.replace_line("pub fn foo() {}").unwrap(), "pub fn foo_v2() {}");
+        assert_eq!(r.replace_line("let x = 1;"), None);
+        assert_eq!(
+            r.diff_line("fn a; fn b").unwrap(),
+            [
+                Segment::Replace("fn a", "fn a_v2".into()),
+                Segment::Keep("; "),
+                Segment::Replace("fn b", "fn b_v2".into()),
+            ]
+        );
+
+        // smart case: lowercase patterns ignore case
+        let r = replacer(&["a.b", "todo"], true, When::Auto, "$$x");
+        assert_eq!(r.replace_line("a.b axb TODO").unwrap(), "$x axb $x");
+        let r = replacer(&["Todo"], true, When::Auto, "-");
+        assert_eq!(r.replace_line("TODO Todo").unwrap(), "TODO -");
+    }
+
+    #[test]
+    fn test_apply_lines() {
+        let r = replacer(&["old"], false, When::Always, "new");
+        let content = "old\r\nkeep old\nold";
+
+        let (all, n) = r.apply(content, None).unwrap();
+        assert_eq!((all.as_str(), n), ("new\r\nkeep new\nnew", 3));
+
+        let lines = BTreeSet::from([1, 3]);
+        let (some, n) = r.apply(content, Some(&lines)).unwrap();
+        assert_eq!((some.as_str(), n), ("new\r\nkeep old\nnew", 2));
+
+        assert_eq!(r.apply("nothing", None), None);
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        std::fs::write(&path, "before").unwrap();
+
+        write_atomic(&path, b"after").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "after");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target.txt");
+        let link = dir.path().join("link.txt");
+        std::fs::write(&target, "before").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write_atomic(&link, b"after").unwrap();
+        assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "after");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_hard_link() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        let other = dir.path().join("other.txt");
+        std::fs::write(&path, "before").unwrap();
+        std::fs::hard_link(&path, &other).unwrap();
+
+        write_atomic(&path, b"after").unwrap();
+        assert_eq!(std::fs::read_to_string(&other).unwrap(), "after");
+    }
+}
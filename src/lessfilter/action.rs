@@ -8,7 +8,7 @@ use serde::{Deserialize, Deserializer};
 use crate::arr;
 use crate::cli::paths::current_exe;
 use crate::lessfilter::Preset;
-use crate::lessfilter::helpers::{application_icon_path, image_viewer, infer_editor, infer_visual};
+use crate::lessfilter::helpers::{application_icon_path, infer_editor, infer_visual};
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, serde::Serialize)]
 #[serde(untagged)]
@@ -67,6 +67,9 @@ pub enum CommandStrategy {
     Metadata(PathBuf),
     /// Show an error message and wait for keypress in-process.
     ShowError(String),
+    /// Display the image at [PathBuf] in-process, at most [u8] columns wide (see
+    /// [`crate::lessfilter::image`]).
+    Image(PathBuf, Option<u8>),
     /// Spawn the given program with args.
     Prog(OsString, Vec<OsString>),
     /// No program to spawn (empty command line).
@@ -92,7 +95,7 @@ impl Action {
         path: &Path,
         preset: Preset,
     ) -> (ArrayVec<CommandStrategy, 5>, [bool; 3]) {
-        use CommandStrategy::{Header, Metadata, Pager, Prog, ShowError};
+        use CommandStrategy::{Header, Image, Metadata, Pager, Prog, ShowError};
 
         match preset {
             Preset::Default => return Default::default(), // do nothing, should be unreachable
@@ -180,14 +183,10 @@ impl Action {
             },
             Action::Image => match preset {
                 Preset::Preview | Preset::Display => {
-                    (arr![image_viewer(path, None).into()], [true, false, false])
+                    (arr![Image(path.into(), None)], [true, false, false])
                 }
                 Preset::Extended => (
-                    arr![
-                        Header,
-                        image_viewer(path, None).into(),
-                        Metadata(path.into())
-                    ],
+                    arr![Header, Image(path.into(), None), Metadata(path.into())],
                     [true, false, false],
                 ),
                 Preset::Info => (arr![Header, Metadata(path.into())], [true, false, false]),
@@ -208,21 +207,21 @@ impl Action {
                     Preset::Preview | Preset::Display => {
                         // fallback to directory display if no icon found
                         let ac = if let Some(icon) = display_path {
-                            image_viewer(icon, Some(16))
+                            Image(icon.into(), Some(16))
                         } else {
-                            application_fallback(path)
+                            application_fallback(path).into()
                         };
-                        (arr![ac.into()], [false, false, false])
+                        (arr![ac], [false, false, false])
                     }
                     Preset::Extended => {
                         let ac = if let Some(icon) = display_path {
-                            image_viewer(icon, Some(16))
+                            Image(icon.into(), Some(16))
                         } else {
-                            application_fallback(path)
+                            application_fallback(path).into()
                         };
 
                         (
-                            arr![Header, ac.into(), Metadata(path.into())],
+                            arr![Header, ac, Metadata(path.into())],
                             [true, false, true],
                         )
                     }
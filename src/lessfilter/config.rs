@@ -107,6 +107,8 @@ pub struct LessfilterSettings {
 #[derive(Default, Debug, serde::Deserialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct RunSettings {
+    /// Program (and arguments) displaying images, called with the path. Empty = the built-in
+    /// viewer (see [`crate::lessfilter::image`]).
     pub image_viewer: Vec<String>,
 }
 
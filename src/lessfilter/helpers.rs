@@ -168,62 +168,12 @@ fn count_file<P: AsRef<Path>>(path: P) -> Result<[usize; 3], MapReaderError<Stri
     Ok([chars, words, lines])
 }
 
-// ----------------- IMAGE ---------------------------------
+// ----------------- VISUAL ---------------------------------
 
 thread_local! {
-    // vs lazycell?
-    static CHAFA_FORMAT: OnceCell<&'static str> = const { OnceCell::new() };
     static VISUAL: OnceCell<Vec<String>> = const { OnceCell::new() };
 }
 
-fn infer_chafa_format() -> &'static str {
-    CHAFA_FORMAT.with(|cell| {
-        *cell.get_or_init(|| {
-            if let Ok(v) = env::var("CHAFA_FORMAT") {
-                if !v.is_empty() {
-                    return Box::leak(v.into_boxed_str());
-                }
-            }
-
-            if env::var_os("KITTY_WINDOW_ID").is_some() {
-                return "kitty";
-            }
-
-            if env::var_os("ITERM_SESSION_ID").is_some() {
-                return "iterm";
-            }
-
-            let sixel = Command::new("infocmp")
-                .output()
-                .ok()
-                .map(|o| o.status.success() && String::from_utf8_lossy(&o.stdout).contains("sixel"))
-                .unwrap_or(false);
-
-            if sixel { "sixels" } else { "symbols" }
-        })
-    })
-}
-
-pub fn image_viewer(path: &Path, max_w: Option<u8>) -> Vec<OsString> {
-    let mut args = vec_![: "chafa", "-f", infer_chafa_format()];
-
-    if let Some(w) = max_w {
-        // Read lines from env
-        let terminal_lines = env::var("LINES")
-            .ok()
-            .and_then(|v| v.parse::<usize>().ok())
-            .unwrap_or(24);
-
-        // Apply both width and height bounds so it doesn't overflow vertically
-        args.push(format!("--size={}x{}", w, terminal_lines).into());
-    }
-
-    args.push("--scale=max".into()); // default
-    args.push(path.into());
-
-    args
-}
-
 pub fn infer_visual(path: &Path) -> Vec<OsString> {
     VISUAL.with(|cell| {
         cell.get_or_init(|| {
@@ -0,0 +1,545 @@
+//! In-process image previews.
+//!
+//! The image is scaled to fit the preview area (`COLUMNS`x`LINES` cells) and written in the
+//! graphics protocol of the terminal ([`Protocol::detect`]). Scaled thumbnails are cached on
+//! disk as PNG, keyed by the path, size and modification time of the image and the target size.
+//! The cache is kept under [`CACHE_MAX`] bytes by evicting the least recently used thumbnails.
+//!
+//! Rendering runs in the lessfilter process, which the previewer kills when the cursor moves, so
+//! a slow decode never outlives its preview.
+
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    process::Command,
+    time::SystemTime,
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use image::{ImageFormat, ImageReader, RgbaImage, imageops::FilterType};
+
+use crate::cli::paths::cache_dir;
+
+/// How images are written to the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// The kitty graphics protocol (kitty, ghostty).
+    Kitty,
+    Sixel,
+    /// iTerm2 inline images (iTerm2, WezTerm).
+    Iterm,
+    /// Unicode half blocks in true color, for any other terminal.
+    Blocks,
+}
+
+impl Protocol {
+    /// `FS_IMAGE_PROTOCOL` (`kitty`, `sixel`, `iterm` or `blocks`), else inferred from the
+    /// environment and the terminfo entry.
+    pub fn detect() -> Self {
+        if let Ok(v) = env::var("FS_IMAGE_PROTOCOL") {
+            match v.as_str() {
+                "kitty" => return Self::Kitty,
+                "sixel" | "sixels" => return Self::Sixel,
+                "iterm" => return Self::Iterm,
+                "blocks" | "symbols" => return Self::Blocks,
+                _ => log::warn!("Unknown FS_IMAGE_PROTOCOL: {v}"),
+            }
+        }
+
+        let term = env::var("TERM").unwrap_or_default();
+        let program = env::var("TERM_PROGRAM").unwrap_or_default();
+        if env::var_os("KITTY_WINDOW_ID").is_some()
+            || term == "xterm-kitty"
+            || term == "xterm-ghostty"
+            || program == "ghostty"
+        {
+            return Self::Kitty;
+        }
+        if env::var_os("ITERM_SESSION_ID").is_some()
+            || program == "iTerm.app"
+            || program == "WezTerm"
+        {
+            return Self::Iterm;
+        }
+
+        let sixel = Command::new("infocmp").output().ok().is_some_and(|o| {
+            o.status.success() && String::from_utf8_lossy(&o.stdout).contains("sixel")
+        });
+        if sixel { Self::Sixel } else { Self::Blocks }
+    }
+
+    /// Image pixels per terminal cell, given the pixel size of a cell.
+    fn pixels_per_cell(
+        self,
+        cell: (u32, u32),
+    ) -> (u32, u32) {
+        match self {
+            Self::Blocks => (1, 2),
+            _ => cell,
+        }
+    }
+}
+
+/// Display `path` in the preview area, at most `max_w` columns wide. Returns whether the image
+/// could be displayed.
+pub fn show(
+    path: &Path,
+    max_w: Option<u8>,
+) -> bool {
+    let protocol = Protocol::detect();
+    let mut out = BufWriter::new(io::stdout().lock());
+    match render(path, protocol, area(max_w), &mut out).and_then(|_| out.flush()) {
+        Ok(()) => true,
+        Err(e) => {
+            log::error!("Failed to display {}: {e}", path.display());
+            false
+        }
+    }
+}
+
+/// The preview area in cells, from `COLUMNS` and `LINES`.
+fn area(max_w: Option<u8>) -> (u32, u32) {
+    let var = |name: &str, default: u32| {
+        env::var(name)
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(default)
+    };
+    let cols = var("COLUMNS", 80);
+    let cols = max_w.map_or(cols, |w| cols.min(w.into()));
+    (cols, var("LINES", 24))
+}
+
+/// The pixel size of a terminal cell, as reported by the terminal, else 8x16.
+fn cell_size() -> (u32, u32) {
+    let size = File::open("/dev/tty").ok().and_then(|tty| {
+        use std::os::fd::AsRawFd;
+        let mut ws: libc::winsize = unsafe { std::mem::zeroed() };
+        let ret = unsafe { libc::ioctl(tty.as_raw_fd(), libc::TIOCGWINSZ, &mut ws) };
+        (ret == 0 && ws.ws_col > 0 && ws.ws_row > 0 && ws.ws_xpixel > 0 && ws.ws_ypixel > 0).then(
+            || {
+                (
+                    u32::from(ws.ws_xpixel / ws.ws_col),
+                    u32::from(ws.ws_ypixel / ws.ws_row),
+                )
+            },
+        )
+    });
+    size.filter(|(w, h)| *w > 0 && *h > 0).unwrap_or((8, 16))
+}
+
+/// The size in pixels of an image of `dims` scaled to fit `area` cells, with `per_cell` pixels
+/// in a cell of `cell` screen pixels.
+fn fit(
+    dims: (u32, u32),
+    area: (u32, u32),
+    cell: (u32, u32),
+    per_cell: (u32, u32),
+) -> (u32, u32) {
+    let (w, h) = (dims.0.max(1) as f64, dims.1.max(1) as f64);
+    // screen pixels
+    let scale = f64::min((area.0 * cell.0) as f64 / w, (area.1 * cell.1) as f64 / h);
+    let to_pixels = |screen: f64, cell: u32, per_cell: u32| {
+        ((screen * per_cell as f64 / cell as f64).floor() as u32).max(1)
+    };
+    (
+        to_pixels(w * scale, cell.0, per_cell.0),
+        to_pixels(h * scale, cell.1, per_cell.1),
+    )
+}
+
+/// Write the image at `path`, scaled to fit `area`, to `out`.
+fn render(
+    path: &Path,
+    protocol: Protocol,
+    area: (u32, u32),
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let dims = ImageReader::open(path)?
+        .with_guessed_format()?
+        .into_dimensions()
+        .map_err(io::Error::other)?;
+    let cell = cell_size();
+    let (w, h) = fit(dims, area, cell, protocol.pixels_per_cell(cell));
+    let cells = (w.div_ceil(cell.0), h.div_ceil(cell.1));
+
+    if protocol == Protocol::Iterm {
+        // the terminal scales the original
+        return write_iterm(&fs::read(path)?, cells, out);
+    }
+    let image = thumbnail(&cache_dir().join("thumbnails"), path, (w, h))?;
+    match protocol {
+        Protocol::Kitty => write_kitty(&image, cells, out),
+        Protocol::Sixel => write_sixel(&image, out),
+        _ => write_blocks(&image, out),
+    }
+}
+
+/// The size of the thumbnail cache above which the least recently used thumbnails are evicted.
+const CACHE_MAX: u64 = 64 * 1024 * 1024;
+
+/// The image at `path` scaled to `size`, from the cache in `dir` when present.
+fn thumbnail(
+    dir: &Path,
+    path: &Path,
+    size: (u32, u32),
+) -> io::Result<RgbaImage> {
+    let cached = thumbnail_path(dir, path, size)?;
+    if let Ok(image) = image::open(&cached).map(|i| i.to_rgba8())
+        && image.dimensions() == size
+    {
+        // the modification time orders the eviction
+        if let Err(e) = File::options()
+            .write(true)
+            .open(&cached)
+            .and_then(|f| f.set_modified(SystemTime::now()))
+        {
+            log::debug!("Failed to touch {}: {e}", cached.display());
+        }
+        return Ok(image);
+    }
+
+    let image = ImageReader::open(path)?
+        .with_guessed_format()?
+        .decode()
+        .map_err(io::Error::other)?
+        .resize_exact(size.0, size.1, FilterType::Triangle)
+        .to_rgba8();
+    // written aside first: the previewer may kill this process mid-write
+    let partial = cached.with_extension("part");
+    let saved = fs::create_dir_all(dir)
+        .and_then(|_| {
+            image
+                .save_with_format(&partial, ImageFormat::Png)
+                .map_err(io::Error::other)
+        })
+        .and_then(|_| fs::rename(&partial, &cached));
+    match saved {
+        Ok(()) => evict(dir, CACHE_MAX),
+        Err(e) => log::warn!("Failed to cache the thumbnail of {}: {e}", path.display()),
+    }
+    Ok(image)
+}
+
+/// Remove the least recently used thumbnails of `dir` until it holds at most `max` bytes.
+fn evict(
+    dir: &Path,
+    max: u64,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = entries
+        .filter_map(|e| {
+            let e = e.ok()?;
+            let meta = e.metadata().ok()?;
+            if !meta.is_file() {
+                return None;
+            }
+            Some((meta.modified().ok()?, meta.len(), e.path()))
+        })
+        .collect();
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    if total <= max {
+        return;
+    }
+    files.sort();
+    for (_, len, path) in files {
+        if total <= max {
+            break;
+        }
+        match fs::remove_file(&path) {
+            Ok(()) => total -= len,
+            Err(e) => log::debug!("Failed to evict {}: {e}", path.display()),
+        }
+    }
+}
+
+fn thumbnail_path(
+    dir: &Path,
+    path: &Path,
+    size: (u32, u32),
+) -> io::Result<PathBuf> {
+    let meta = fs::metadata(path)?;
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(path.as_os_str().as_encoded_bytes());
+    for n in [
+        meta.len(),
+        meta.mtime() as u64,
+        meta.mtime_nsec() as u64,
+        size.0.into(),
+        size.1.into(),
+    ] {
+        hasher.update(&n.to_le_bytes());
+    }
+    Ok(dir.join(format!("{}.png", hasher.finalize().to_hex())))
+}
+
+/// Base64 chunk size of the kitty graphics protocol.
+const KITTY_CHUNK: usize = 4096;
+
+fn write_kitty(
+    image: &RgbaImage,
+    cells: (u32, u32),
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let data = STANDARD.encode(image.as_raw());
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            write!(
+                out,
+                "\x1b_Ga=T,f=32,q=2,s={},v={},c={},r={},m={more};",
+                image.width(),
+                image.height(),
+                cells.0,
+                cells.1
+            )?;
+        } else {
+            write!(out, "\x1b_Gm={more};")?;
+        }
+        out.write_all(chunk)?;
+        out.write_all(b"\x1b\\")?;
+    }
+    writeln!(out)
+}
+
+fn write_iterm(
+    data: &[u8],
+    cells: (u32, u32),
+    out: &mut impl Write,
+) -> io::Result<()> {
+    write!(
+        out,
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+        data.len(),
+        cells.0,
+        cells.1,
+        STANDARD.encode(data)
+    )?;
+    writeln!(out)
+}
+
+/// Pixels more transparent than this are left blank.
+const ALPHA_THRESHOLD: u8 = 128;
+
+/// The index of the color of `p` in the 6x6x6 color cube, `None` when transparent.
+fn cube_index(p: &image::Rgba<u8>) -> Option<u8> {
+    let [r, g, b, a] = p.0;
+    let level = |c: u8| (u16::from(c) * 5 + 127) / 255;
+    (a >= ALPHA_THRESHOLD).then(|| (level(r) * 36 + level(g) * 6 + level(b)) as u8)
+}
+
+fn write_sixel(
+    image: &RgbaImage,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let (w, h) = image.dimensions();
+    // P2=1: transparent pixels keep the background
+    write!(out, "\x1bP0;1q\"1;1;{w};{h}")?;
+    for i in 0..216u16 {
+        let percent = |level: u16| level * 100 / 5;
+        write!(
+            out,
+            "#{i};2;{};{};{}",
+            percent(i / 36),
+            percent(i / 6 % 6),
+            percent(i % 6)
+        )?;
+    }
+
+    for band in (0..h).step_by(6) {
+        // the sixels of each color in this band
+        let mut colors: Vec<(u8, Vec<u8>)> = Vec::new();
+        for dy in 0..6.min(h - band) {
+            for x in 0..w {
+                let Some(c) = cube_index(image.get_pixel(x, band + dy)) else {
+                    continue;
+                };
+                let row = match colors.iter().position(|(k, _)| *k == c) {
+                    Some(i) => &mut colors[i].1,
+                    None => {
+                        colors.push((c, vec![0; w as usize]));
+                        &mut colors.last_mut().unwrap().1
+                    }
+                };
+                row[x as usize] |= 1 << dy;
+            }
+        }
+        for (i, (c, row)) in colors.iter().enumerate() {
+            if i > 0 {
+                // back to the start of the band
+                out.write_all(b"$")?;
+            }
+            write!(out, "#{c}")?;
+            write_sixel_row(row, out)?;
+        }
+        out.write_all(b"-")?;
+    }
+    out.write_all(b"\x1b\\")?;
+    writeln!(out)
+}
+
+/// A row of sixels, run-length encoded.
+fn write_sixel_row(
+    row: &[u8],
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut i = 0;
+    while i < row.len() {
+        let run = row[i..].iter().take_while(|s| **s == row[i]).count();
+        let c = 63 + row[i];
+        if run > 3 {
+            write!(out, "!{run}{}", c as char)?;
+        } else {
+            out.write_all(&vec![c; run])?;
+        }
+        i += run;
+    }
+    Ok(())
+}
+
+fn write_blocks(
+    image: &RgbaImage,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let (w, h) = image.dimensions();
+    let visible = |x: u32, y: u32| {
+        (y < h)
+            .then(|| image.get_pixel(x, y))
+            .filter(|p| p.0[3] >= ALPHA_THRESHOLD)
+            .map(|p| (p.0[0], p.0[1], p.0[2]))
+    };
+    for y in (0..h).step_by(2) {
+        for x in 0..w {
+            match (visible(x, y), visible(x, y + 1)) {
+                (Some((r, g, b)), Some((br, bg, bb))) => {
+                    write!(out, "\x1b[38;2;{r};{g};{b};48;2;{br};{bg};{bb}m▀")?
+                }
+                (Some((r, g, b)), None) => write!(out, "\x1b[0;38;2;{r};{g};{b}m▀")?,
+                (None, Some((r, g, b))) => write!(out, "\x1b[0;38;2;{r};{g};{b}m▄")?,
+                (None, None) => write!(out, "\x1b[0m ")?,
+            }
+        }
+        writeln!(out, "\x1b[0m")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn checkers(size: u32) -> RgbaImage {
+        RgbaImage::from_fn(size, size, |x, y| {
+            if (x + y) % 2 == 0 {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 0, 0])
+            }
+        })
+    }
+
+    #[test]
+    fn test_fit() {
+        // a square image in a wide area is bounded by the height
+        assert_eq!(fit((100, 100), (80, 24), (8, 16), (8, 16)), (384, 384));
+        // half blocks: one pixel per column, two per row
+        assert_eq!(fit((100, 100), (80, 24), (8, 16), (1, 2)), (48, 48));
+        // a wide image is bounded by the width, and small images are scaled up
+        assert_eq!(fit((40, 10), (10, 10), (8, 16), (8, 16)), (80, 20));
+    }
+
+    #[test]
+    fn test_thumbnail_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("image.png");
+        checkers(8).save(&path).unwrap();
+        let cache = dir.path().join("cache");
+
+        let thumb = thumbnail(&cache, &path, (4, 2)).unwrap();
+        assert_eq!(thumb.dimensions(), (4, 2));
+        let cached = thumbnail_path(&cache, &path, (4, 2)).unwrap();
+        assert!(cached.exists());
+        // served from the cache
+        fs::write(&path, "not an image").unwrap();
+        let key = thumbnail_path(&cache, &path, (4, 2)).unwrap();
+        assert_ne!(key, cached);
+        fs::rename(&cached, &key).unwrap();
+        assert_eq!(thumbnail(&cache, &path, (4, 2)).unwrap(), thumb);
+        // stored as PNG
+        assert_eq!(
+            ImageReader::open(&key)
+                .unwrap()
+                .with_guessed_format()
+                .unwrap()
+                .format(),
+            Some(ImageFormat::Png)
+        );
+    }
+
+    #[test]
+    fn test_evict() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = SystemTime::now();
+        for (i, name) in ["old", "mid", "new"].iter().enumerate() {
+            let path = dir.path().join(name);
+            fs::write(&path, [0; 10]).unwrap();
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - std::time::Duration::from_secs(60 * (3 - i as u64)))
+                .unwrap();
+        }
+
+        evict(dir.path(), 30);
+        assert!(dir.path().join("old").exists());
+
+        // the least recently used go first
+        evict(dir.path(), 25);
+        assert!(!dir.path().join("old").exists());
+        assert!(dir.path().join("mid").exists());
+        evict(dir.path(), 0);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_encoders() {
+        let image = checkers(4);
+
+        let mut out = Vec::new();
+        write_blocks(&image, &mut out).unwrap();
+        let blocks = String::from_utf8(out).unwrap();
+        assert_eq!(blocks.lines().count(), 2);
+        assert!(blocks.contains("38;2;255;0;0m▀"));
+        assert!(blocks.contains("38;2;255;0;0m▄"));
+
+        let mut out = Vec::new();
+        write_sixel(&image, &mut out).unwrap();
+        let sixel = String::from_utf8(out).unwrap();
+        assert!(sixel.starts_with("\x1bP0;1q\"1;1;4;4"));
+        assert!(sixel.trim_end().ends_with("-\x1b\\"));
+        // only the red pixels are drawn, in the red of the cube
+        assert!(sixel.contains("#180"));
+        assert!(!sixel.contains("$"));
+
+        let mut out = Vec::new();
+        write_kitty(&image, (1, 1), &mut out).unwrap();
+        let kitty = String::from_utf8(out).unwrap();
+        assert!(kitty.starts_with("\x1b_Ga=T,f=32,q=2,s=4,v=4,c=1,r=1,m=0;"));
+    }
+
+    #[test]
+    fn test_sixel_rle() {
+        let mut out = Vec::new();
+        write_sixel_row(&[1, 1, 1, 1, 1, 0, 2], &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "!5@?A");
+    }
+}
@@ -7,6 +7,7 @@ mod application_helper;
 mod config;
 pub mod file_rule;
 mod helpers;
+pub mod image;
 pub mod rule_matcher;
 pub use config::*;
 pub use helpers::env_bat_opts;
@@ -142,6 +143,16 @@ pub fn handle(
                         crate::utils::prompt::show_error(&[msg.as_str()]);
                         true
                     }
+                    CommandStrategy::Image(p, max_w) => match cfg.settings.run.image_viewer.split_first() {
+                        // a configured viewer replaces the built-in one
+                        Some((prog, args)) => Command::new(prog)
+                            .args(args)
+                            .arg(&p)
+                            .status()
+                            ._ebog()
+                            .is_some_and(|s| s.success()),
+                        None => image::show(&p, max_w),
+                    },
                     // an empty command line renders nothing; not a failure
                     CommandStrategy::None => true,
 
@@ -253,6 +264,9 @@ fn run_diagnose(preset: Preset, paths: Vec<PathBuf>, mut cfg: LessfilterConfig)
                                 CommandStrategy::ShowError(msg) => {
                                     format!("<showerror \"{msg}\">")
                                 }
+                                CommandStrategy::Image(p, _) => {
+                                    format!("<image {}>", p.display())
+                                }
                                 CommandStrategy::None => "<none>".to_string(),
 
                                 CommandStrategy::Prog(prog, args) => std::iter::once(prog)
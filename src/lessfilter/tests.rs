@@ -129,16 +129,14 @@ fn test_image_file_matching() {
 
     let progs = action.to_progs(&path, Preset::Preview);
     assert_eq!(progs.0.len(), 1);
-    assert!(matches!(&progs.0[0], CommandStrategy::Prog(prog, _) if prog == "chafa"));
+    assert_eq!(progs.0[0], CommandStrategy::Image(path.clone(), None));
 
     // Test extended preset for multiple commands
     let extended_action = best_action(&cfg, Preset::Extended, &path).unwrap();
     let extended_progs = extended_action.to_progs(&path, Preset::Extended);
     assert_eq!(extended_progs.0.len(), 3);
     assert_eq!(extended_progs.0[0], CommandStrategy::Header); // header
-    assert!(
-        matches!(&extended_progs.0[1], CommandStrategy::Prog(prog, _) if prog == "chafa") // image viewer
-    );
+    assert_eq!(extended_progs.0[1], CommandStrategy::Image(path.clone(), None)); // image viewer
     assert_eq!(extended_progs.0[2], CommandStrategy::Metadata(path.clone())); // metadata
 }
 
@@ -13,7 +13,11 @@ use matchmaker::nucleo::Span;
 
 use crate::{
     abspath::AbsPath,
-    run::state::{TOAST, ToastStyle},
+    run::{
+        FsAction,
+        stash::{StashOp, StashOperands},
+        state::{GLOBAL, TOAST, ToastStyle},
+    },
 };
 
 // The progress cell of the queue item whose command is running on this
@@ -48,6 +52,7 @@ pub fn execute(
     let lua = Lua::new();
     register_progress_global(&lua)?;
     register_toast_globals(&lua)?;
+    register_stash_globals(&lua)?;
     override_os_exit(&lua)?;
     let f = lua
         .load(source)
@@ -91,6 +96,7 @@ pub fn check_compiles(source: &str) -> Result<(), String> {
     let lua = Lua::new();
     register_progress_global(&lua)?;
     register_toast_globals(&lua)?;
+    register_stash_globals(&lua)?;
     lua.load(source)
         .into_function()
         .map(|_| ())
@@ -136,6 +142,68 @@ fn register_toast_globals(lua: &Lua) -> Result<(), String> {
     Ok(())
 }
 
+/// Register the stash globals: `stash_union(a, b, into)`, `stash_intersect`,
+/// `stash_diff` and `stash_symdiff` (`into` defaults to `a`),
+/// `stash_move(from, to)` and `stash_clear(name)`. Names default to the
+/// unnamed stash. Each dispatches the matching [`FsAction`], which runs after
+/// the script. Outside of the TUI (no render channel), they raise an error instead.
+fn register_stash_globals(lua: &Lua) -> Result<(), String> {
+    let globals = lua.globals();
+    if !GLOBAL::has_render_tx() {
+        let names = StashOp::ALL
+            .iter()
+            .map(|op| format!("stash_{op}"))
+            .chain(["stash_move".to_string(), "stash_clear".to_string()]);
+        for name in names {
+            let message = format!("{name}: stashes can only be changed from the TUI");
+            let f = lua
+                .create_function(move |_, _: MultiValue| {
+                    Err::<(), _>(mlua::Error::runtime(&message))
+                })
+                .map_err(|e| e.to_string())?;
+            globals.set(name, f).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+    for op in StashOp::ALL {
+        let f = lua
+            .create_function(
+                move |_, (a, b, into): (Option<String>, Option<String>, Option<String>)| {
+                    let operands = StashOperands {
+                        into,
+                        ..StashOperands::new(a.unwrap_or_default(), b.unwrap_or_default())
+                    };
+                    GLOBAL::send_action(op.action(operands));
+                    Ok(())
+                },
+            )
+            .map_err(|e| e.to_string())?;
+        let name = format!("stash_{op}");
+        globals.set(name, f).map_err(|e| e.to_string())?;
+    }
+
+    let move_fn = lua
+        .create_function(|_, (from, to): (Option<String>, Option<String>)| {
+            let operands = StashOperands::new(from.unwrap_or_default(), to.unwrap_or_default());
+            GLOBAL::send_action(FsAction::MoveStash(operands));
+            Ok(())
+        })
+        .map_err(|e| e.to_string())?;
+    let clear_fn = lua
+        .create_function(|_, name: Option<String>| {
+            GLOBAL::send_action(FsAction::ClearStash(name.unwrap_or_default()));
+            Ok(())
+        })
+        .map_err(|e| e.to_string())?;
+    globals
+        .set("stash_move", move_fn)
+        .map_err(|e| e.to_string())?;
+    globals
+        .set("stash_clear", clear_fn)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 /// Register the `set_progress(v)` global on `lua`. `v` is on the internal
 /// 0-255 scale and is written to the executing queue item's progress cell;
 /// see [`PROGRESS_TARGET`] for when a target exists.
@@ -354,4 +422,19 @@ mod tests {
         let res = execute(cmd, &[], "", None, None);
         assert!(res.is_ok(), "execute failed: {res:?}");
     }
+
+    #[test]
+    fn test_lua_stash_functions() {
+        GLOBAL::init_test_senders();
+        let cmd = r#"
+            stash_union("", "bookmark", "both")
+            stash_intersect("a", "b")
+            stash_diff("a", "b")
+            stash_symdiff("a", "b")
+            stash_move("a", "b")
+            stash_clear()
+        "#;
+        let res = execute(cmd, &[], "", None, None);
+        assert!(res.is_ok(), "execute failed: {res:?}");
+    }
 }
@@ -4,11 +4,11 @@
 use std::path::PathBuf;
 
 use cba::{bait::ResultExt, bath::PathExt, bring::split::join_with_single_quotes, unwrap};
-use fist_types::filters::SortOrder;
+use fist_types::{categories::FileCategory, filters::SortOrder};
 use matchmaker::{
     acs,
     action::{Action, Actions},
-    message::Interrupt,
+    message::{Event, Interrupt},
     nucleo::{Color, Modifier, Span, Style},
 };
 use ratatui::text::{Line, Text};
@@ -17,31 +17,44 @@ use crate::run::state::GLOBAL::db;
 use crate::{
     abspath::AbsPath,
     aliases::MMState,
-    cli::paths::__home,
-    clipboard::{copy_files, copy_paths_as_text},
-    config::{InsertionStrategy, StashPaneKind},
+    cli::paths::{__home, config_path},
+    clipboard::{self, copy_files, copy_paths_as_text},
+    config::{InsertionStrategy, SavedSearch, StashPaneKind},
     db::DbTable,
+    find::walker::list_dir,
+    fs::{
+        compare,
+        dupes::{self, DupeKeep},
+    },
     lessfilter::Preset,
     menu::{MenuEvaluationContext, MenuStrategy},
     run::{
-        ahandlers::{enter_dir_pane, enter_prompt, fs_reload, lock_prompt, refresh_prompt},
+        ahandlers::{
+            enter_dir_pane, enter_prompt, enter_tab, fs_reload, lock_prompt, refresh_header,
+            refresh_prompt, selected_hashes, switch_tab,
+        },
         item::short_display,
+        marks, mouse,
         pane::FsPane,
         queue::{
             BUILTIN_KINDS, QUEUE, QueueKind, QueueSelector, SelectorResult, show_queue_variant,
             validate_queue_kind,
         },
         register::{ExecutionMode, resolve_target},
+        replace::{self, ReplacePreview},
+        selection,
+        stash::{self, StashFormat, StashOp, StashOperands, export_entries},
         state::{
-            AcceptFlavor, ExecuteHandlerShouldProcessParent, FILTERS, GLOBAL, HideMetadata,
-            InPrompt, MENU_ACTIONS, MenuPrompt, STACK, STORE, TASKS, TOAST, ToastFlags, ToastStyle,
-            context::ActionContext, lessfilter_cfg, sort,
+            AcceptFlavor, COMMAND_HISTORY, CONFIG_PATH, ExecuteHandlerShouldProcessParent, FILTERS,
+            GLOBAL, HideMetadata, InPrompt, MENU_ACTIONS, MenuPrompt, SESSION_SEARCHES, STACK,
+            STORE, TASKS, TOAST, ToastFlags, ToastStyle, context::ActionContext, lessfilter_cfg,
+            sort,
         },
     },
     spawn::open_wrapped,
     ui::{
         confirm_overlay::ConfirmPrompt,
-        menu_overlay::{PromptKind, rename_prompt_for},
+        menu_overlay::{PromptKind, rename_prompt_for, replace_prompt},
     },
     unzip,
     utils::trash::trash,
@@ -73,12 +86,74 @@ pub enum FsAction {
     Jump(Vec<PathBuf>),
     /// Enter app launching pane. No-op when already in an app pane.
     App,
+    /// List every aliased file and directory. [`Self::SetAlias`] edits an alias in place, and
+    /// [`Self::Trash`]/[`Self::Delete`] remove it.
+    Aliases,
+    /// Find the duplicate files under the selected items, or else the current directory (see
+    /// [`FsPane::Dupes`]).
+    FindDuplicates,
+    /// In [`FsPane::Dupes`], select every file but the one to keep in each group, for
+    /// [`Self::Trash`]/[`Self::Delete`] to remove the rest.
+    SelectDuplicates(DupeKeep),
+    /// In [`FsPane::Nav`], expand or collapse the directory under the cursor in place, or the
+    /// directory of a nested file (see [`FsPane::Nav`]).
+    ToggleExpand,
+    /// In [`FsPane::Nav`], list this many more levels of directories (fewer when negative).
+    /// Resets the directories expanded or collapsed by [`Self::ToggleExpand`].
+    TreeDepth(isize),
+    /// Open the saved search `[searches.NAME]` (see [`crate::config::SavedSearch`]).
+    SavedSearch(String),
+    /// Save the current Find or Search pane to the config as `[searches.NAME]`.
+    /// Empty name = prompt for one.
+    SaveSearch(String),
+    /// Mark the current directory with a letter or digit (see [`marks`]).
+    /// No mark = list the marks, and take the next key as the mark.
+    SetMark(Option<char>),
+    /// Jump to the directory of a mark; `'` is the directory left by the last jump.
+    /// No mark = list the marks, and take the next key as the mark.
+    JumpMark(Option<char>),
 
     /// Go back
     Undo,
     /// Go forward
     Redo,
 
+    // Tabs
+    // ----------------------------------
+    /// Open a tab on the current directory. Each tab has its own panes, filters, sort and
+    /// selections, while the queue, stashes and clipboard are shared.
+    NewTab,
+    /// Switch to the next tab, wrapping around.
+    NextTab,
+    /// Close the current tab, unless it is the only one.
+    CloseTab,
+    /// Switch to the nth tab, counting from 1.
+    GotoTab(usize),
+    /// Toggle the dual-pane mode of the current tab: a second panel with its own panes, listed
+    /// in the preview, which [`Self::Copy`] and [`Self::Move`] transfer to.
+    Commander,
+    /// Switch to the other panel of the dual-pane mode.
+    SwitchPanel,
+    /// Select the files which differ between the directories of both panels, by name, size and
+    /// modification time (see [`crate::fs::compare::differs`]).
+    ComparePanels,
+
+    // Selection
+    // ----------------------------------
+    /// Toggle the visual mode: moving the cursor selects the items from where the mode was
+    /// entered to the cursor, on top of the selection it was entered with. Leaving it keeps the
+    /// selection.
+    Visual,
+    /// Select the items whose name matches the glob, or whose path does when it contains a
+    /// separator. Empty glob = prompt for one.
+    SelectMatching(String),
+    /// Select the files of a category (see [`FileCategory`]).
+    SelectCategory(FileCategory),
+    /// Select the files with the extension of the current item.
+    SelectSameExtension,
+    /// Select the unselected items, and deselect the others.
+    InvertSelection,
+
     // Display
     // ----------------------------------
     /// Display current filters.
@@ -95,16 +170,42 @@ pub enum FsAction {
     OpenStash(String),
     /// Add the selection (or cwd) to the named stash.
     PushStash(String),
+    /// Store the entries of either stash: `StashUnion(a,b[,into])`, see
+    /// [`StashOperands`].
+    StashUnion(StashOperands),
+    /// Store the entries of the first stash which are also in the second.
+    StashIntersect(StashOperands),
+    /// Store the entries of the first stash which are not in the second.
+    StashDiff(StashOperands),
+    /// Store the entries of exactly one of the stashes.
+    StashSymDiff(StashOperands),
+    /// Move all entries of a stash into another: `MoveStash(from,to)`.
+    MoveStash(StashOperands),
+    /// Remove all entries of the named stash. Empty name = the unnamed stash.
+    ClearStash(String),
+    /// Write the entries of the current stash pane to a file (relative to the
+    /// home directory; JSON for `.json` files, NUL-separated for `.nul`
+    /// files, otherwise one path per line), or to the clipboard when empty.
+    ExportStash(PathBuf),
+    /// Edit the note of the stash entry under the cursor (stash panes only).
+    /// The note is the entry's second column, and can be filtered on.
+    AnnotateStashEntry,
     /// Execute the queued operations selected by a queue-kind selector.
     ExecuteQueue(QueueSelector),
 
     /// Show available actions on the current item(s).
     ShowMenu,
+    /// Show the history of executed commands and menu actions, to re-run one
+    /// on the current item(s).
+    CommandHistory,
     /// Toggle directory/file visibility.
     /// In [`FsPane::Files`], [`FsPane::Folders`], [`FsPane::Launch`], [`FsPane::Rg`], this toggles their sort order.
     FsToggle,
     /// Toggle visibility between default and with hidden.
     ToggleHidden,
+    /// Sort the current pane by this order, or restore the unsorted order when already sorted by
+    /// it. Pairs with the size and mtime columns of [`crate::run::meta`].
+    Sort(SortOrder),
 
     // file actions
     // ----------------------------------
@@ -126,6 +227,17 @@ pub enum FsAction {
 
     /// Save the file to the backup directory. (todo)
     Backup,
+    /// Search pane only: preview replacing the matches with the given
+    /// replacement (`$1`, `${name}` expand capture groups), selecting all of
+    /// them. Prompts for the replacement when empty.
+    Replace(String),
+    /// Replace the selected matches of the preview. Each file is rewritten
+    /// atomically, after its contents are saved to the operation journal.
+    ApplyReplace,
+    /// End the replacement preview.
+    CancelReplace,
+    /// Restore the files overwritten by the latest journaled operation.
+    UndoOperation,
     /// Delete the file using system trash.
     Trash(bool),
     /// Permanently delete the file.
@@ -186,6 +298,8 @@ pub enum FsAction {
     AcceptPrompt,
     Filtering(Option<bool>),
     SetStatus(Option<Line<'static>>),
+    /// A left click, forwarded by [`mouse::listen`].
+    Click(mouse::Click),
 
     // Other
     // ----------------------------------
@@ -292,7 +406,8 @@ pub fn fsaction_aliaser(a: Action<FsAction>, state: &mut MMState<'_>) -> Actions
                     // db/rg panes
                     // order via SQL/rg, so a reload is required; everything
                     // else fills metadata and dispatches ReSort.
-                    if STACK::reloads_by_sorting() {
+                    // trees are also sorted as they populate
+                    if STACK::reloads_by_sorting() || STACK::with_current(FsPane::is_tree) {
                         fs_reload(state, false, false);
                     } else {
                         sort::fill_then_resort(state);
@@ -338,6 +453,7 @@ pub fn fsaction_aliaser(a: Action<FsAction>, state: &mut MMState<'_>) -> Actions
                 state.picker_ui.status.set(s);
                 acs![]
             }
+            FsAction::Click(click) => mouse::handle(click, state),
 
             // Actions which only trigger when not in the prompt:
             // -------------------------------------------------
@@ -391,6 +507,7 @@ pub fn fsaction_aliaser(a: Action<FsAction>, state: &mut MMState<'_>) -> Actions
             | FsAction::ShowOptions
             | FsAction::Confirm
             | FsAction::ShowMenu
+            | FsAction::CommandHistory
                 if state.overlay_index().is_some() =>
             {
                 acs![fa]
@@ -708,6 +825,105 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
             }
         }
 
+        FsAction::Aliases => {
+            let (content, index) = state.get_content_and_index();
+            STACK::save_input(content, index);
+
+            STACK::set_or_push(FsPane::new_aliases());
+            fs_reload(state, true, false);
+        }
+
+        FsAction::FindDuplicates => {
+            let (content, index) = state.get_content_and_index();
+            STACK::save_input(content, index);
+
+            let paths = if state.selections().is_empty() {
+                vec![]
+            } else {
+                state.map_selected_to_vec(|_i, x| x.path.inner())
+            };
+
+            STACK::push(FsPane::new_dupes(STACK::_cwd(), paths, FILTERS::visibility()));
+            fs_reload(state, true, false);
+        }
+
+        FsAction::SelectDuplicates(keep) => {
+            if !STACK::with_current(|p| matches!(p, FsPane::Dupes { .. })) {
+                return;
+            }
+            let items = state.picker_ui.worker.nucleo.items();
+            let indices = dupes::all_but_kept(
+                items.iter().filter_map(|(i, item)| match &item.tail {
+                    Ok([group, _]) => Some((i, group.as_str(), item.path.as_path())),
+                    Err(_) => None,
+                }),
+                keep,
+            );
+            state.picker_ui.selector.clear();
+            state.picker_ui.selector.extend(indices);
+        }
+
+        FsAction::ToggleExpand => {
+            let Some(cwd) = STACK::nav_cwd() else {
+                return;
+            };
+            let Some(item) = state.current_raw() else {
+                return;
+            };
+            let (content, mut index) = state.get_content_and_index();
+            let path = item.path.inner();
+            let level = path.strip_prefix(&cwd).map_or(0, |p| p.components().count());
+
+            let dir = if path.is_dir() {
+                path
+            } else if level > 1
+                && let Some(parent) = path.parent()
+            {
+                // collapse the directory of a nested file, and move onto it
+                if let Some(i) = (0..index).rev().find(|&i| {
+                    state
+                        .picker_ui
+                        .worker
+                        .get_nth(i)
+                        .is_some_and(|x| x.path.as_path() == parent)
+                }) {
+                    index = i;
+                }
+                parent.to_path_buf()
+            } else {
+                return;
+            };
+
+            STACK::with_current_mut(|p| {
+                if let FsPane::Nav { toggled, .. } = p
+                    && !toggled.remove(&dir)
+                {
+                    toggled.insert(dir);
+                }
+            });
+            // the rows above the directory are unchanged
+            STACK::save_input(content, index);
+            fs_reload(state, false, false);
+        }
+
+        FsAction::TreeDepth(delta) => {
+            let Some(new) = STACK::with_current_mut(|p| match p {
+                FsPane::Nav { depth, toggled, .. } => {
+                    let new = depth.saturating_add_signed(delta).max(1);
+                    let changed = new != *depth || !toggled.is_empty();
+                    *depth = new;
+                    toggled.clear();
+                    changed.then_some(new)
+                }
+                _ => None,
+            }) else {
+                return;
+            };
+            let style = Style::new().add_modifier(Modifier::DIM).italic();
+            TOAST::msg(Span::styled(format!("Depth: {new}"), style), true);
+            fs_reload(state, false, false);
+        }
+
         FsAction::Undo => {
             // save input
             let (content, index) = state.get_content_and_index();
@@ -729,6 +945,115 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
             };
         }
 
+        FsAction::NewTab => {
+            let (content, index) = state.get_content_and_index();
+            STACK::save_input(content, index);
+
+            let pane = FsPane::new_nav(STACK::_cwd(), FILTERS::visibility(), sort::get_sort().order);
+            STACK::new_tab(pane, selected_hashes(state));
+            fs_reload(state, true, false);
+        }
+        FsAction::NextTab => {
+            let (tabs, current) = STACK::tabs();
+            switch_tab(state, (current + 1) % tabs);
+        }
+        FsAction::GotoTab(n) => switch_tab(state, n.saturating_sub(1)),
+        FsAction::CloseTab => {
+            let Some(selections) = STACK::close_tab() else {
+                TOAST::msg(Line::from("Only one tab is open"), true);
+                return;
+            };
+            // the tab bar is gone, the parent listing of columns is kept
+            if STACK::tabs().0 == 1 {
+                state.picker_ui.header.clear(true);
+            }
+            enter_tab(state, selections);
+        }
+
+        FsAction::Commander => {
+            if STACK::close_panel() {
+                state.picker_ui.header.clear(true);
+            } else {
+                let pane =
+                    FsPane::new_nav(STACK::_cwd(), FILTERS::visibility(), sort::get_sort().order);
+                STACK::open_panel(pane);
+                // the other panel is listed in the preview
+                if let Some(p) = state.preview_ui.as_mut() {
+                    p.show(true);
+                }
+            }
+            refresh_header(state);
+            state.insert(Event::PreviewChange);
+        }
+        FsAction::SwitchPanel => {
+            let (content, index) = state.get_content_and_index();
+            STACK::save_input(content, index);
+
+            if let Some(selections) = STACK::swap_panel(selected_hashes(state)) {
+                enter_tab(state, selections);
+            }
+        }
+        FsAction::ComparePanels => {
+            let (Some(cwd), Some(other)) = (STACK::cwd(), STACK::panel_cwd()) else {
+                return;
+            };
+            let items = state.picker_ui.worker.nucleo.items();
+            let indices: Vec<u32> = items
+                .iter()
+                .filter(|(_, item)| compare::differs(&item.path, &cwd, &other))
+                .map(|(i, _)| i)
+                .collect();
+            // and the other way round, for when the other panel is switched to
+            let vis = STACK::with_panel(|p, _| p.vis().unwrap_or_default()).unwrap_or_default();
+            let theirs = list_dir(&other, vis)
+                .filter(|p| compare::differs(p, &other, &cwd))
+                .map(|p| selection::hash_path(&AbsPath::new_unchecked(p)))
+                .collect();
+            STACK::set_panel_selections(theirs);
+
+            TOAST::msg(format!("{} differing items", indices.len()), true);
+            state.picker_ui.selector.clear();
+            state.picker_ui.selector.extend(indices);
+        }
+
+        FsAction::SetMark(Some(mark)) => marks::set(mark),
+        FsAction::JumpMark(Some(mark)) => marks::jump(mark),
+        FsAction::SetMark(None) => marks::list(true),
+        FsAction::JumpMark(None) => marks::list(false),
+
+        FsAction::Visual => selection::toggle_visual(state),
+        FsAction::SelectMatching(pattern) => {
+            if pattern.is_empty() {
+                if state.overlay_index().is_none() {
+                    STORE::set_menu_prompt(Some(MenuPrompt::new(PromptKind::SelectMatching)));
+                    GLOBAL::send_action(Action::Overlay(4));
+                }
+                return;
+            }
+            match selection::PathGlob::new(&pattern, &STACK::_cwd()) {
+                Ok(glob) => selection::select_where(state, |item| glob.is_match(&item.path)),
+                Err(e) => TOAST::notice(ToastStyle::Error, format!("Invalid glob: {e}")),
+            }
+        }
+        FsAction::SelectCategory(category) => {
+            selection::select_where(state, |item| {
+                !item.path.is_dir() && FileCategory::get(&item.path).as_ref() == Some(&category)
+            });
+        }
+        FsAction::SelectSameExtension => {
+            let Some(item) = state.current_raw() else {
+                return;
+            };
+            if item.path.is_dir() {
+                return;
+            }
+            let ext = item.path.extension().map(ToOwned::to_owned);
+            selection::select_where(state, |item| {
+                selection::has_extension(&item.path, ext.as_deref())
+            });
+        }
+        FsAction::InvertSelection => selection::invert(state),
+
         FsAction::Jump(mut paths) => {
             let cwd = STACK::cwd().and_then(|p| p.canonicalize().ok());
 
@@ -874,9 +1199,14 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
                 })
             };
             if !items.is_empty() {
-                QUEUE::enqueue("move".into(), items);
-                TOAST::push(ToastStyle::Normal, "Move: ", toast_vec);
-                copy_files(cb_vec, false);
+                let rows = QUEUE::enqueue("move".into(), items);
+                // the dual-pane mode transfers to the other panel right away
+                if let Some(dst) = STACK::panel_cwd() {
+                    QUEUE::dispatch(rows.collect(), Some(dst));
+                } else {
+                    TOAST::push(ToastStyle::Normal, "Move: ", toast_vec);
+                    copy_files(cb_vec, false);
+                }
             };
         }
         FsAction::Copy => {
@@ -898,10 +1228,88 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
                 })
             };
             if !items.is_empty() {
-                QUEUE::enqueue("copy".into(), items);
-                TOAST::push(ToastStyle::Normal, "Copied: ", toast_vec);
-                copy_files(cb_vec, false);
+                let rows = QUEUE::enqueue("copy".into(), items);
+                // the dual-pane mode transfers to the other panel right away
+                if let Some(dst) = STACK::panel_cwd() {
+                    QUEUE::dispatch(rows.collect(), Some(dst));
+                } else {
+                    TOAST::push(ToastStyle::Normal, "Copied: ", toast_vec);
+                    copy_files(cb_vec, false);
+                }
+            };
+        }
+
+        FsAction::SavedSearch(name) => {
+            let Some(search) = saved_search(&name) else {
+                TOAST::push(ToastStyle::Error, "No saved search: ", [Span::raw(name.clone())]);
+                return;
+            };
+            let (content, index) = state.get_content_and_index();
+            STACK::save_input(content, index);
+
+            let pane = search.to_pane(STACK::_cwd(), GLOBAL::cfg(), lessfilter_cfg());
+            if let FsPane::Find { vis, .. } | FsPane::Search { vis, .. } = &pane {
+                FILTERS::set(*vis);
+            }
+            STACK::push(pane);
+            // as with undo/redo, the pane's own visibility and sort stand
+            // over the pane defaults
+            fs_reload(state, true, true);
+        }
+
+        FsAction::SaveSearch(name) => {
+            let searchable =
+                STACK::with_current(|p| matches!(p, FsPane::Find { .. } | FsPane::Search { .. }));
+            if !searchable {
+                TOAST::msg(Line::from("Only Find and Search panes can be saved"), false);
+                return;
+            }
+            if name.is_empty() {
+                if state.overlay_index().is_none() {
+                    STORE::set_menu_prompt(Some(MenuPrompt::new(PromptKind::SaveSearch)));
+                    GLOBAL::send_action(Action::Overlay(4));
+                }
+                return;
+            }
+            if saved_search(&name).is_some() {
+                TOAST::push(
+                    ToastStyle::Error,
+                    "Saved search already exists: ",
+                    [Span::raw(name.clone())],
+                );
+                return;
+            }
+
+            // Search panes keep the query only while filtering the matches;
+            // otherwise it is the rg pattern, saved with the pane
+            let query = state.picker_ui.query.input();
+            let Some(search) = STACK::with_current(|p| {
+                let query = match p {
+                    FsPane::Search {
+                        filtering: false, ..
+                    } => String::new(),
+                    _ => query,
+                };
+                SavedSearch::from_pane(p, query)
+            }) else {
+                return;
             };
+
+            let path = CONFIG_PATH.get().map_or_else(|| config_path().to_path_buf(), Clone::clone);
+            match search.append_to(&name, &path) {
+                Ok(()) => {
+                    SESSION_SEARCHES.lock().unwrap().insert(name.clone(), search);
+                    TOAST::push(ToastStyle::Success, "Saved search: ", [Span::raw(name.clone())]);
+                }
+                Err(e) => {
+                    log::error!("Failed to save search to {}: {e}", path.display());
+                    TOAST::push(
+                        ToastStyle::Error,
+                        "Failed to save search: ",
+                        [Span::raw(name.clone())],
+                    );
+                }
+            }
         }
 
         // Switch to the named stash pane; does not add paths — adding the
@@ -950,11 +1358,245 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
             TOAST::msg(line, false);
         }
 
+        FsAction::CommandHistory => {
+            if state.picker_ui.current_indexed().is_none() && STACK::cwd().is_none() {
+                return;
+            }
+            TASKS::spawn("db command history", async move {
+                let history = match db().get_conn(crate::db::DbTable::files).await {
+                    Ok(mut conn) => conn.get_command_history(0).await._elog(),
+                    Err(e) => {
+                        log::error!("Error getting connection: {e}");
+                        None
+                    }
+                };
+                let Some(history) = history else {
+                    TOAST::notice(ToastStyle::Error, "Failed to load the command history.");
+                    return;
+                };
+                *COMMAND_HISTORY.lock().unwrap() = Some(history);
+                GLOBAL::send_action(Action::Overlay(4));
+            });
+        }
+
+        FsAction::StashUnion(_)
+        | FsAction::StashIntersect(_)
+        | FsAction::StashDiff(_)
+        | FsAction::StashSymDiff(_) => {
+            let (op, operands) = match a {
+                FsAction::StashUnion(o) => (StashOp::Union, o),
+                FsAction::StashIntersect(o) => (StashOp::Intersect, o),
+                FsAction::StashDiff(o) => (StashOp::Diff, o),
+                FsAction::StashSymDiff(o) => (StashOp::SymDiff, o),
+                _ => unreachable!(),
+            };
+            let reload = stash_pane_is(&[operands.target()]);
+            TASKS::spawn("stash combine", async move {
+                match stash::combine(op, &operands).await {
+                    Ok(n) => TOAST::notice(
+                        ToastStyle::Success,
+                        format!(
+                            "Stash {op} ({}): {n} item(s)",
+                            stash_label(operands.target())
+                        ),
+                    ),
+                    Err(e) => {
+                        log::error!("Error combining stashes: {e}");
+                        TOAST::notice(ToastStyle::Error, "Failed to combine stashes.");
+                    }
+                }
+                if reload {
+                    GLOBAL::send_action(FsAction::Reload);
+                }
+            });
+        }
+
+        FsAction::MoveStash(StashOperands { lhs, rhs, .. }) => {
+            let reload = stash_pane_is(&[&lhs, &rhs]);
+            TASKS::spawn("stash move", async move {
+                match stash::move_all(&lhs, &rhs).await {
+                    Ok(n) => TOAST::notice(
+                        ToastStyle::Success,
+                        format!(
+                            "Moved {n} item(s) from stash ({}) to ({})",
+                            stash_label(&lhs),
+                            stash_label(&rhs)
+                        ),
+                    ),
+                    Err(e) => {
+                        log::error!("Error moving stash entries: {e}");
+                        TOAST::notice(ToastStyle::Error, "Failed to move stash entries.");
+                    }
+                }
+                if reload {
+                    GLOBAL::send_action(FsAction::Reload);
+                }
+            });
+        }
+
+        FsAction::ClearStash(name) => {
+            let reload = stash_pane_is(&[&name]);
+            TASKS::spawn("stash clear", async move {
+                match stash::set_entries(&name, Vec::new()).await {
+                    Ok(()) => TOAST::notice(
+                        ToastStyle::Success,
+                        format!("Cleared stash ({})", stash_label(&name)),
+                    ),
+                    Err(e) => {
+                        log::error!("Error clearing stash: {e}");
+                        TOAST::notice(ToastStyle::Error, "Failed to clear stash.");
+                    }
+                }
+                if reload {
+                    GLOBAL::send_action(FsAction::Reload);
+                }
+            });
+        }
+
+        FsAction::ExportStash(dest) => {
+            let Some(name) = STACK::with_current(|p| match p {
+                FsPane::Stash { stash_name, .. } => Some(stash_name.clone()),
+                _ => None,
+            }) else {
+                return;
+            };
+            TASKS::spawn("stash export", async move {
+                let Some(entries) = stash::get_entries(&name).await._elog() else {
+                    TOAST::notice(ToastStyle::Error, "Failed to read the stash.");
+                    return;
+                };
+                if dest.as_os_str().is_empty() {
+                    if let Some(text) = export_entries(&entries, StashFormat::Lines)._elog() {
+                        clipboard::copy_text(text, true);
+                    }
+                    return;
+                }
+
+                let dest = match dest.strip_prefix("~") {
+                    Ok(rest) => __home().join(rest),
+                    Err(_) => dest.abs(__home()),
+                };
+                let dest = AbsPath::new_unchecked(dest);
+                let written = export_entries(&entries, StashFormat::from_path(&dest))
+                    .map_err(|e| e.to_string())
+                    .and_then(|text| std::fs::write(&dest, text).map_err(|e| e.to_string()));
+                match written {
+                    Ok(()) => TOAST::push(
+                        ToastStyle::Success,
+                        format!("Exported {} item(s) to: ", entries.len()),
+                        [short_display(&dest)],
+                    ),
+                    Err(e) => {
+                        log::error!("Failed to export stash to {}: {e}", dest.to_string_lossy());
+                        TOAST::push(
+                            ToastStyle::Error,
+                            "Failed to export to: ",
+                            [short_display(&dest)],
+                        );
+                    }
+                }
+            });
+        }
+
         FsAction::Backup => {
             // todo: impl using custom stash + some kind of db-based kv store
         }
 
+        FsAction::Replace(replacement) => {
+            if !STACK::with_current(|p| matches!(p, FsPane::Search { .. })) {
+                TOAST::msg(Line::from("Only Search panes can replace"), false);
+                return;
+            }
+            if replacement.is_empty() {
+                if state.overlay_index().is_none() {
+                    STORE::set_menu_prompt(Some(replace_prompt()));
+                    GLOBAL::send_action(Action::Overlay(4));
+                }
+                return;
+            }
+            replace::set_preview(state, replacement);
+        }
+
+        FsAction::ApplyReplace => {
+            let Some(ReplacePreview(replacer)) = STORE::get() else {
+                TOAST::msg(Line::from("No replacement to apply"), false);
+                return;
+            };
+            let Some(one_line) = STACK::with_current(|p| match p {
+                FsPane::Search { one_line, .. } => Some(*one_line),
+                _ => None,
+            }) else {
+                return;
+            };
+            let edits = replace::selected_edits(state, one_line);
+            STORE::take::<ReplacePreview>();
+            state.picker_ui.selector.clear();
+            state.picker_ui.results.set_dirty();
+
+            TASKS::spawn("replace", async move {
+                match replace::apply(&replacer, edits).await {
+                    Ok(applied) => {
+                        TOAST::push(
+                            ToastStyle::Success,
+                            "Replaced: ",
+                            [Span::raw(format!(
+                                "{} lines in {} files",
+                                applied.lines, applied.files
+                            ))],
+                        );
+                        if applied.failed > 0 {
+                            TOAST::push(
+                                ToastStyle::Error,
+                                "Not replaced: ",
+                                [Span::raw(format!("{} files", applied.failed))],
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("Failed to journal the replacement: {e}");
+                        TOAST::notice(ToastStyle::Error, "Backup failed, nothing was replaced");
+                    }
+                }
+                GLOBAL::send_action(FsAction::Reload);
+            });
+        }
+
+        FsAction::CancelReplace => {
+            if STORE::take::<ReplacePreview>().is_some() {
+                state.picker_ui.selector.clear();
+                state.picker_ui.results.set_dirty();
+            }
+        }
+
+        FsAction::UndoOperation => {
+            TASKS::spawn("undo operation", async move {
+                match replace::undo().await {
+                    Ok(Some((op, 0))) => TOAST::push(
+                        ToastStyle::Success,
+                        "Undone: ",
+                        [Span::raw(format!("{} ({} files)", op.kind, op.files.len()))],
+                    ),
+                    Ok(Some((op, failed))) => TOAST::push(
+                        ToastStyle::Error,
+                        "Failed to restore: ",
+                        [Span::raw(format!("{failed} of {} files", op.files.len()))],
+                    ),
+                    Ok(None) => TOAST::notice(ToastStyle::Normal, "Nothing to undo"),
+                    Err(e) => {
+                        log::error!("Failed to read the journal: {e}");
+                        TOAST::notice(ToastStyle::Error, "Failed to read the journal");
+                    }
+                }
+                GLOBAL::send_action(FsAction::Reload);
+            });
+        }
+
         FsAction::Trash(no_confirm) => {
+            // in the aliases pane, Trash clears the alias like Delete
+            if STACK::with_current(|p| matches!(p, FsPane::Aliases { .. })) {
+                GLOBAL::send_action(FsAction::Delete(no_confirm));
+                return;
+            }
             // in a stash pane, Trash removes from the stash, not the actual path
             let stash_name = STACK::with_current(|p| match p {
                 FsPane::Stash { stash_name, .. } => Some(stash_name.clone()),
@@ -1030,8 +1672,10 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
         }
         FsAction::Delete(no_confirm) => {
             // In a stash pane, Delete removes the stash entry; in the db
-            // history panes (files/dirs/apps) it removes the db entry. The
-            // actual path is only deleted in the fs panes (nav/find/search/custom).
+            // history panes (files/dirs/apps) it removes the db entry, and in
+            // the aliases pane the alias. The actual path is only deleted in
+            // the fs panes (nav/find/search/custom).
+            let in_aliases = STACK::with_current(|p| matches!(p, FsPane::Aliases { .. }));
             let (stash_name, history_table) = STACK::with_current(|p| match p {
                 FsPane::Stash { stash_name, .. } => (Some(stash_name.clone()), None),
                 FsPane::Files { .. } => (None, Some(DbTable::files)),
@@ -1054,6 +1698,7 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
                 let remove_from = match (&stash_name, &history_table) {
                     (Some(_), _) => Some("stash"),
                     (_, Some(_)) => Some("history"),
+                    _ if in_aliases => Some("aliases"),
                     _ => None,
                 };
 
@@ -1111,6 +1756,11 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
                 return;
             }
 
+            if in_aliases {
+                db_remove_aliases(items);
+                return;
+            }
+
             TASKS::spawn("delete", async move {
                 // Surface long-running deletes with a persistent progress
                 // toast; the size walk runs off-thread so the UI is not
@@ -1339,6 +1989,18 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
             });
             GLOBAL::send_action(FsAction::Refilter);
         }
+        FsAction::Sort(order) => {
+            if !STACK::with_current(|p| p.sort_options().contains(&order)) {
+                TOAST::msg(format!("Cannot sort this pane by {order}"), true);
+                return;
+            }
+            STACK::with_current_mut(|p| {
+                let sort = p.sort_mut();
+                *sort = if *sort == order { SortOrder::none } else { order };
+            });
+            GLOBAL::send_action(FsAction::Refilter);
+            refresh_prompt(state);
+        }
         // ------------------------------------------------------
         // Execute/Accept
         FsAction::Lessfilter {
@@ -1469,7 +2131,11 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
         // loop, so the current overlay (e.g. the queue's rename editor)
         // gets first crack; when the menu itself is open they are handed
         // back so the menu can trigger the matching item.
-        FsAction::New | FsAction::NewDir | FsAction::Rename | FsAction::SetAlias(_) => {
+        FsAction::New
+        | FsAction::NewDir
+        | FsAction::Rename
+        | FsAction::SetAlias(_)
+        | FsAction::AnnotateStashEntry => {
             if state.overlay_index() == Some(4) {
                 GLOBAL::send_action(a.clone());
                 return;
@@ -1502,6 +2168,18 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
                         GLOBAL::send_action(Action::Overlay(4));
                     }
                 }
+                FsAction::AnnotateStashEntry => {
+                    if !STACK::with_current(|p| matches!(p, FsPane::Stash { .. })) {
+                        return;
+                    }
+                    if let Some(item) = state.current_raw() {
+                        let note = item.tail_text().to_string();
+                        STORE::set_menu_prompt(Some(
+                            MenuPrompt::new(PromptKind::Annotate).initial(note),
+                        ));
+                        GLOBAL::send_action(Action::Overlay(4));
+                    }
+                }
                 FsAction::Rename => {
                     // the app pane lists commands, not filesystem items
                     if STACK::in_app() {
@@ -1529,10 +2207,31 @@ pub fn fsaction_handler(a: FsAction, state: &mut MMState<'_>, context: &mut Acti
     }
 }
 
+/// The saved search `name`: from the config, or saved during this session.
+fn saved_search(name: &str) -> Option<SavedSearch> {
+    GLOBAL::cfg()
+        .searches
+        .get(name)
+        .cloned()
+        .or_else(|| SESSION_SEARCHES.lock().unwrap().get(name).cloned())
+}
+
+/// Whether the current pane is the stash pane of one of `names`.
+fn stash_pane_is(names: &[&str]) -> bool {
+    STACK::with_current(
+        |p| matches!(p, FsPane::Stash { stash_name, .. } if names.contains(&stash_name.as_str())),
+    )
+}
+
+/// Display name of a stash in toasts.
+fn stash_label(name: &str) -> &str {
+    if name.is_empty() { "unnamed" } else { name }
+}
+
 /// Insert `paths` into the named stash, applying the stash's configured
 /// [`InsertionStrategy`] to paths already present. Reloads after completion
 /// if reload = true.
-fn db_stash(name: String, paths: Vec<AbsPath>, reload: bool) {
+pub(crate) fn db_stash(name: String, paths: Vec<AbsPath>, reload: bool) {
     let c = GLOBAL::cfg();
     let setting = c.panes.stashes.get(&name);
     let (insert, kind) = (
@@ -1671,30 +2370,68 @@ fn db_remove_history_entries(table: DbTable, paths: Vec<PathBuf>) {
     });
 }
 
+fn db_remove_aliases(paths: Vec<PathBuf>) {
+    TASKS::spawn("db remove aliases", async move {
+        let paths: Vec<AbsPath> = paths.iter().map(AbsPath::new_unchecked).collect();
+        match db().get_conn(DbTable::dirs).await {
+            Ok(mut conn) => {
+                // the pane lists both tables, and a missing path has no type
+                let mut result = Ok(());
+                for table in [DbTable::dirs, DbTable::files] {
+                    conn.switch_table(table);
+                    for path in &paths {
+                        result = result.and(conn.set_alias(path, "").await);
+                    }
+                }
+                match result {
+                    Ok(()) => {
+                        TOAST::notice(
+                            ToastStyle::Success,
+                            format!("Removed {} alias(es)", paths.len()),
+                        );
+                    }
+                    Err(e) => {
+                        log::error!("Error removing aliases: {e}");
+                        TOAST::notice(ToastStyle::Error, "Failed to remove aliases.");
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("Error getting connection: {e}");
+            }
+        }
+        GLOBAL::send_action(FsAction::Reload);
+    });
+}
+
 // ------------- BOILERPLATE ---------------
 enum_from_str_display! {
     FsAction;
 
     units:
-    Advance, Parent, Find, Search, History, App,
-    Undo, Redo,
+    Advance, Parent, Find, Search, History, App, Aliases, FindDuplicates, ToggleExpand,
+    Undo, Redo, NewTab, NextTab, CloseTab, Commander, SwitchPanel, ComparePanels,
+    Visual, SelectSameExtension, InvertSelection,
     ShowOptions, ShowQueue,
-    ShowMenu, FsToggle, ToggleHidden,
-    Move, Copy, CopyPath, New, NewDir, Rename,
-    Backup;
+    ShowMenu, CommandHistory, FsToggle, ToggleHidden,
+    Move, Copy, CopyPath, New, NewDir, Rename, AnnotateStashEntry,
+    Backup, ApplyReplace, CancelReplace, UndoOperation;
 
     tuples:
-    AutoJump, SetAlias, Enqueue,
+    AutoJump, GotoTab, Sort, SetAlias, Enqueue, SavedSearch, SelectCategory,
+    StashUnion, StashIntersect, StashDiff, StashSymDiff, MoveStash,
     ExecPaged = ExecutePaged, ExecTTY = ExecuteTTY, ExecDetached = ExecuteDetached, ExecSilent = ExecuteSilent, CopyCommand, CopyCommandAsync;
 
     defaults:
-    (Delete, false), (Trash, false), (OpenStash, String::new()), (PushStash = Stash, String::new())
+    (Delete, false), (Trash, false), (OpenStash, String::new()), (PushStash = Stash, String::new()),
+    (ClearStash, String::new()), (SaveSearch, String::new()), (Replace, String::new()),
+    (SelectDuplicates, DupeKeep::Newest), (TreeDepth, 1), (SelectMatching, String::new())
     ;
     options:
-    LockPrompt;
+    LockPrompt, SetMark, JumpMark;
 
     lossy:
-    Paste;
+    Paste, ExportStash;
 }
 
 macro_rules! enum_from_str_display {
@@ -1765,7 +2502,7 @@ macro_rules! enum_from_str_display {
                                         write!(f, "ClearQueue({selector})")
                                     }
                                 }
-                                SaveInput | SetHeader(_) | SetFooter(_) | Reload | ReSort | ResortSizes | Refilter | AcceptPrompt | Filtering(_) | SetStatus(_) | Confirm | MenuAction(_) | MenuActionSilent(_) | MenuActionExecPaged(_) => Ok(()), // internal
+                                SaveInput | SetHeader(_) | SetFooter(_) | Reload | ReSort | ResortSizes | Refilter | AcceptPrompt | Filtering(_) | SetStatus(_) | Click(_) | Confirm | MenuAction(_) | MenuActionSilent(_) | MenuActionExecPaged(_) => Ok(()), // internal
                                 Lessfilter { preset, paging, header: _, special, } => {
                                     if *special == 1 {
                                         write!(f, "Help")
@@ -1997,6 +2734,39 @@ mod queue_action_parse_tests {
 
         let stash_bm: FsAction = "Stash(bookmark)".parse().unwrap();
         assert_eq!(stash_bm, FsAction::PushStash("bookmark".into()));
+
+        let union: FsAction = "StashUnion(,bookmark,both)".parse().unwrap();
+        let mut operands = StashOperands::new("", "bookmark");
+        operands.into = Some("both".into());
+        assert_eq!(union, FsAction::StashUnion(operands));
+        assert_eq!(union.to_string(), "StashUnion(,bookmark,both)");
+        assert!("StashDiff(a)".parse::<FsAction>().is_err());
+
+        let clear: FsAction = "ClearStash".parse().unwrap();
+        assert_eq!(clear, FsAction::ClearStash(String::new()));
+
+        let export: FsAction = "ExportStash(~/stash.json)".parse().unwrap();
+        assert_eq!(export, FsAction::ExportStash("~/stash.json".into()));
+
+        let note: FsAction = "AnnotateStashEntry".parse().unwrap();
+        assert_eq!(note, FsAction::AnnotateStashEntry);
+
+        let saved: FsAction = "SavedSearch(todo)".parse().unwrap();
+        assert_eq!(saved, FsAction::SavedSearch("todo".into()));
+        assert_eq!(saved.to_string(), "SavedSearch(todo)");
+        assert_eq!("SaveSearch".parse::<FsAction>().unwrap(), FsAction::SaveSearch(String::new()));
+
+        let replace: FsAction = "Replace(${1}_v2)".parse().unwrap();
+        assert_eq!(replace, FsAction::Replace("${1}_v2".into()));
+        assert_eq!(replace.to_string(), "Replace(${1}_v2)");
+        assert_eq!("Replace".parse::<FsAction>().unwrap(), FsAction::Replace(String::new()));
+        assert_eq!("ApplyReplace".parse::<FsAction>().unwrap(), FsAction::ApplyReplace);
+        assert_eq!("UndoOperation".parse::<FsAction>().unwrap(), FsAction::UndoOperation);
+
+        let sort: FsAction = "Sort(size)".parse().unwrap();
+        assert_eq!(sort, FsAction::Sort(SortOrder::size));
+        assert_eq!(sort.to_string(), "Sort(size)");
+        assert!("Sort(owner)".parse::<FsAction>().is_err());
     }
 
     #[test]
@@ -2056,6 +2826,45 @@ mod queue_action_parse_tests {
         assert_eq!(confirmed.to_string(), "ClearQueue");
     }
 
+    #[test]
+    fn selection_actions_parse_and_display() {
+        let action: FsAction = "SelectMatching(*.log)".parse().unwrap();
+        assert_eq!(action, FsAction::SelectMatching("*.log".into()));
+        assert_eq!(action.to_string(), "SelectMatching(*.log)");
+        // bare = prompt for the glob
+        let action: FsAction = "SelectMatching".parse().unwrap();
+        assert_eq!(action, FsAction::SelectMatching(String::new()));
+        assert_eq!(action.to_string(), "SelectMatching");
+
+        let action: FsAction = "SelectCategory(image)".parse().unwrap();
+        assert_eq!(action, FsAction::SelectCategory(FileCategory::Image));
+        assert_eq!(action.to_string(), "SelectCategory(image)");
+        assert!("SelectCategory".parse::<FsAction>().is_err());
+        assert!("SelectCategory(nope)".parse::<FsAction>().is_err());
+
+        assert_eq!("Visual".parse::<FsAction>().unwrap(), FsAction::Visual);
+        assert_eq!(
+            "InvertSelection".parse::<FsAction>().unwrap(),
+            FsAction::InvertSelection
+        );
+    }
+
+    #[test]
+    fn mark_actions_parse_and_display() {
+        let action: FsAction = "SetMark(a)".parse().unwrap();
+        assert_eq!(action, FsAction::SetMark(Some('a')));
+        assert_eq!(action.to_string(), "SetMark(a)");
+        let action: FsAction = "JumpMark(')".parse().unwrap();
+        assert_eq!(action, FsAction::JumpMark(Some('\'')));
+        assert_eq!(action.to_string(), "JumpMark(')");
+
+        // bare = prefix key
+        let action: FsAction = "JumpMark".parse().unwrap();
+        assert_eq!(action, FsAction::JumpMark(None));
+        assert_eq!(action.to_string(), "JumpMark");
+        assert!("SetMark(ab)".parse::<FsAction>().is_err());
+    }
+
     #[test]
     fn enqueue_actions_parse_and_display() {
         let action: FsAction = "Enqueue(zip)".parse().unwrap();
@@ -11,18 +11,23 @@ use matchmaker::{
     message::{BindDirective, Event},
     ui::StatusUI,
 };
-use ratatui::text::Line;
+use ratatui::{
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+};
 
 use crate::run::state::GLOBAL::db;
 use crate::{
     abspath::AbsPath,
     aliases::MMState,
     run::{
-        FsAction, FsPane,
+        FsAction, FsPane, commander, drop, meta,
         queue::{QUEUE, QueueSelector, SelectorResult},
+        replace::{self, ReplacePreview},
         selection,
         state::{
-            FILTERS, GLOBAL, HideMetadata, InPrompt, STACK, STORE, TOAST, ToastStyle, sort,
+            FILTERS, GLOBAL, HideMetadata, InPrompt, PreviousDir, STACK, STORE, TOAST, ToastStyle,
+            VisualAnchor, sort,
             ui::{global_ui, prompt_main_style},
         },
     },
@@ -38,6 +43,11 @@ pub fn paste_handler(content: String, state: &MMState<'_>) -> String {
             || STORE::contains::<InPrompt>()
             || state.overlay_index().is_some())
     {
+        // a dropped file arrives as a paste of its path
+        if let Some(paths) = drop::dropped_paths(&content) {
+            drop::ask(paths, c);
+            return String::new();
+        }
         match QUEUE::select(&QueueSelector::Builtins, Some(&c)) {
             SelectorResult::Ready(indices) => QUEUE::dispatch(indices, Some(c)),
             SelectorResult::MissingDestination => TOAST::notice(
@@ -68,6 +78,11 @@ pub fn refresh_prompt(state: &mut MMState<'_>) {
         } else {
             state.picker_ui.query.set_prompt(None);
         };
+    } else if STORE::contains::<VisualAnchor>() {
+        state
+            .picker_ui
+            .query
+            .set_prompt_line(Line::styled("v: ", prompt_main_style()));
     } else {
         let vis = FILTERS::visibility();
         if vis.dirs && !vis.files {
@@ -153,6 +168,11 @@ pub fn enter_dir_pane(state: &mut MMState<'_>, path: AbsPath) {
     STACK::save_input(content, index);
     // record
     db().bump_path(true, path.clone());
+    if let Some(cwd) = STACK::cwd()
+        && cwd != path
+    {
+        STORE::set(PreviousDir(cwd));
+    }
 
     // apply specific settings
     // cancel the query when leaving a pane that wants it — the input was
@@ -198,7 +218,16 @@ pub fn enter_dir_pane(state: &mut MMState<'_>, path: AbsPath) {
         }
     }
 
-    let pane = FsPane::new_nav(path, vis, sort::get_sort().order);
+    let mut pane = FsPane::new_nav(path, vis, sort::get_sort().order);
+    // a tree stays one as its directories are entered
+    if let FsPane::Nav { depth, .. } = &mut pane
+        && let Some(d) = STACK::with_current(|p| match p {
+            FsPane::Nav { depth, .. } => Some(*depth),
+            _ => None,
+        })
+    {
+        *depth = d;
+    }
     STACK::push(pane);
     fs_reload(state, is_new, dir_changed);
 }
@@ -212,6 +241,12 @@ pub fn fs_reload(
     // dir_changed matters only for `enter_dir` and `undo/redo`
     dir_changed: bool,
 ) {
+    // a replacement preview belongs to the pane it was entered in
+    if is_new {
+        STORE::take::<ReplacePreview>();
+    }
+    meta::clear();
+
     // apply vis/sort changes
     if is_new && !dir_changed {
         STACK::with_current_mut(|pane| {
@@ -245,10 +280,13 @@ pub fn fs_reload(
 
                     FILTERS::set(*vis);
                 }
+                // a sorted listing would scatter the groups
+                FsPane::Dupes { vis, .. } => FILTERS::set(*vis),
                 FsPane::Files { .. }
                 | FsPane::Folders { .. }
                 | FsPane::Apps { .. }
-                | FsPane::Stash { .. } => {
+                | FsPane::Stash { .. }
+                | FsPane::Aliases { .. } => {
                     // logically we should add configurable default but i don't think anything besides frecency is desirable [for the default]
                 }
             }
@@ -261,18 +299,12 @@ pub fn fs_reload(
     {
         let refill = GLOBAL::cfg().fs.refill_selections_after_reload && !is_new && !dir_changed;
         if refill && !state.picker_ui.selector.is_empty() {
-            let hashes: Vec<u64> = state
-                .picker_ui
-                .selector
-                .iter()
-                .filter_map(|&idx| state.picker_ui.worker.get_by_idx(idx))
-                .map(|item| selection::hash_path(&item.path))
-                .collect();
-            STORE::set(selection::PendingSelections(hashes));
+            STORE::set(selection::PendingSelections(selected_hashes(state)));
         } else {
             STORE::take::<selection::PendingSelections>();
         }
         state.picker_ui.selector.clear();
+        STORE::take::<VisualAnchor>();
     }
 
     // a new pane whose sort matches its configured default override (startup
@@ -321,6 +353,7 @@ pub fn fs_reload(
                         split_whitespace_preserve_single_quotes(&state.picker_ui.query.input());
                 };
             }
+            replace::refresh_preview(p);
         }
         _ => {}
     });
@@ -512,6 +545,87 @@ pub fn fs_post_reload(state: &mut MMState<'_>) {
                 GLOBAL::send_bind(BindDirective::Unbind(Event::QueryChange.into()))
             }
         }
+
         _trace!(pane);
     });
+    refresh_header(state);
+}
+
+/// The header lists the tabs and the panels of the dual-pane mode. Left alone when neither is in
+/// use.
+pub fn refresh_header(state: &mut MMState<'_>) {
+    let (tabs, _) = STACK::tabs();
+    let panels = commander::panel_bar();
+    if tabs < 2 && panels.is_none() {
+        return;
+    }
+
+    let mut lines = Vec::new();
+    if tabs > 1 {
+        lines.push(tab_bar());
+    }
+    lines.extend(panels);
+    if lines.is_empty() {
+        state.picker_ui.header.clear(true);
+    } else {
+        state.picker_ui.header.set(lines);
+    }
+}
+
+/// One label per tab, numbered from 1 (see [`crate::run::FsAction::GotoTab`]), with the current
+/// tab highlighted.
+fn tab_bar() -> Line<'static> {
+    let (_, current) = STACK::tabs();
+    let mut line = Line::default();
+    for (i, label) in STACK::map_tabs(FsPane::tab_label).into_iter().enumerate() {
+        if i > 0 {
+            line.push_span(Span::styled(" │ ", Style::new().add_modifier(Modifier::DIM)));
+        }
+        let span = Span::raw(format!("{} {label}", i + 1));
+        line.push_span(if i == current {
+            span.add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        } else {
+            span
+        });
+    }
+    line
+}
+
+/// Hashes of the selected paths (see [`selection`]), which survive a worker restart.
+pub fn selected_hashes(state: &MMState<'_>) -> Vec<u64> {
+    state
+        .picker_ui
+        .selector
+        .iter()
+        .filter_map(|&idx| state.picker_ui.worker.get_by_idx(idx))
+        .map(|item| selection::hash_path(&item.path))
+        .collect()
+}
+
+/// Switch to tab `n` (0-based), if it exists and is not the current one.
+pub fn switch_tab(
+    state: &mut MMState<'_>,
+    n: usize,
+) {
+    let (content, index) = state.get_content_and_index();
+    STACK::save_input(content, index);
+
+    if let Some(selections) = STACK::goto_tab(n, selected_hashes(state)) {
+        enter_tab(state, selections);
+    }
+}
+
+/// Show the pane of the tab just switched to: as with undo/redo, its own visibility and sort
+/// stand, and the `selections` it was left with are refilled once it has populated.
+pub fn enter_tab(
+    state: &mut MMState<'_>,
+    selections: Vec<u64>,
+) {
+    if let Some(vis) = STACK::with_current(FsPane::vis) {
+        FILTERS::set(vis);
+    }
+    fs_reload(state, true, true);
+    if !selections.is_empty() {
+        STORE::set(selection::PendingSelections(selections));
+    }
 }
@@ -60,6 +60,8 @@ fn config_as_code() -> BindMap<FsAction> {
         key!(right) => Action::ForwardChar,
         key!(left) => Action::BackwardChar,
         key!(tab) => [Action::ToggleSelection, Action::Down(1)],
+        key!(alt-v) => FsAction::Visual,
+        key!(alt-shift-v) => FsAction::InvertSelection,
         key!(alt-enter) => Action::Print("".into()),
         key!(alt-r) => Action::Reload("".to_string()),
 
@@ -73,6 +75,14 @@ fn config_as_code() -> BindMap<FsAction> {
         key!(ctrl-z) => FsAction::Undo,
         // key!(alt-a), key!(alt-shift-a) => FsAction::App,
         key!(alt-z), key!(ctrl-shift-'z') => FsAction::Redo,
+        key!(alt-m) => FsAction::SetMark(None),
+        key!(alt - '\'') => FsAction::JumpMark(None),
+        key!(ctrl-t) => FsAction::NewTab,
+        key!(ctrl-tab) => FsAction::NextTab,
+        key!(ctrl-w) => FsAction::CloseTab,
+        key!(alt-c) => FsAction::Commander,
+        key!(ctrl-o) => FsAction::SwitchPanel,
+        key!(alt-shift-c) => FsAction::ComparePanels,
 
         // Display
         // ----------------------------------
@@ -80,10 +90,14 @@ fn config_as_code() -> BindMap<FsAction> {
         key!(ctrl-shift-u), key!(alt-shift-u) => FsAction::ClearQueue(QueueSelector::All, false),
         key!(alt-u) => Action::ClearQuery,
         key!(ctrl-e) => FsAction::ShowMenu,
+        key!(alt-e) => FsAction::CommandHistory,
         // -- options --
         key!(ctrl-p) => FsAction::ShowOptions,
         key!(ctrl-d) => FsAction::FsToggle,
         key!(ctrl-s) => FsAction::ToggleHidden,
+        key!(alt-t) => FsAction::ToggleExpand,
+        key!(alt-'.') => FsAction::TreeDepth(1),
+        key!(alt-',') => FsAction::TreeDepth(-1),
 
         // File actions
         // ----------------------------------
@@ -0,0 +1,254 @@
+//! Miller columns for the Nav pane (`panes.nav.columns`).
+//!
+//! The worker drives the current directory, and the preview stays on its side with the child
+//! listing or the file preview. The parent directory gets its own area in the header of the
+//! results, with the cwd highlighted. It is listed from [`list_dir`] with the pane's visibility
+//! in the background, and cached until the cwd changes.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use matchmaker::{
+    message::RenderCommand,
+    nucleo::{Line, Modifier, Style, Text},
+};
+
+use crate::{
+    aliases::MMState,
+    find::walker::list_dir,
+    run::{
+        FsPane,
+        item::short_display,
+        state::{GLOBAL, STACK, TASKS, sort},
+    },
+};
+use fist_types::filters::{SortOrder, Visibility};
+
+/// The entries of `dir`, ordered by `order` (by name when `none`).
+///
+/// Sizes are not waited for: a size order only uses the sizes already in the cache, and lists
+/// the others last by name.
+pub fn entries(
+    dir: &Path,
+    vis: Visibility,
+    order: SortOrder,
+) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = list_dir(dir, vis).collect();
+    paths.sort();
+    match order {
+        SortOrder::none | SortOrder::name => {}
+        SortOrder::size => {
+            paths.sort_by_cached_key(|p| std::cmp::Reverse(sort::size_of(p).unwrap_or(0)))
+        }
+        order => sort::sort_level(&mut paths, order),
+    }
+    paths
+}
+
+/// The entries of the parent of `cwd`, one per line, with `cwd` highlighted and scrolled into the
+/// `height` lines of the column (all of them when 0). Empty at the root.
+pub fn parent_column(
+    cwd: &Path,
+    vis: Visibility,
+    order: SortOrder,
+    height: u16,
+) -> Text<'static> {
+    let Some(parent) = cwd.parent() else {
+        return Text::default();
+    };
+    let mut vis = vis;
+    // the cwd is listed even when the pane only shows files
+    vis.files = false;
+
+    let entries = entries(parent, vis, order);
+    let current = entries.iter().position(|p| p == cwd).unwrap_or(0);
+    let height = if height == 0 {
+        entries.len()
+    } else {
+        height as usize
+    };
+    // keep the cwd in the middle of the column once it is past the first half
+    let start = current
+        .saturating_sub(height / 2)
+        .min(entries.len().saturating_sub(height));
+
+    Text::from(
+        entries
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(height)
+            .map(|(i, p)| {
+                let span = short_display(p);
+                Line::from(if i == current && p == cwd {
+                    span.patch_style(Style::new().add_modifier(Modifier::REVERSED | Modifier::BOLD))
+                } else {
+                    span
+                })
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// The last requested listing of a side column, and its text once listed in the background.
+pub struct Latest<K>(Mutex<Option<(K, Option<Text<'static>>)>>);
+
+impl<K: Clone + PartialEq + Send + 'static> Latest<K> {
+    pub const fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    /// Passes the listing for `key` to `send`: right away when it is cached, else from a
+    /// background task once `list` returns. Dropped when superseded by another request in the
+    /// meantime.
+    pub fn request(
+        &'static self,
+        desc: &str,
+        key: K,
+        list: impl FnOnce(&K) -> Text<'static> + Send + 'static,
+        send: impl FnOnce(Text<'static>) + Send + 'static,
+    ) {
+        {
+            let mut latest = self.0.lock().unwrap();
+            match &*latest {
+                Some((k, Some(text))) if *k == key => return send(text.clone()),
+                // already being listed
+                Some((k, None)) if *k == key => return,
+                _ => *latest = Some((key.clone(), None)),
+            }
+        }
+
+        TASKS::spawn_blocking(desc, move || {
+            let text = list(&key);
+            let mut latest = self.0.lock().unwrap();
+            if let Some((k, cached)) = &mut *latest
+                && *k == key
+            {
+                *cached = Some(text.clone());
+                drop(latest);
+                send(text);
+            }
+        });
+    }
+
+    /// Forgets the last request, so that a pending listing is dropped. Returns whether there was
+    /// one.
+    pub fn clear(&self) -> bool {
+        self.0.lock().unwrap().take().is_some()
+    }
+}
+
+static PARENT: Latest<(PathBuf, Visibility, SortOrder, u16)> = Latest::new();
+
+/// Passes the [`parent_column`] of `cwd` to `send` (see [`Latest::request`]).
+pub fn request_parent_column(
+    cwd: PathBuf,
+    vis: Visibility,
+    order: SortOrder,
+    height: u16,
+    send: impl FnOnce(Text<'static>) + Send + 'static,
+) {
+    PARENT.request(
+        "parent column",
+        (cwd, vis, order, height),
+        |(cwd, vis, order, height)| parent_column(cwd, *vis, *order, *height),
+        send,
+    );
+}
+
+/// Lists the parent of the Nav pane in the header when `panes.nav.columns` is set (a quarter of
+/// the height, at least 3 lines), and removes it otherwise.
+pub fn refresh_parent(state: &mut MMState<'_>) {
+    let nav = STACK::with_current(|p| match p {
+        FsPane::Nav { cwd, vis, sort, .. } => Some((cwd.to_path_buf(), *vis, *sort)),
+        _ => None,
+    });
+    match nav {
+        Some((cwd, vis, order)) if GLOBAL::cfg().panes.nav.columns => {
+            let height = (state.ui_size()[1] / 4).max(3);
+            request_parent_column(cwd, vis, order, height, |text| {
+                let rows = text.lines.into_iter().map(|l| vec![l]).collect();
+                GLOBAL::send_mm(RenderCommand::HeaderTable(rows));
+            });
+        }
+        _ => {
+            if PARENT.clear() {
+                let header = &mut state.picker_ui.header;
+                header.header_table(Vec::new());
+                // still shown when it has a text
+                header.show = header.height() > header.config.border.height();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for d in ["alpha", "beta", "gamma", "delta/inner"] {
+            std::fs::create_dir_all(root.join(d)).unwrap();
+        }
+        std::fs::write(root.join("file"), "").unwrap();
+        std::fs::write(root.join("delta/b.txt"), "").unwrap();
+
+        let current = |text: &Text<'_>| {
+            text.lines
+                .iter()
+                .find(|l| {
+                    l.spans
+                        .iter()
+                        .any(|s| s.style.add_modifier.contains(Modifier::REVERSED))
+                })
+                .map(|l| l.to_string())
+        };
+
+        // the cwd is highlighted among the entries of its parent
+        let text = parent_column(
+            &root.join("beta"),
+            Visibility::default(),
+            SortOrder::name,
+            0,
+        );
+        assert_eq!(text.to_string(), "alpha\nbeta\ndelta\nfile\ngamma");
+        assert_eq!(current(&text).as_deref(), Some("beta"));
+
+        // short columns scroll to the cwd
+        let text = parent_column(
+            &root.join("gamma"),
+            Visibility::default(),
+            SortOrder::name,
+            2,
+        );
+        assert_eq!(text.to_string(), "file\ngamma");
+        assert_eq!(current(&text).as_deref(), Some("gamma"));
+        let text = parent_column(
+            &root.join("alpha"),
+            Visibility::default(),
+            SortOrder::name,
+            2,
+        );
+        assert_eq!(text.to_string(), "alpha\nbeta");
+
+        // sizes are not waited for
+        let text = parent_column(
+            &root.join("beta"),
+            Visibility::default(),
+            SortOrder::size,
+            0,
+        );
+        assert_eq!(text.lines.len(), 5);
+
+        assert!(
+            parent_column(Path::new("/"), Visibility::default(), SortOrder::name, 0)
+                .lines
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! The dual-pane mode: two panels with their own pane stacks in the current tab (see
+//! [`crate::run::state::STACK::open_panel`]).
+//!
+//! The active panel is the live list, and the other one is listed in the preview, docked on its
+//! side of the results at half the width (see [`crate::run::previewer::dock_preview`]). It is
+//! shown with the pane's visibility and sort, and with the cursor and selections it was left
+//! with, which [`crate::run::FsAction::SwitchPanel`] restores when switching to it.
+//! [`crate::run::FsAction::Copy`]/[`crate::run::FsAction::Move`] transfer to its cwd.
+
+use std::path::{Path, PathBuf};
+
+use matchmaker::nucleo::{Line, Modifier, Span, Style, Text};
+
+use crate::{
+    abspath::AbsPath,
+    run::{
+        FsPane,
+        columns::{self, Latest},
+        item::short_display,
+        selection::hash_path,
+        state::STACK,
+    },
+};
+use fist_types::filters::{SortOrder, Visibility};
+
+/// What the other panel is listed from: its cwd, visibility and sort, the saved cursor and
+/// selections, the height of the preview and the prefixes of the results.
+type PanelKey = (
+    PathBuf,
+    Visibility,
+    SortOrder,
+    u32,
+    Vec<u64>,
+    u16,
+    [String; 2],
+);
+
+static PANEL: Latest<PanelKey> = Latest::new();
+
+/// Passes the [`panel_listing`] of the other panel to `send` (see [`Latest::request`]).
+/// `prefixes` are the results' default and multi-selection prefixes.
+pub fn request_panel_listing(
+    height: u16,
+    prefixes: [String; 2],
+    send: impl FnOnce(Text<'static>) + Send + 'static,
+) {
+    let Some(cwd) = STACK::panel_cwd() else {
+        return;
+    };
+    let Some((vis, sort, cursor)) = STACK::with_panel(|p, _| {
+        let cursor = match p {
+            FsPane::Nav { input, .. } => input.1,
+            _ => 0,
+        };
+        (p.vis().unwrap_or_default(), p.sort_order(), cursor)
+    }) else {
+        return;
+    };
+    let key = (
+        cwd.into(),
+        vis,
+        sort,
+        cursor,
+        STACK::panel_selections(),
+        height,
+        prefixes,
+    );
+    PANEL.request(
+        "panel listing",
+        key,
+        |(cwd, vis, sort, cursor, selections, height, prefixes)| {
+            panel_listing(cwd, *vis, *sort, *cursor, selections, *height, prefixes)
+        },
+        send,
+    );
+}
+
+/// The other panel as a list below its cwd: its entries with `prefixes[1]` before the selected
+/// ones (`prefixes[0]` before the others), and the saved cursor highlighted and scrolled into the
+/// `height` lines.
+pub fn panel_listing(
+    cwd: &Path,
+    vis: Visibility,
+    sort: SortOrder,
+    cursor: u32,
+    selections: &[u64],
+    height: u16,
+    prefixes: &[String; 2],
+) -> Text<'static> {
+    let title = Line::styled(
+        cwd.to_string_lossy().into_owned(),
+        Style::new().add_modifier(Modifier::BOLD),
+    );
+    let entries = columns::entries(cwd, vis, sort);
+    if entries.is_empty() {
+        return Text::from(vec![
+            title,
+            Line::styled(
+                "empty",
+                Style::new().add_modifier(Modifier::DIM | Modifier::ITALIC),
+            ),
+        ]);
+    }
+
+    let rows = if height == 0 {
+        entries.len()
+    } else {
+        (height as usize).saturating_sub(1).max(1)
+    };
+    let cursor = (cursor as usize).min(entries.len() - 1);
+    let start = cursor.saturating_sub(rows - 1);
+
+    let mut lines = vec![title];
+    lines.extend(
+        entries
+            .into_iter()
+            .enumerate()
+            .skip(start)
+            .take(rows)
+            .map(|(i, p)| {
+                let selected = selections.contains(&hash_path(&AbsPath::new_unchecked(&p)));
+                let mut line = Line::from(vec![
+                    Span::raw(prefixes[selected as usize].clone()),
+                    short_display(&p),
+                ]);
+                if i == cursor {
+                    line = line.patch_style(Style::new().add_modifier(Modifier::REVERSED));
+                }
+                line
+            }),
+    );
+    Text::from(lines)
+}
+
+/// The labels of both panels, left first, with the active one highlighted. `None` outside of
+/// the dual-pane mode.
+pub fn panel_bar() -> Option<Line<'static>> {
+    let (other, right) = STACK::with_panel(|p, right| (p.tab_label(), right))?;
+    let current = STACK::with_current(FsPane::tab_label);
+
+    let active = |label: String| {
+        Span::styled(
+            label,
+            Style::new().add_modifier(Modifier::REVERSED | Modifier::BOLD),
+        )
+    };
+    let (left, right) = if right {
+        (Span::raw(other), active(current))
+    } else {
+        (active(current), Span::raw(other))
+    };
+    Some(Line::from(vec![
+        left,
+        Span::styled(" ⇄ ", Style::new().add_modifier(Modifier::DIM)),
+        right,
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panel_listing() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for f in ["a", "b", "c", "d"] {
+            std::fs::write(root.join(f), "").unwrap();
+        }
+        let prefixes = ["  ".to_string(), "▌ ".to_string()];
+        let selections = [hash_path(&AbsPath::new_unchecked(root.join("b")))];
+        let listing = |cursor, height| {
+            panel_listing(
+                root,
+                Visibility::default(),
+                SortOrder::name,
+                cursor,
+                &selections,
+                height,
+                &prefixes,
+            )
+        };
+
+        let text = listing(1, 0);
+        let lines: Vec<_> = text.lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(lines[1..], ["  a", "▌ b", "  c", "  d"]);
+        assert!(
+            text.lines[2]
+                .style
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        );
+
+        // the cursor is scrolled into view, below the title
+        let text = listing(3, 3);
+        let lines: Vec<_> = text.lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(lines[1..], ["  c", "  d"]);
+        assert!(
+            text.lines[2]
+                .style
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Terminal drag-and-drop into the Nav pane.
+//!
+//! Terminals deliver dropped files as a bracketed paste of their (quoted or escaped) paths, or of
+//! their `file://` URIs. A paste that consists only of existing paths asks what to do with them:
+//! enqueue them for a copy, move or symlink into the current directory, or stash them.
+
+use std::path::{MAIN_SEPARATOR_STR, Path, PathBuf};
+
+use cba::bath::PathExt;
+use ratatui::text::{Line, Span, Text};
+
+use crate::{
+    abspath::AbsPath,
+    cli::paths::__home,
+    run::{
+        FsAction,
+        action::db_stash,
+        item::short_display,
+        queue::{QUEUE, QueueView},
+        state::{GLOBAL, STORE, TOAST, ToastStyle},
+    },
+    ui::confirm_overlay::ConfirmPrompt,
+};
+
+/// The queue kinds of the choices, in order, followed by the stash.
+const KINDS: [&str; 3] = ["copy", "move", "symlink"];
+
+/// The paths of a paste, if it consists only of absolute paths which exist.
+pub fn dropped_paths(content: &str) -> Option<Vec<AbsPath>> {
+    let words = shell_words::split(content.trim()).ok()?;
+    if words.is_empty() {
+        return None;
+    }
+    words
+        .iter()
+        .map(|word| {
+            let path = parse_word(word)?;
+            path.symlink_metadata().is_ok().then(|| AbsPath::new(path))
+        })
+        .collect()
+}
+
+fn parse_word(word: &str) -> Option<PathBuf> {
+    if let Some(uri) = word.strip_prefix("file://") {
+        // the authority (usually empty or localhost) precedes the path
+        let path = &uri[uri.find('/')?..];
+        return Some(PathBuf::from(percent_decode(path)?));
+    }
+    if let Some(rest) = word.strip_prefix("~/") {
+        return Some(Path::new(__home()).join(rest));
+    }
+    let path = Path::new(word);
+    path.is_absolute().then(|| path.to_path_buf())
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Ask whether to copy, move, symlink or stash the dropped `paths` into `cwd`.
+pub fn ask(
+    paths: Vec<AbsPath>,
+    cwd: AbsPath,
+) {
+    let prompt = match &paths[..] {
+        [path] => Line::from(vec!["Dropped ".into(), short_display(path)]),
+        _ => Line::from(format!("Dropped {} items", paths.len())),
+    };
+    let content = (paths.len() > 1).then(|| {
+        Text::from(
+            paths
+                .iter()
+                .map(|p| Line::from(p.display_short(__home())))
+                .collect::<Vec<_>>(),
+        )
+    });
+
+    STORE::set(ConfirmPrompt {
+        prompt,
+        options: vec![
+            ("Copy here", 0),
+            ("Move here", 0),
+            ("Symlink here", 1),
+            ("Stash", 0),
+        ],
+        option_handler: Box::new(move |idx| match KINDS.get(idx) {
+            Some(kind) => enqueue(kind, paths, &cwd),
+            None => {
+                let toast_vec: Vec<Span> = paths.iter().map(|p| short_display(p)).collect();
+                db_stash(String::new(), paths, false);
+                TOAST::push(ToastStyle::Normal, "Stashed: ", toast_vec);
+            }
+        }),
+        content,
+        content_above: false,
+        title_in_border: false,
+        cursor: 0,
+        scroll: 0,
+    });
+    GLOBAL::send_action(FsAction::Confirm);
+}
+
+/// Enqueue `paths` under `kind`, into `cwd`.
+fn enqueue(
+    kind: &str,
+    paths: Vec<AbsPath>,
+    cwd: &AbsPath,
+) {
+    let toast_vec: Vec<Span> = paths.iter().map(|p| short_display(p)).collect();
+    // a trailing separator: into the directory (see `QUEUE::dispatch`)
+    let mut dst = cwd.to_os_string();
+    dst.push(MAIN_SEPARATOR_STR);
+    for i in QUEUE::enqueue(kind.to_string(), paths) {
+        QUEUE::view_update(QueueView::Shared, i, None, Some(dst.clone()));
+    }
+    TOAST::push(
+        ToastStyle::Normal,
+        format!("Enqueued ({kind}): "),
+        toast_vec,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dropped_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let spaced = dir.path().join("a file");
+        std::fs::write(&spaced, "").unwrap();
+        let plain = dir.path().join("plain");
+        std::fs::write(&plain, "").unwrap();
+
+        let paths = |content: &str| {
+            dropped_paths(content).map(|v| v.into_iter().map(PathBuf::from).collect::<Vec<_>>())
+        };
+        let (s, p) = (spaced.display(), plain.display());
+
+        assert_eq!(
+            paths(&format!("'{s}' {p}\n")),
+            Some(vec![spaced.clone(), plain.clone()])
+        );
+        assert_eq!(
+            paths(&s.to_string().replace(' ', "\\ ")),
+            Some(vec![spaced.clone()])
+        );
+        assert_eq!(
+            paths(&format!("file://{}", s.to_string().replace(' ', "%20"))),
+            Some(vec![spaced.clone()])
+        );
+        // any word which is not an existing path makes it an ordinary paste
+        assert_eq!(paths(&format!("{p} missing")), None);
+        assert_eq!(
+            paths(&format!("{p} {}", dir.path().join("missing").display())),
+            None
+        );
+        assert_eq!(paths("plain"), None);
+        assert_eq!(paths(""), None);
+        assert_eq!(paths("'unterminated"), None);
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("/a%20b/%C3%A9").as_deref(), Some("/a b/é"));
+        assert_eq!(percent_decode("/a%2"), None);
+        assert_eq!(percent_decode("/a%zz"), None);
+    }
+}
@@ -14,6 +14,7 @@ use crate::{
     cli::paths::__home,
     config::ui::PathDisplayConfig,
     db::Entry,
+    find::walker::TreeEntry,
     run::state::{render_path, ui::global_ui},
 };
 use fist_types::{
@@ -36,6 +37,9 @@ pub struct PathItem {
     /// nonempty `[1]` is the col-1 display override (app name). `Err(Text)`: pre-rendered
     /// styled text (rg context blocks).
     pub tail: Result<[String; 2], Text<'static>>,
+    /// Nav tree entries below the top level: the indentation guide drawn before the file name,
+    /// which replaces the path.
+    pub guide: Option<Box<str>>,
 }
 
 impl Clone for PathItem {
@@ -47,6 +51,7 @@ impl Clone for PathItem {
                 Ok([a, b]) => Ok([a.clone(), b.clone()]),
                 Err(t) => Err(t.clone()),
             },
+            guide: self.guide.clone(),
         }
     }
 }
@@ -59,6 +64,7 @@ impl PathItem {
             path,
             metadata: AtomicU64::new(u64::MAX),
             tail: Ok([String::new(), String::new()]),
+            guide: None,
         }
     }
 
@@ -67,6 +73,37 @@ impl PathItem {
             path: entry.path,
             metadata: AtomicU64::new(u64::MAX),
             tail: Ok([entry.alias, entry.name]),
+            guide: None,
+        }
+    }
+
+    /// A history entry: its alias is the tail, so it is shown and matched in column 2.
+    pub fn new_entry(entry: Entry) -> Self {
+        Self {
+            path: entry.path,
+            metadata: AtomicU64::new(u64::MAX),
+            tail: Ok([entry.alias, String::new()]),
+            guide: None,
+        }
+    }
+
+    /// A file of a [`crate::run::FsPane::Dupes`] group: the group id is the tail.
+    pub fn new_dupe(path: PathBuf, group: usize) -> Self {
+        Self {
+            path: AbsPath::new_unchecked(path),
+            metadata: AtomicU64::new(u64::MAX),
+            tail: Ok([format!("#{group}"), String::new()]),
+            guide: None,
+        }
+    }
+
+    /// An entry of a [`crate::run::FsPane::Nav`] tree (see [`crate::find::walker::list_tree`]).
+    pub fn new_tree_entry(entry: TreeEntry) -> Self {
+        Self {
+            path: AbsPath::new_unchecked(entry.path),
+            metadata: AtomicU64::new(u64::MAX),
+            tail: Ok([String::new(), String::new()]),
+            guide: (!entry.guide.is_empty()).then(|| entry.guide.into()),
         }
     }
 
@@ -77,11 +114,15 @@ impl PathItem {
             path,
             metadata: AtomicU64::new(u64::MAX),
             tail: Ok([String::new(), String::new()]),
+            guide: None,
         }
     }
 
     pub fn render(&self) -> Text<'static> {
-        render(&self.path, render_path().as_ref().map(|p| p.as_ref()))
+        match &self.guide {
+            Some(guide) => render_guided(&global_ui().path, guide, &self.path),
+            None => render(&self.path, render_path().as_ref().map(|p| p.as_ref())),
+        }
     }
 
     pub fn tail_text(&self) -> Text<'static> {
@@ -203,6 +244,22 @@ fn render_with(cfg: &PathDisplayConfig, mut path: &Path, cwd: Option<&Path>) ->
         path.to_owned()
     };
 
+    render_styled(cfg, full_path, &path)
+}
+
+/// A tree entry: its file name behind the indentation guide.
+fn render_guided(cfg: &PathDisplayConfig, guide: &str, path: &Path) -> Text<'static> {
+    let name = path.file_name().map_or(path, Path::new);
+    let mut text = render_styled(cfg, path, name);
+    if let Some(line) = text.lines.first_mut() {
+        line.spans
+            .insert(0, Span::styled(guide.to_string(), Color::DarkGray));
+    }
+    text
+}
+
+/// Render `path` for `full_path` with its icon and colors.
+fn render_styled(cfg: &PathDisplayConfig, full_path: &Path, path: &Path) -> Text<'static> {
     match full_path.is_dir() {
         true => {
             let icon = icon_for_file(full_path);
@@ -248,7 +305,7 @@ fn render_with(cfg: &PathDisplayConfig, mut path: &Path, cwd: Option<&Path>) ->
             let icon = icon_for_file(full_path);
             let path_str = path.to_string_lossy();
             let style = if cfg.file_colors {
-                let mut style = FileCategory::get(&path)
+                let mut style = FileCategory::get(path)
                     .map(|c| cfg.file_styles.style(&c))
                     .unwrap_or_default();
 
@@ -0,0 +1,134 @@
+//! Vim-style directory marks: [`FsAction::SetMark`] points a single character at the current
+//! directory, and [`FsAction::JumpMark`] jumps back to it through [`FsAction::Jump`]. The marks
+//! are stored in the db (see [`crate::db::Mark`]), except for [`PREVIOUS`], the directory left by
+//! the last jump.
+//!
+//! Without a mark, both actions act as a prefix key: the menu overlay lists the marks and takes
+//! the next key as the mark.
+
+use matchmaker::{action::Action, nucleo::Span};
+
+use crate::run::state::GLOBAL::db;
+use crate::{
+    abspath::AbsPath,
+    db::DbTable,
+    run::{
+        FsAction,
+        item::short_display,
+        state::{GLOBAL, MARK_LISTING, PreviousDir, STACK, STORE, TASKS, TOAST, ToastStyle},
+    },
+};
+
+/// The mark of the directory left by the last jump.
+pub const PREVIOUS: char = '\'';
+
+/// The marks listed by the menu overlay, fetched by [`list`].
+#[derive(Debug)]
+pub struct MarkListing {
+    /// Whether the next key sets a mark, rather than jumps to one.
+    pub set: bool,
+    pub marks: Vec<(char, AbsPath)>,
+}
+
+/// Whether `mark` can be set.
+pub fn settable(mark: char) -> bool {
+    mark.is_ascii_alphanumeric()
+}
+
+/// Mark the current directory as `mark`.
+pub fn set(mark: char) {
+    if !settable(mark) {
+        TOAST::push(
+            ToastStyle::Error,
+            "Invalid mark: ",
+            [Span::raw(mark.to_string())],
+        );
+        return;
+    }
+    let Some(cwd) = STACK::cwd() else {
+        TOAST::notice(ToastStyle::Normal, "No current directory.");
+        return;
+    };
+    TASKS::spawn("db set mark", async move {
+        match db().get_conn(DbTable::dirs).await {
+            Ok(mut conn) => match conn.set_mark(mark, &cwd).await {
+                Ok(()) => TOAST::push(
+                    ToastStyle::Success,
+                    format!("Marked {mark}: "),
+                    [short_display(&cwd)],
+                ),
+                Err(e) => {
+                    log::error!("Error setting mark: {e}");
+                    TOAST::notice(ToastStyle::Error, "Failed to set the mark.");
+                }
+            },
+            Err(e) => {
+                log::error!("Error getting connection: {e}");
+            }
+        }
+    });
+}
+
+/// Jump to the directory of `mark`.
+pub fn jump(mark: char) {
+    if mark == PREVIOUS {
+        match STORE::get::<PreviousDir>() {
+            Some(PreviousDir(path)) => GLOBAL::send_action(FsAction::Jump(vec![path.into()])),
+            None => TOAST::notice(ToastStyle::Normal, "No previous directory."),
+        }
+        return;
+    }
+    TASKS::spawn("db get mark", async move {
+        match db().get_conn(DbTable::dirs).await {
+            Ok(mut conn) => match conn.get_mark(mark).await {
+                Ok(Some(path)) => GLOBAL::send_action(FsAction::Jump(vec![path.into()])),
+                Ok(None) => TOAST::push(
+                    ToastStyle::Normal,
+                    "Mark not set: ",
+                    [Span::raw(mark.to_string())],
+                ),
+                Err(e) => {
+                    log::error!("Error getting mark: {e}");
+                    TOAST::notice(ToastStyle::Error, "Failed to get the mark.");
+                }
+            },
+            Err(e) => {
+                log::error!("Error getting connection: {e}");
+            }
+        }
+    });
+}
+
+/// Open the menu overlay on the marks, for the next key to set (`set`) or jump to a mark.
+pub fn list(set: bool) {
+    let previous = (!set)
+        .then(STORE::get::<PreviousDir>)
+        .flatten()
+        .map(|p| (PREVIOUS, p.0));
+    TASKS::spawn("db list marks", async move {
+        let marks = match db().get_conn(DbTable::dirs).await {
+            Ok(mut conn) => match conn.get_marks().await {
+                Ok(marks) => marks,
+                Err(e) => {
+                    log::error!("Error listing marks: {e}");
+                    TOAST::notice(ToastStyle::Error, "Failed to load the marks.");
+                    return;
+                }
+            },
+            Err(e) => {
+                log::error!("Error getting connection: {e}");
+                return;
+            }
+        };
+        let marks = previous
+            .into_iter()
+            .chain(
+                marks
+                    .into_iter()
+                    .filter_map(|m| Some((m.mark.chars().next()?, m.path))),
+            )
+            .collect();
+        *MARK_LISTING.lock().unwrap() = Some(MarkListing { set, marks });
+        GLOBAL::send_action(Action::Overlay(4));
+    });
+}
@@ -0,0 +1,469 @@
+//! Metadata columns of the Nav and Find panes (`panes.nav.metadata`, `panes.find.metadata`).
+//!
+//! Every column is a worker column (see [`crate::run::start`]) which renders empty unless it is
+//! enabled for the current pane, so that disabled columns take no width. The enabled columns
+//! start from the config and are toggled through the options overlay. They share half of the
+//! width of the results, in the order they were enabled: the columns past that budget are
+//! hidden until the results are wide enough.
+//!
+//! Cells are rendered from [`Stat`]s read by a background thread and cached until the next
+//! reload (see [`clear`]), or until the watcher reports a change to the path (see
+//! [`invalidate`]). Uncached cells are blank until the thread redraws.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs::{self, Metadata},
+    os::unix::fs::{MetadataExt, PermissionsExt},
+    path::{Path, PathBuf},
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicU16, Ordering},
+        mpsc::{self, Sender},
+    },
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Local};
+use fist_types::git;
+use matchmaker::{
+    message::RenderCommand,
+    nucleo::{Span, Style, Text},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    cli::liza::backend::native::walker::format_symbolic_permissions,
+    display::human_size,
+    run::{
+        FsPane, git_status_style,
+        item::PathItem,
+        state::{GLOBAL, STACK, sort},
+    },
+};
+
+/// A metadata column, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetaColumn {
+    /// File size, or the total size of a directory (computed in the background).
+    Size,
+    /// Modification time.
+    Mtime,
+    /// Symbolic permissions, as in `ls -l`.
+    Perms,
+    /// Name of the owning user.
+    Owner,
+    /// Git status marker (replacing the one in front of the tail).
+    Git,
+    /// Number of entries of a directory.
+    Items,
+}
+
+impl MetaColumn {
+    pub const ALL: [Self; 6] = [
+        Self::Size,
+        Self::Mtime,
+        Self::Perms,
+        Self::Owner,
+        Self::Git,
+        Self::Items,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Size => "size",
+            Self::Mtime => "mtime",
+            Self::Perms => "perms",
+            Self::Owner => "owner",
+            Self::Git => "git",
+            Self::Items => "items",
+        }
+    }
+
+    /// Options overlay label, with the index of its (uppercased) key.
+    pub fn label(self) -> (&'static str, usize) {
+        match self {
+            Self::Size => ("Size", 0),
+            Self::Mtime => ("Modified", 0),
+            Self::Perms => ("Permissions", 0),
+            Self::Owner => ("Owner", 0),
+            Self::Git => ("Git status", 0),
+            Self::Items => ("Item count", 5),
+        }
+    }
+
+    /// The widest cell of this column, which it is budgeted for (longer owners are truncated).
+    pub fn width(self) -> u16 {
+        match self {
+            Self::Size => 8,
+            Self::Mtime => 16,
+            Self::Perms => 10,
+            Self::Owner => 8,
+            Self::Git => 1,
+            Self::Items => 6,
+        }
+    }
+
+    /// The options overlay key toggling this column.
+    pub fn key(self) -> char {
+        let (label, i) = self.label();
+        label.as_bytes()[i].to_ascii_uppercase() as char
+    }
+
+    /// The cell of `item`, empty when the column is disabled for the current pane or does not
+    /// fit in the budget.
+    pub fn render(
+        self,
+        item: &PathItem,
+    ) -> Text<'static> {
+        if !visible(self) {
+            return Text::default();
+        }
+        let path = &item.path;
+        if self == Self::Git {
+            return git::cached_status(path)
+                .map(|s| Text::from(Span::styled(s.marker().to_string(), git_status_style(s))))
+                .unwrap_or_default();
+        }
+        let Some(stat) = cached_stat(path, self == Self::Items) else {
+            return Text::default();
+        };
+        match self {
+            Self::Size | Self::Items => Text::from(format_cell(self, path, &stat)).right_aligned(),
+            _ => Text::from(Span::styled(
+                format_cell(self, path, &stat),
+                Style::new().dim(),
+            )),
+        }
+    }
+}
+
+/// What the columns show of a path.
+#[derive(Debug, Clone)]
+struct Stat {
+    is_dir: bool,
+    len: u64,
+    mtime: Option<SystemTime>,
+    mode: u32,
+    uid: u32,
+    /// The number of entries of a directory, only counted for [`MetaColumn::Items`].
+    items: Option<usize>,
+}
+
+impl Stat {
+    fn read(
+        path: &Path,
+        count: bool,
+    ) -> Option<Self> {
+        let meta: Metadata = fs::symlink_metadata(path).ok()?;
+        let items = (count && meta.is_dir())
+            .then(|| fs::read_dir(path).ok().map(|d| d.count()))
+            .flatten();
+        Some(Self {
+            is_dir: meta.is_dir(),
+            len: meta.len(),
+            mtime: meta.modified().ok(),
+            mode: meta.permissions().mode(),
+            uid: meta.uid(),
+            items,
+        })
+    }
+}
+
+/// The text of a column other than [`MetaColumn::Git`]. Directory sizes are blank until
+/// [`sort::dir_size`] has computed them.
+fn format_cell(
+    column: MetaColumn,
+    path: &Path,
+    stat: &Stat,
+) -> String {
+    match column {
+        MetaColumn::Size if stat.is_dir => match sort::size_of(path) {
+            Some(size) => human_size(size, true),
+            None => {
+                sort::dir_size().add(path);
+                String::new()
+            }
+        },
+        MetaColumn::Size => human_size(stat.len, true),
+        MetaColumn::Mtime => stat
+            .mtime
+            .map(|t| {
+                DateTime::<Local>::from(t)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default(),
+        MetaColumn::Perms => format_symbolic_permissions(stat.mode & 0o7777, stat.is_dir),
+        MetaColumn::Owner => truncate(user_name(stat.uid), MetaColumn::Owner.width()),
+        MetaColumn::Items => stat.items.map(|n| n.to_string()).unwrap_or_default(),
+        MetaColumn::Git => String::new(),
+    }
+}
+
+/// `s` cut to `width` columns, ending with `…` when cut.
+fn truncate(
+    s: String,
+    width: u16,
+) -> String {
+    let width = width as usize;
+    if s.width() <= width {
+        return s;
+    }
+    let mut out = String::new();
+    for c in s.chars() {
+        if out.width() + c.to_string().width() >= width {
+            break;
+        }
+        out.push(c);
+    }
+    out.push('…');
+    out
+}
+
+// ---------------- cache
+
+/// A [`Stat`] in [`STATS`]: `None` when the path could not be read.
+#[derive(Debug)]
+enum Cached {
+    Reading,
+    Read(Option<Stat>),
+}
+
+static STATS: LazyLock<Mutex<HashMap<PathBuf, Cached>>> = LazyLock::new(Default::default);
+
+/// The background thread reading the [`Stat`]s of the requested paths (and whether to count
+/// their entries). Redraws once it runs out of requests.
+static READER: LazyLock<Mutex<Sender<(PathBuf, bool)>>> = LazyLock::new(|| {
+    let (tx, rx) = mpsc::channel::<(PathBuf, bool)>();
+    std::thread::spawn(move || {
+        while let Ok(mut request) = rx.recv() {
+            loop {
+                let (path, count) = request;
+                let stat = Stat::read(&path, count);
+                // dropped when invalidated in the meantime
+                if let Some(entry @ Cached::Reading) = STATS.lock().unwrap().get_mut(&path) {
+                    *entry = Cached::Read(stat);
+                }
+                match rx.try_recv() {
+                    Ok(next) => request = next,
+                    Err(_) => break,
+                }
+            }
+            GLOBAL::send_mm(RenderCommand::Redraw);
+        }
+    });
+    Mutex::new(tx)
+});
+
+/// The cached [`Stat`] of `path`, with its entries counted when `count`. Requested from the
+/// background thread when missing.
+fn cached_stat(
+    path: &Path,
+    count: bool,
+) -> Option<Stat> {
+    let mut stats = STATS.lock().unwrap();
+    match stats.get(path) {
+        Some(Cached::Reading) => return None,
+        Some(Cached::Read(stat)) => match stat {
+            Some(stat) if count && stat.is_dir && stat.items.is_none() => {}
+            stat => return stat.clone(),
+        },
+        None => {}
+    }
+    stats.insert(path.to_path_buf(), Cached::Reading);
+    READER
+        .lock()
+        .unwrap()
+        .send((path.to_path_buf(), count))
+        .ok();
+    None
+}
+
+/// Drop the cached [`Stat`]s, e.g. when the pane is reloaded.
+pub fn clear() {
+    STATS.lock().unwrap().clear();
+}
+
+/// Drop the cached [`Stat`]s of `paths`, returning whether any was cached.
+pub fn invalidate(paths: &[PathBuf]) -> bool {
+    let mut stats = STATS.lock().unwrap();
+    let mut any = false;
+    for p in paths {
+        any |= stats.remove(p).is_some();
+    }
+    any
+}
+
+// ---------------- budget
+
+/// The width of the results (see [`set_width`]).
+static WIDTH: AtomicU16 = AtomicU16::new(u16::MAX);
+
+/// Record the width of the results, half of which is shared by the columns.
+pub fn set_width(width: u16) {
+    WIDTH.store(width, Ordering::Relaxed);
+}
+
+/// Whether `column` is enabled and fits in the budget after the columns enabled before it.
+fn visible(column: MetaColumn) -> bool {
+    let Some(i) = slot() else {
+        return false;
+    };
+    let budget = WIDTH.load(Ordering::Relaxed) / 2;
+    with_enabled(|e| {
+        let mut used = 0;
+        for c in &e[i] {
+            // one for the separator
+            used += c.width() + 1;
+            if used > budget {
+                return false;
+            }
+            if *c == column {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+thread_local! {
+    /// Enabled columns of the Nav and Find panes, initialized from the config.
+    static ENABLED: RefCell<Option<[Vec<MetaColumn>; 2]>> = const { RefCell::new(None) };
+    static USERS: RefCell<HashMap<u32, String>> = RefCell::new(HashMap::new());
+}
+
+/// The index of the current pane in [`ENABLED`], if it shows metadata columns.
+fn slot() -> Option<usize> {
+    STACK::with_current(|p| match p {
+        FsPane::Nav { .. } => Some(0),
+        FsPane::Find { .. } => Some(1),
+        _ => None,
+    })
+}
+
+fn with_enabled<R>(f: impl FnOnce(&mut [Vec<MetaColumn>; 2]) -> R) -> R {
+    ENABLED.with(|cell| {
+        let mut enabled = cell.borrow_mut();
+        let enabled = enabled.get_or_insert_with(|| {
+            let panes = &GLOBAL::cfg().panes;
+            [panes.nav.metadata.clone(), panes.find.metadata.clone()]
+        });
+        f(enabled)
+    })
+}
+
+/// Whether the current pane shows metadata columns.
+pub fn supported() -> bool {
+    slot().is_some()
+}
+
+/// Whether `column` is shown in the current pane.
+pub fn enabled(column: MetaColumn) -> bool {
+    slot().is_some_and(|i| with_enabled(|e| e[i].contains(&column)))
+}
+
+/// Show or hide `column` in the current pane. Returns whether the pane shows metadata columns.
+pub fn toggle(column: MetaColumn) -> bool {
+    let Some(i) = slot() else {
+        return false;
+    };
+    with_enabled(|e| {
+        if let Some(pos) = e[i].iter().position(|c| *c == column) {
+            e[i].remove(pos);
+        } else {
+            e[i].push(column);
+        }
+    });
+    true
+}
+
+/// The name of the user `uid`, or the uid when it has none.
+fn user_name(uid: u32) -> String {
+    USERS.with(|cache| {
+        cache
+            .borrow_mut()
+            .entry(uid)
+            .or_insert_with(|| lookup_user(uid).unwrap_or_else(|| uid.to_string()))
+            .clone()
+    })
+}
+
+fn lookup_user(uid: u32) -> Option<String> {
+    let mut buf = vec![0 as libc::c_char; 1024];
+    let mut pwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result = std::ptr::null_mut();
+    let ret = unsafe { libc::getpwuid_r(uid, &mut pwd, buf.as_mut_ptr(), buf.len(), &mut result) };
+    if ret != 0 || result.is_null() {
+        return None;
+    }
+    let name = unsafe { std::ffi::CStr::from_ptr(pwd.pw_name) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_cell() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("file");
+        fs::write(&file, "abc").unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+
+        let cell =
+            |column, path: &Path| format_cell(column, path, &Stat::read(path, true).unwrap());
+        assert_eq!(cell(MetaColumn::Size, &file), "3 B");
+        assert_eq!(cell(MetaColumn::Items, &file), "");
+        assert_eq!(cell(MetaColumn::Items, dir.path()), "2");
+        assert!(cell(MetaColumn::Perms, &file).starts_with('-'));
+        assert!(cell(MetaColumn::Perms, dir.path()).starts_with('d'));
+        assert_eq!(
+            cell(MetaColumn::Mtime, &file).len(),
+            "2026-01-01 00:00".len()
+        );
+        assert_eq!(
+            cell(MetaColumn::Owner, &file),
+            user_name(unsafe { libc::getuid() })
+        );
+
+        // entries are only counted on request
+        assert_eq!(
+            format_cell(
+                MetaColumn::Items,
+                dir.path(),
+                &Stat::read(dir.path(), false).unwrap()
+            ),
+            ""
+        );
+        assert!(Stat::read(&dir.path().join("missing"), false).is_none());
+    }
+
+    #[test]
+    fn test_stat_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("file");
+        STATS
+            .lock()
+            .unwrap()
+            .insert(file.clone(), Cached::Read(None));
+        assert!(cached_stat(&file, false).is_none());
+        assert!(invalidate(std::slice::from_ref(&file)));
+        assert!(!invalidate(&[file]));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("root".into(), 8), "root");
+        assert_eq!(truncate("postgres".into(), 8), "postgres");
+        assert_eq!(truncate("systemd-network".into(), 8), "systemd…");
+    }
+
+    #[test]
+    fn test_keys() {
+        let keys: Vec<char> = MetaColumn::ALL.iter().map(|c| c.key()).collect();
+        assert_eq!(keys, ['S', 'M', 'P', 'O', 'G', 'C']);
+    }
+}
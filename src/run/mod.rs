@@ -1,11 +1,17 @@
 pub mod ahandlers;
 mod binds;
+pub mod columns;
+pub mod commander;
+mod drop;
 mod previewer;
 pub(crate) mod register;
 
 // mm/init
 pub mod item;
+pub mod marks;
+pub mod meta;
 pub mod mm_config;
+pub mod mouse;
 mod start;
 pub use start::*;
 // logic
@@ -19,5 +25,6 @@ pub mod selection;
 mod pane;
 pub use pane::*;
 pub mod queue;
+pub mod replace;
 pub mod stash;
 pub mod state;
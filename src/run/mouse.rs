@@ -0,0 +1,393 @@
+//! Mouse support on top of matchmaker's own handling, which moves the cursor on click and scrolls
+//! the list and preview: double-click to [`FsAction::Advance`], shift-click or drag to select a
+//! range, and clicks on the breadcrumbs of the cwd prompt to jump to an ancestor.
+//!
+//! Matchmaker forwards the mouse events it does not bind to every sender of its event loop, so
+//! [`listen`] adds one and turns the left clicks into [`FsAction::Click`], handled by [`handle`].
+//! With `[ui] mouse_events = false` (mm.toml), [`release_capture`] instead leaves the mouse to the
+//! terminal, so that its text selection works.
+
+use std::{
+    fs::OpenOptions,
+    path::{MAIN_SEPARATOR, Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use cba::bait::ResultExt;
+use crossterm::event::{
+    DisableMouseCapture, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use matchmaker::{
+    acs,
+    action::Actions,
+    event::EventLoop,
+    message::{Event, Interrupt, RenderCommand},
+};
+use ratatui::layout::{Position, Rect};
+use tokio::sync::mpsc;
+use unicode_width::UnicodeWidthChar;
+
+use crate::{
+    aliases::MMState,
+    run::{
+        FsAction, FsMatchmaker,
+        ahandlers::lock_prompt,
+        state::{DragAnchor, GLOBAL, InPrompt, LastCursor, STACK, STORE},
+    },
+    utils::formatter::format_prompt,
+};
+
+/// Longest interval between the two clicks of a double-click.
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickKind {
+    /// Left button press.
+    Press,
+    /// Second press on the same cell within [`DOUBLE_CLICK`].
+    Double,
+    /// Left button press with shift held.
+    Shift,
+    /// Motion with the left button held.
+    Drag,
+}
+
+/// A left click at a terminal cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Click {
+    pub kind: ClickKind,
+    pub column: u16,
+    pub row: u16,
+}
+
+/// Tells double-clicks apart from single ones.
+#[derive(Debug, Default)]
+struct ClickTracker {
+    last: Option<(Instant, u16, u16)>,
+}
+
+impl ClickTracker {
+    fn classify(
+        &mut self,
+        event: MouseEvent,
+        now: Instant,
+    ) -> Option<Click> {
+        let MouseEvent {
+            column,
+            row,
+            modifiers,
+            ..
+        } = event;
+        let kind = match event.kind {
+            MouseEventKind::Down(MouseButton::Left) if modifiers.contains(KeyModifiers::SHIFT) => {
+                self.last = None;
+                ClickKind::Shift
+            }
+            MouseEventKind::Down(MouseButton::Left) => match self.last.take() {
+                Some((at, c, r)) if (c, r) == (column, row) && now - at <= DOUBLE_CLICK => {
+                    ClickKind::Double
+                }
+                _ => {
+                    self.last = Some((now, column, row));
+                    ClickKind::Press
+                }
+            },
+            MouseEventKind::Drag(MouseButton::Left) => ClickKind::Drag,
+            _ => return None,
+        };
+        Some(Click { kind, column, row })
+    }
+}
+
+/// Forward the left clicks of `event_loop` as [`FsAction::Click`].
+pub fn listen(event_loop: &mut EventLoop<FsAction>) {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    event_loop.add_tx(tx);
+    tokio::spawn(async move {
+        let mut tracker = ClickTracker::default();
+        while let Some(command) = rx.recv().await {
+            if let RenderCommand::Mouse(event) = command
+                && let Some(click) = tracker.classify(event, Instant::now())
+            {
+                GLOBAL::send_action(FsAction::Click(click));
+            }
+        }
+    });
+}
+
+/// Keep the mouse capture off: matchmaker enables it on start and whenever it returns from an
+/// executed command.
+pub fn release_capture(mm: &mut FsMatchmaker) {
+    mm.register_event_handler(Event::Start | Event::Redraw, |_, _| {
+        // the tty rather than stdout, which may be piped
+        if let Ok(mut tty) = OpenOptions::new().write(true).open("/dev/tty") {
+            crossterm::execute!(tty, DisableMouseCapture)._elog();
+        }
+    });
+    mm.register_interrupt_handler(Interrupt::Execute, |state| {
+        state.insert(Event::Redraw);
+    });
+}
+
+/// Record the cursor position as the anchor of the next shift-click, which matchmaker may
+/// already have moved the cursor away from by the time it is handled.
+pub fn track_cursor(state: &mut MMState<'_>) {
+    if !state.picker_ui.results.cursor_disabled() {
+        STORE::set(LastCursor(state.picker_ui.results.index()));
+    }
+}
+
+/// The input and results areas, as laid out by matchmaker.
+fn areas(state: &MMState<'_>) -> [Rect; 2] {
+    let area = state.ui.border().inner_of(state.ui.picker_area());
+    let picker = &state.picker_ui;
+    let [input, _, _, mut results] = picker.layout(area);
+    // the footer may be split off the picker area, on the side away from the input
+    let height = picker.results.height() + picker.results.config.border.height();
+    if height < results.height {
+        if picker.reverse() {
+            results.y += results.height - height;
+        }
+        results.height = height;
+    }
+    [input, results]
+}
+
+pub fn handle(
+    click: Click,
+    state: &mut MMState<'_>,
+) -> Actions<FsAction> {
+    if state.overlay_index().is_some() {
+        return acs![];
+    }
+    let pos = Position::new(click.column, click.row);
+    let [input, results_area] = areas(state);
+
+    if input.contains(pos) {
+        if click.kind == ClickKind::Press
+            && let Some(path) = clicked_ancestor(state, input, pos.x)
+        {
+            return acs![FsAction::Jump(vec![path])];
+        }
+        return acs![];
+    }
+
+    // a press on a column gutter resizes the column
+    let on_gutter = || {
+        let results = &state.picker_ui.results;
+        results.get_gutter_col_idx(pos.x - results_area.x).is_some()
+    };
+    let index = (results_area.contains(pos) && (click.kind == ClickKind::Drag || !on_gutter()))
+        .then(|| {
+            state
+                .picker_ui
+                .results
+                .get_index_of_row(pos.y - results_area.y)
+        })
+        .flatten();
+    let Some(index) = index else {
+        if click.kind == ClickKind::Press {
+            STORE::take::<DragAnchor>();
+        }
+        return acs![];
+    };
+
+    match click.kind {
+        ClickKind::Press => {
+            STORE::set(DragAnchor {
+                anchor: index,
+                base: state.picker_ui.selector.iter().copied().collect(),
+            });
+            acs![]
+        }
+        ClickKind::Double => {
+            if STORE::contains::<InPrompt>() {
+                lock_prompt(state, false);
+            }
+            state.picker_ui.results.cursor_jump(index);
+            acs![FsAction::Advance]
+        }
+        ClickKind::Shift => {
+            let anchor = DragAnchor {
+                anchor: STORE::get::<LastCursor>().map_or(index, |c| c.0),
+                base: state.picker_ui.selector.iter().copied().collect(),
+            };
+            select_range(state, &anchor, index);
+            STORE::set(anchor);
+            acs![]
+        }
+        ClickKind::Drag => {
+            if let Some(anchor) = STORE::get::<DragAnchor>() {
+                select_range(state, &anchor, index);
+            }
+            acs![]
+        }
+    }
+}
+
+/// Select the items from the anchor to `index` (both results positions) on top of the selection
+/// the drag started with, so that dragging back deselects, and move the cursor to `index`.
+fn select_range(
+    state: &mut MMState<'_>,
+    DragAnchor { anchor, base }: &DragAnchor,
+    index: u32,
+) {
+    let picker = &mut *state.picker_ui;
+    let ids: Vec<u32> = ((*anchor).min(index)..=(*anchor).max(index))
+        .filter_map(|i| picker.worker.get_nth_indexed(i).map(|(id, _)| id))
+        .collect();
+    picker.selector.clear();
+    picker.selector.extend(base.iter().copied());
+    picker.selector.extend(ids);
+    picker.results.cursor_jump(index);
+}
+
+/// The ancestor whose breadcrumb was clicked at column `x` of the cwd prompt.
+fn clicked_ancestor(
+    state: &MMState<'_>,
+    input: Rect,
+    x: u16,
+) -> Option<PathBuf> {
+    if !state.picker_ui.results.cursor_disabled() {
+        return None;
+    }
+    let cwd = STACK::cwd()?;
+    let prompt = format_prompt(&GLOBAL::cfg().interface.cwd_prompt, &cwd);
+    let start = prompt_start(input.x + state.picker_ui.query.left(), &prompt)?;
+    breadcrumb(&prompt, x.checked_sub(start)?, &cwd)
+}
+
+/// The column of `prompt`, which ends where the query starts at column `query`. `None` when it
+/// does not fit on the left of the query.
+fn prompt_start(
+    query: u16,
+    prompt: &str,
+) -> Option<u16> {
+    query.checked_sub(prompt_width(prompt))
+}
+
+fn prompt_width(prompt: &str) -> u16 {
+    prompt.chars().filter_map(|c| c.width()).sum::<usize>() as u16
+}
+
+/// The ancestor of `cwd` displayed at column `x` of `prompt`: the path shown in the prompt ends
+/// with `cwd`, so each separator from the clicked column on is a level up. `None` for `cwd`
+/// itself.
+fn breadcrumb(
+    prompt: &str,
+    x: u16,
+    cwd: &Path,
+) -> Option<PathBuf> {
+    let mut width = 0;
+    let (i, _) = prompt.char_indices().find(|(_, c)| {
+        width += c.width().unwrap_or(0);
+        width > x as usize
+    })?;
+    let up = prompt[i..]
+        .trim_end()
+        .trim_end_matches(MAIN_SEPARATOR)
+        .chars()
+        .filter(|c| *c == MAIN_SEPARATOR)
+        .count();
+    if up == 0 {
+        return None;
+    }
+    cwd.ancestors().nth(up).map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        kind: MouseEventKind,
+        column: u16,
+        modifiers: KeyModifiers,
+    ) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row: 3,
+            modifiers,
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let mut tracker = ClickTracker::default();
+        let down = MouseEventKind::Down(MouseButton::Left);
+        let now = Instant::now();
+        let kind = |tracker: &mut ClickTracker, column, modifiers, at| {
+            tracker
+                .classify(event(down, column, modifiers), at)
+                .map(|c| c.kind)
+        };
+        let none = KeyModifiers::NONE;
+
+        assert_eq!(kind(&mut tracker, 1, none, now), Some(ClickKind::Press));
+        assert_eq!(
+            kind(&mut tracker, 1, none, now + Duration::from_millis(100)),
+            Some(ClickKind::Double)
+        );
+        // a third click starts over
+        assert_eq!(
+            kind(&mut tracker, 1, none, now + Duration::from_millis(200)),
+            Some(ClickKind::Press)
+        );
+        // too slow
+        assert_eq!(
+            kind(&mut tracker, 1, none, now + Duration::from_secs(1)),
+            Some(ClickKind::Press)
+        );
+        // another cell
+        assert_eq!(
+            kind(&mut tracker, 2, none, now + Duration::from_millis(1100)),
+            Some(ClickKind::Press)
+        );
+        assert_eq!(
+            kind(
+                &mut tracker,
+                2,
+                KeyModifiers::SHIFT,
+                now + Duration::from_millis(1200)
+            ),
+            Some(ClickKind::Shift)
+        );
+        assert_eq!(
+            tracker
+                .classify(event(MouseEventKind::Drag(MouseButton::Left), 5, none), now)
+                .map(|c| c.kind),
+            Some(ClickKind::Drag)
+        );
+        assert_eq!(
+            tracker.classify(event(MouseEventKind::ScrollDown, 5, none), now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_breadcrumb() {
+        let cwd = Path::new("/home/user/src/fist");
+        let prompt = "~/src/fist ";
+        let at = |x| breadcrumb(prompt, x, cwd);
+        assert_eq!(at(0), Some(PathBuf::from("/home/user")));
+        assert_eq!(at(1), Some(PathBuf::from("/home/user")));
+        assert_eq!(at(3), Some(PathBuf::from("/home/user/src")));
+        assert_eq!(at(5), Some(PathBuf::from("/home/user/src")));
+        assert_eq!(at(6), None);
+        assert_eq!(at(10), None);
+        assert_eq!(at(20), None);
+
+        let root = Path::new("/usr/lib");
+        assert_eq!(breadcrumb("/usr/lib ", 0, root), Some(PathBuf::from("/")));
+        assert_eq!(
+            breadcrumb("/usr/lib ", 2, root),
+            Some(PathBuf::from("/usr"))
+        );
+        assert_eq!(breadcrumb("/ ", 0, Path::new("/")), None);
+
+        // a prompt wider than the space on the left of the query
+        assert_eq!(prompt_start(12, prompt), Some(1));
+        assert_eq!(prompt_start(4, prompt), None);
+    }
+}
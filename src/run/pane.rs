@@ -1,14 +1,16 @@
 use std::{
+    collections::HashSet,
     ffi::OsString,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, atomic::AtomicBool},
 };
 
-use cba::{StringError, bring::split::join_with_single_quotes};
+use cba::{StringError, bath::PathExt, bring::split::join_with_single_quotes};
 use matchmaker::preview::AppendOnly;
 
 use crate::{
     abspath::AbsPath,
+    db::VisitRange,
     lua::{LuaFn, compile_script},
     run::{
         item::PathItem,
@@ -74,10 +76,13 @@ pub enum FsPane {
     Files {
         sort: SortOrder,
         input: (String, u32), // input, INDEX
+        /// Only list entries visited within this range (see [`crate::db::Visit`]).
+        range: VisitRange,
     },
     Folders {
         sort: SortOrder,
         input: (String, u32), // input, INDEX
+        range: VisitRange,
     },
     Apps {
         sort: SortOrder,
@@ -98,7 +103,26 @@ pub enum FsPane {
         vis: Visibility,
         input: (String, u32), // input, INDEX
         complete: Arc<AtomicBool>,
+        /// Directories up to this many levels deep are listed (1 = a flat listing, see
+        /// [`crate::find::walker::list_tree`]).
         depth: usize,
+        /// Directories expanded or collapsed against `depth`.
+        toggled: HashSet<PathBuf>,
+    },
+    /// Every aliased entry of the `dirs` and `files` db tables.
+    Aliases {
+        sort: SortOrder,
+        input: (String, u32), // input, INDEX
+    },
+    /// Groups of identical files under `paths` (see [`crate::fs::dupes`]).
+    /// Each item's tail is the id of its group.
+    Dupes {
+        cwd: AbsPath,
+        paths: Vec<PathBuf>,
+        sort: SortOrder,
+        vis: Visibility,
+        input: (String, u32), // input, INDEX
+        complete: Arc<AtomicBool>,
     },
 }
 
@@ -154,6 +178,32 @@ impl FsPane {
         }
     }
 
+    /// Aliases panes are nucleo-sorted like stash panes.
+    pub fn new_aliases() -> Self {
+        Self::Aliases {
+            sort: SortOrder::none,
+            input: (String::new(), 0),
+        }
+    }
+
+    /// Duplicates pane over `paths`, or the cwd when empty. Starts unsorted so that the files
+    /// of each group stay together.
+    pub fn new_dupes(cwd: AbsPath, paths: Vec<PathBuf>, vis: Visibility) -> Self {
+        let paths = if paths.is_empty() {
+            vec![cwd.inner()]
+        } else {
+            paths.into_iter().map(|p| p.abs(&cwd)).collect()
+        };
+        Self::Dupes {
+            cwd,
+            paths,
+            sort: SortOrder::none,
+            vis: vis.validated(),
+            input: Default::default(),
+            complete: Default::default(),
+        }
+    }
+
     pub fn new_fd_full(
         cwd: AbsPath,
         vis: Visibility,
@@ -245,6 +295,7 @@ impl FsPane {
             sort,
             vis: vis.validated(),
             depth: 1,
+            toggled: Default::default(),
             input: Default::default(),
             complete: Default::default(),
         }
@@ -257,11 +308,13 @@ impl FsPane {
             Self::Folders {
                 sort: SortOrder::none,
                 input: (String::new(), 0),
+                range: VisitRange::default(),
             }
         } else {
             Self::Files {
                 sort: SortOrder::none,
                 input: (String::new(), 0),
+                range: VisitRange::default(),
             }
         }
     }
@@ -279,6 +332,8 @@ impl FsPane {
             | FsPane::Folders { sort, .. }
             | FsPane::Apps { sort, .. }
             | FsPane::Stash { sort, .. }
+            | FsPane::Aliases { sort, .. }
+            | FsPane::Dupes { sort, .. }
             | FsPane::Nav { sort, .. } => *sort,
         }
     }
@@ -293,6 +348,8 @@ impl FsPane {
             | FsPane::Folders { sort, .. }
             | FsPane::Apps { sort, .. }
             | FsPane::Stash { sort, .. }
+            | FsPane::Aliases { sort, .. }
+            | FsPane::Dupes { sort, .. }
             | FsPane::Nav { sort, .. } => sort,
         }
     }
@@ -312,8 +369,8 @@ impl FsPane {
         Ok(self)
     }
 
-    /// Static pane name used in CLI error messages.
-    fn pane_name(&self) -> &'static str {
+    /// Static pane name used in CLI error messages and the visit log.
+    pub(crate) fn pane_name(&self) -> &'static str {
         match self {
             FsPane::Custom { .. } => "custom",
             FsPane::Find { .. } => "fd",
@@ -322,22 +379,47 @@ impl FsPane {
             FsPane::Folders { .. } => "folders",
             FsPane::Apps { .. } => "apps",
             FsPane::Stash { .. } => "stash",
+            FsPane::Aliases { .. } => "aliases",
+            FsPane::Dupes { .. } => "dupes",
             FsPane::Nav { .. } => "nav",
         }
     }
 
+    /// Label of the pane in the tab bar: the name of its directory, after the pane name outside of
+    /// [`FsPane::Nav`].
+    pub fn tab_label(&self) -> String {
+        let dir = |cwd: &AbsPath| {
+            cwd.file_name()
+                .map_or_else(|| cwd.to_string_lossy(), |n| n.to_string_lossy())
+                .into_owned()
+        };
+        match self {
+            FsPane::Nav { cwd, .. } => dir(cwd),
+            FsPane::Custom { cwd, .. }
+            | FsPane::Find { cwd, .. }
+            | FsPane::Search { cwd, .. }
+            | FsPane::Dupes { cwd, .. } => format!("{} {}", self.pane_name(), dir(cwd)),
+            FsPane::Stash { stash_name, .. } if !stash_name.is_empty() => {
+                format!("{} {stash_name}", self.pane_name())
+            }
+            _ => self.pane_name().to_string(),
+        }
+    }
+
     #[inline]
     pub fn vis(&self) -> Option<Visibility> {
         match self {
             FsPane::Custom { vis, .. }
             | FsPane::Find { vis, .. }
             | FsPane::Search { vis, .. }
+            | FsPane::Dupes { vis, .. }
             | FsPane::Nav { vis, .. } => Some(*vis),
 
             FsPane::Files { .. }
             | FsPane::Folders { .. }
             | FsPane::Apps { .. }
-            | FsPane::Stash { .. } => None,
+            | FsPane::Stash { .. }
+            | FsPane::Aliases { .. } => None,
         }
     }
 
@@ -349,6 +431,7 @@ impl FsPane {
                 | FsPane::Custom { .. }
                 | FsPane::Find { .. }
                 | FsPane::Search { .. }
+                | FsPane::Dupes { .. }
         )
     }
 
@@ -378,8 +461,10 @@ impl FsPane {
                 SortOrder::size,
                 SortOrder::none,
             ],
-            // Stash is nucleo-sorted like Nav/fd
+            // Stash/Aliases are nucleo-sorted like Nav/fd
             FsPane::Stash { .. }
+            | FsPane::Aliases { .. }
+            | FsPane::Dupes { .. }
             | FsPane::Nav { .. }
             | FsPane::Find { .. }
             | FsPane::Custom { .. } => &[
@@ -412,11 +497,13 @@ impl FsPane {
             FsPane::Custom { complete, .. }
             | FsPane::Find { complete, .. }
             | FsPane::Search { complete, .. }
+            | FsPane::Dupes { complete, .. }
             | FsPane::Nav { complete, .. } => complete.load(std::sync::atomic::Ordering::Acquire),
             FsPane::Files { .. }
             | FsPane::Folders { .. }
             | FsPane::Apps { .. }
-            | FsPane::Stash { .. } => true,
+            | FsPane::Stash { .. }
+            | FsPane::Aliases { .. } => true,
         }
     }
 
@@ -439,7 +526,13 @@ impl FsPane {
                 }
             }
             FsPane::Custom { .. } => GLOBAL::cfg().interface.stability_threshold, // maybe
-            FsPane::Nav { sort, .. } | FsPane::Find { sort, .. } | FsPane::Stash { sort, .. } => {
+            // tree order is the insertion order
+            FsPane::Nav { .. } if self.is_tree() => u32::MAX,
+            FsPane::Nav { sort, .. }
+            | FsPane::Find { sort, .. }
+            | FsPane::Stash { sort, .. }
+            | FsPane::Aliases { sort, .. }
+            | FsPane::Dupes { sort, .. } => {
                 if matches!(sort, SortOrder::none) {
                     0
                 } else {
@@ -449,6 +542,24 @@ impl FsPane {
         }
     }
 
+    /// Whether the pane is a [`FsPane::Nav`] listing more than one level, which is sorted per
+    /// level as it populates.
+    #[inline]
+    pub fn is_tree(&self) -> bool {
+        matches!(self, FsPane::Nav { depth, toggled, .. } if *depth > 1 || !toggled.is_empty())
+    }
+
+    /// Whether a directory `level` levels below the cwd of a [`FsPane::Nav`] is expanded.
+    pub fn is_expanded(
+        depth: usize,
+        toggled: &HashSet<PathBuf>,
+        dir: &Path,
+        level: usize,
+    ) -> bool {
+        // symlinks are only expanded on request
+        (level < depth && !dir.is_symlink()) != toggled.contains(dir)
+    }
+
     /// Whether the pane sits on its configured default-sort override, with a
     /// `none` default never counting. This is the state that hides the metadata
     /// column ([`HideMetadata`]) until the first explicit re-sort.
@@ -474,7 +585,9 @@ impl FsPane {
             | FsPane::Nav { input, .. }
             | FsPane::Files { input, .. }
             | FsPane::Folders { input, .. }
-            | FsPane::Stash { input, .. } => input.0.clone(),
+            | FsPane::Stash { input, .. }
+            | FsPane::Aliases { input, .. }
+            | FsPane::Dupes { input, .. } => input.0.clone(),
 
             FsPane::Search {
                 input,
@@ -505,12 +618,14 @@ impl FsPane {
             FsPane::Custom { vis, .. }
             | FsPane::Find { vis, .. }
             | FsPane::Search { vis, .. }
+            | FsPane::Dupes { vis, .. }
             | FsPane::Nav { vis, .. } => Some(vis),
 
             FsPane::Files { .. }
             | FsPane::Folders { .. }
             | FsPane::Apps { .. }
-            | FsPane::Stash { .. } => None,
+            | FsPane::Stash { .. }
+            | FsPane::Aliases { .. } => None,
         }
     }
 }
@@ -3,9 +3,10 @@
 use std::{
     cell::RefCell,
     collections::VecDeque,
+    ffi::OsString,
     fmt::Display,
     io::{self, Read},
-    path::Path,
+    path::{Path, PathBuf},
     process::Command,
     sync::{
         Arc,
@@ -30,25 +31,40 @@ use crate::run::state::GLOBAL::db;
 use crate::{
     abspath::AbsPath,
     db::DbTable,
-    find::{apps::collect_apps, fd::build_fd_args, walker::list_dir},
+    find::{
+        apps::collect_apps,
+        fd::{build_fd_args, use_native_fd},
+        native::NativeFd,
+        walker::{list_dir, list_tree},
+    },
+    fs::dupes::find_duplicates,
     lua::call_transform,
     run::{
         FsAction,
         item::PathItem,
+        meta::{self, MetaColumn},
         start::FsInjector,
         state::{GLOBAL, STACK, TASKS, TaskId, sort},
     },
 };
 use crate::{
     config::{GlobalConfig, StashPaneKind},
-    find::rg::{build_rg_args, is_inverted},
+    find::{
+        grep::NativeRg,
+        rg::{build_rg_args, is_inverted, use_native_rg},
+    },
     run::{
         FsPane,
-        populate_rg::{BufItem, MultilineRgParser, flush_rg_buffer, process_rg_line},
+        populate_rg::{
+            BufItem, MultilineRgParser, flush_rg_buffer, native_rg_items, process_rg_line,
+        },
         state::{RanRecache, STORE, ShouldNotAbortOnEmpty, TOAST},
     },
 };
-use fist_types::filters::SortOrder;
+use fist_types::{
+    filters::{SortOrder, Visibility},
+    git,
+};
 
 // todo: when do we need be able to restart after STOP
 // todo: lowpri: this is like 1.2 slower than pure fd? Accept input is a bit sluggish? Cache to reduce disk reads?
@@ -63,6 +79,10 @@ impl FsPane {
         log::debug!("Populating: {self:?}");
         let toast_on_empty = GLOBAL::cfg().interface.toast_on_empty;
 
+        if let Self::Nav { cwd, .. } | Self::Find { cwd, .. } | Self::Custom { cwd, .. } = self {
+            prefetch_git_status(cwd);
+        }
+
         let ret = match self {
             Self::Custom {
                 cmd,
@@ -169,14 +189,24 @@ impl FsPane {
                 let start_time = std::time::Instant::now();
                 let (prog, args) = ("fd", build_fd_args(vis, types, paths, fd_args, &cfg.fd));
 
-                log::info!("spawning: {}", display_sh_prog_and_args(prog, &args));
-
-                let (child, stdout) = Command::new(prog)
-                    .args(args)
-                    .current_dir(&cwd)
-                    .spawn_piped()
-                    ._ebog()?;
-                TASKS::register_child(TaskId::Populate, child);
+                // fd's stdout, or None to walk in-process
+                let stdout = if !use_native_fd(&cfg.fd) {
+                    log::info!("spawning: {}", display_sh_prog_and_args(prog, &args));
+
+                    let (child, stdout) = Command::new(prog)
+                        .args(&args)
+                        .current_dir(&cwd)
+                        .spawn_piped()
+                        ._ebog()?;
+                    TASKS::register_child(TaskId::Populate, child);
+                    Some(stdout)
+                } else {
+                    log::info!(
+                        "walking natively: {}",
+                        display_sh_prog_and_args(prog, &args)
+                    );
+                    None
+                };
 
                 // not sure about this, but this kinda works to ensure only first run of fs will abort
                 let abort_empty = STORE::get::<ShouldNotAbortOnEmpty>().is_none();
@@ -187,61 +217,62 @@ impl FsPane {
                 let _complete = complete.clone();
                 let _cwd = cwd.clone();
                 let transform = transform.clone();
-                map_reader(
-                    stdout,
-                    Some('\0'),
-                    move |line| {
-                        // an empty record is the end-of-stream flush marker, not a path
-                        if line.is_empty() {
-                            return anyhow::Ok(());
-                        }
+                let walk_cwd = cwd.to_path_buf();
+                let on_line = move |line: String| {
+                    // an empty record is the end-of-stream flush marker, not a path
+                    if line.is_empty() {
+                        return anyhow::Ok(());
+                    }
 
-                        let item = if let Some(f) = transform.as_ref() {
-                            let p = AbsPath::new_unchecked(line.abs(&cwd));
-                            let (path, display, tail) = call_transform(f, &p, "")?;
-                            // A missing path omits the entry from the listing.
-                            let Some(path) = path else {
-                                return anyhow::Ok(());
-                            };
-                            let mut item = PathItem::new(path, &cwd);
-                            item.tail = Ok([tail.unwrap_or_default(), display.unwrap_or_default()]);
-                            item
-                        } else {
-                            PathItem::new(line, &cwd)
+                    let item = if let Some(f) = transform.as_ref() {
+                        let p = AbsPath::new_unchecked(line.abs(&cwd));
+                        let (path, display, tail) = call_transform(f, &p, "")?;
+                        // A missing path omits the entry from the listing.
+                        let Some(path) = path else {
+                            return anyhow::Ok(());
                         };
-                        let push = vis.post_fd_filter(&item.path);
+                        let mut item = PathItem::new(path, &cwd);
+                        item.tail = Ok([tail.unwrap_or_default(), display.unwrap_or_default()]);
+                        item
+                    } else {
+                        PathItem::new(line, &cwd)
+                    };
+                    let push = vis.post_fd_filter(&item.path);
 
-                        if push {
-                            sort::store_sort_value(&item, sort::get_sort().order);
-                            injector.push(item)?;
-                        }
-                        anyhow::Ok(())
-                    },
-                    move |count| {
-                        if count == Some(0) {
-                            if abort_empty {
-                                GLOBAL::send_mm(RenderCommand::NoMatch);
-                            } else if toast_on_empty {
-                                TOAST::toast_empty();
-                            }
+                    if push {
+                        sort::store_sort_value(&item, sort::get_sort().order);
+                        injector.push(item)?;
+                    }
+                    anyhow::Ok(())
+                };
+                let on_complete = move |count: Option<usize>| {
+                    if count == Some(0) {
+                        if abort_empty {
+                            GLOBAL::send_mm(RenderCommand::NoMatch);
+                        } else if toast_on_empty {
+                            TOAST::toast_empty();
                         }
+                    }
 
-                        // lowpri: theoretically this should be immune to triggering after pane changes
-                        // as push would Err but we should have a test
-                        if let Some(c) = count
-                            && ({ c == 0 || c < threshold } && {
-                                time_threshold.is_zero() || start_time.elapsed() < time_threshold
-                            })
-                        {
-                            GLOBAL::send_watcher(crate::watcher::WatcherMessage::Switch(
-                                _cwd.inner(),
-                                notify::RecursiveMode::Recursive,
-                            ));
-                        }
+                    // lowpri: theoretically this should be immune to triggering after pane changes
+                    // as push would Err but we should have a test
+                    if let Some(c) = count
+                        && ({ c == 0 || c < threshold } && {
+                            time_threshold.is_zero() || start_time.elapsed() < time_threshold
+                        })
+                    {
+                        GLOBAL::send_watcher(crate::watcher::WatcherMessage::Switch(
+                            _cwd.inner(),
+                            notify::RecursiveMode::Recursive,
+                        ));
+                    }
 
-                        _complete.store(true, Ordering::SeqCst);
-                    },
-                )
+                    _complete.store(true, Ordering::SeqCst);
+                };
+                match stdout {
+                    Some(stdout) => map_reader(stdout, Some('\0'), on_line, on_complete),
+                    None => map_native(args, walk_cwd, on_line, on_complete),
+                }
             }
             Self::Search {
                 cwd,
@@ -282,6 +313,22 @@ impl FsPane {
                 let toast_on_empty = toast_on_empty && is_initial.take();
                 let no_column = is_inverted(&args);
 
+                if use_native_rg(&cfg.rg) {
+                    log::info!(
+                        "searching natively: {}",
+                        display_sh_prog_and_args(prog, &args)
+                    );
+                    return Some(map_native_rg(
+                        args,
+                        cwd.to_path_buf(),
+                        *one_line,
+                        vis,
+                        injector,
+                        toast_on_empty,
+                        complete.clone(),
+                    ));
+                }
+
                 log::info!("spawning: {}", display_sh_prog_and_args(prog, &args));
 
                 let (child, stdout) = Command::new(prog)
@@ -338,6 +385,7 @@ impl FsPane {
                         *context,
                         &cwd,
                         no_column,
+                        vis,
                         injector,
                         toast_on_empty,
                         complete.clone(),
@@ -367,31 +415,41 @@ impl FsPane {
                     )
                 }
             }
-            Self::Files { sort, .. } => {
-                let sort = *sort;
+            Self::Files { sort, range, .. } => {
+                let (sort, range) = (*sort, *range);
+                let cwd = STACK::cwd();
 
                 tokio::spawn(async move {
                     let mut conn = db().get_conn(DbTable::files).await.elog()?;
-                    let entries = GLOBAL::get_db_entries(&mut conn, sort).await?;
+                    let context = cwd.as_deref().map(AsRef::as_ref);
+                    let mut entries = GLOBAL::get_db_entries(&mut conn, sort, context).await?;
+                    if !range.is_unbounded() {
+                        let visited = conn.visited_paths(range).await.elog()?;
+                        entries.retain(|e| visited.contains(&e.path));
+                    }
                     if entries.is_empty() && toast_on_empty {
                         TOAST::toast_empty();
                     }
 
                     for e in entries {
-                        let item = PathItem::new_unchecked(e.path.into());
+                        let item = PathItem::new_entry(e);
                         injector.push(item)?;
                     }
 
                     Ok(())
                 })
             }
-            Self::Folders { sort, .. } => {
-                let sort = *sort;
+            Self::Folders { sort, range, .. } => {
+                let (sort, range) = (*sort, *range);
                 let cwd = STACK::_cwd();
 
                 tokio::spawn(async move {
                     let mut conn = db().get_conn(DbTable::dirs).await.elog()?;
-                    let entries = GLOBAL::get_db_entries(&mut conn, sort).await?;
+                    let mut entries = GLOBAL::get_db_entries(&mut conn, sort, Some(&cwd)).await?;
+                    if !range.is_unbounded() {
+                        let visited = conn.visited_paths(range).await.elog()?;
+                        entries.retain(|e| visited.contains(&e.path));
+                    }
                     if entries.is_empty() && toast_on_empty {
                         TOAST::toast_empty();
                     }
@@ -403,19 +461,68 @@ impl FsPane {
                         if let Some(e) = entries.next()
                             && e.path != cwd
                         {
-                            let item = PathItem::new_unchecked(e.path.into());
+                            let item = PathItem::new_entry(e);
                             injector.push(item)?
                         }
                     }
 
                     for e in entries {
-                        let item = PathItem::new_unchecked(e.path.into());
+                        let item = PathItem::new_entry(e);
                         injector.push(item)?
                     }
 
                     Ok(())
                 })
             }
+            Self::Dupes {
+                paths,
+                vis,
+                complete,
+                ..
+            } => {
+                let paths = paths.clone();
+                let vis = *vis;
+                let complete = complete.clone();
+                complete.store(false, Ordering::SeqCst);
+
+                spawn_blocking(move || {
+                    let groups = find_duplicates(&paths, vis);
+
+                    if groups.is_empty() && toast_on_empty {
+                        TOAST::toast_empty();
+                    }
+
+                    for (i, group) in groups.into_iter().enumerate() {
+                        for path in group.paths {
+                            let item = PathItem::new_dupe(path, i + 1);
+                            sort::store_sort_value(&item, sort::get_sort().order);
+                            injector.push(item)?
+                        }
+                    }
+
+                    complete.store(true, Ordering::Release);
+
+                    anyhow::Ok(())
+                })
+            }
+            Self::Aliases { .. } => tokio::spawn(async move {
+                let mut conn = db().get_conn(DbTable::dirs).await.elog()?;
+                let mut entries = conn.get_aliased_entries().await.elog()?;
+                conn.switch_table(DbTable::files);
+                entries.extend(conn.get_aliased_entries().await.elog()?);
+
+                if entries.is_empty() && toast_on_empty {
+                    TOAST::toast_empty();
+                }
+
+                for e in entries {
+                    let item = PathItem::new_entry(e);
+                    sort::store_sort_value(&item, sort::get_sort().order);
+                    injector.push(item)?;
+                }
+
+                Ok(())
+            }),
             Self::Stash { stash_name, .. } => {
                 let stash_name = stash_name.clone();
                 let kind = cfg
@@ -481,7 +588,7 @@ impl FsPane {
                 let sort = *sort;
                 let ret = tokio::spawn(async move {
                     let mut conn = db().get_conn(DbTable::apps).await.elog()?;
-                    let entries = GLOBAL::get_db_entries(&mut conn, sort).await?;
+                    let entries = GLOBAL::get_db_entries(&mut conn, sort, None).await?;
 
                     if toast_on_empty && entries.is_empty() {
                         TOAST::toast_empty();
@@ -515,6 +622,7 @@ impl FsPane {
                 sort,
                 vis,
                 depth,
+                toggled,
                 complete,
                 ..
             } => {
@@ -522,16 +630,33 @@ impl FsPane {
                 let vis = *vis;
                 let sort = *sort;
                 let depth = *depth;
+                let toggled = self.is_tree().then(|| toggled.clone());
                 let complete = complete.clone();
 
                 spawn_blocking(move || {
                     let mut empty = toast_on_empty;
-
-                    for path in list_dir(&cwd, vis, depth) {
-                        empty = false;
-                        let item = PathItem::new_unchecked(path);
-                        sort::store_sort_value(&item, sort::get_sort().order);
-                        injector.push(item)?
+                    let order = sort::get_sort().order;
+
+                    if let Some(toggled) = toggled {
+                        let entries = list_tree(
+                            &cwd,
+                            vis,
+                            |dir, level| FsPane::is_expanded(depth, &toggled, dir, level),
+                            |paths| sort::sort_level(paths, order),
+                        );
+                        for entry in entries {
+                            empty = false;
+                            let item = PathItem::new_tree_entry(entry);
+                            sort::store_sort_value(&item, order);
+                            injector.push(item)?
+                        }
+                    } else {
+                        for path in list_dir(&cwd, vis) {
+                            empty = false;
+                            let item = PathItem::new_unchecked(path);
+                            sort::store_sort_value(&item, order);
+                            injector.push(item)?
+                        }
                     }
 
                     if empty {
@@ -548,6 +673,20 @@ impl FsPane {
     }
 }
 
+/// Compute the git status of the repository containing `cwd` off the render thread, which only
+/// reads the cache (see [`crate::run::start`]).
+fn prefetch_git_status(cwd: &AbsPath) {
+    if !GLOBAL::cfg().interface.git_status && !meta::enabled(MetaColumn::Git) {
+        return;
+    }
+    let cwd = cwd.inner();
+    spawn_blocking(move || {
+        if git::prefetch(&cwd) {
+            GLOBAL::send_mm(RenderCommand::Redraw);
+        }
+    });
+}
+
 pub fn map_reader<E: matchmaker::SSS + Display>(
     reader: impl Read + matchmaker::SSS,
     delimiter: Option<char>,
@@ -570,12 +709,85 @@ pub fn map_reader<E: matchmaker::SSS + Display>(
     })
 }
 
+/// [`map_reader`] over the results of [`NativeFd`], for when fd is not installed.
+/// `f` is called from the walker's threads.
+pub fn map_native<E: matchmaker::SSS + Display>(
+    args: Vec<OsString>,
+    cwd: PathBuf,
+    f: impl Fn(String) -> Result<(), E> + SSS,
+    complete: impl FnOnce(Option<usize>) + SSS,
+) -> tokio::task::JoinHandle<anyhow::Result<()>> {
+    spawn_blocking(move || {
+        let count = NativeFd::parse(&args)._ebog().map(|native| {
+            native.walk(&cwd, |path| {
+                f(path.to_string_lossy().into_owned())
+                    .map_err(|e| log::debug!("Stopping walk: {e}"))
+                    .is_ok()
+            })
+        });
+
+        let _ = f(String::new());
+
+        complete(count);
+        log::info!("Walk completed");
+        anyhow::Ok(())
+    })
+}
+
+/// [`map_reader_rg`] for [`NativeRg`]: items are built from the search results
+/// directly. Files are searched from the walker's threads.
+pub fn map_native_rg(
+    args: Vec<OsString>,
+    cwd: PathBuf,
+    one_line: bool,
+    vis: Visibility,
+    injector: FsInjector,
+    toast_on_empty: bool,
+    complete: Arc<AtomicBool>,
+) -> tokio::task::JoinHandle<anyhow::Result<()>> {
+    spawn_blocking(move || {
+        let count = NativeRg::parse(&args)._ebog().map(|rg| {
+            let Some(rg) = rg else {
+                return 0;
+            };
+            rg.search(&cwd, |file| {
+                // like rg's heading layout, the per-file items honour the
+                // visibility. The predicates and git filter apply to both layouts.
+                let path = file.path.abs(&cwd);
+                let visible = if one_line {
+                    vis.predicates.matches(&path) && vis.git_matches(&path)
+                } else {
+                    vis.post_fd_filter(&path)
+                };
+                if !visible {
+                    return true;
+                }
+                let mut pushed = true;
+                native_rg_items(&rg, file, one_line, &cwd, |item| {
+                    pushed &= injector.push(item).is_ok();
+                });
+                pushed
+            })
+        });
+
+        if count == Some(0) && toast_on_empty {
+            TOAST::toast_empty();
+        }
+        complete.store(true, Ordering::SeqCst);
+
+        log::info!("Search completed");
+        anyhow::Ok(())
+    })
+}
+
 // todo: lowpri: rg --null adds null byte after filepaths
+#[allow(clippy::too_many_arguments)]
 pub fn map_reader_rg(
     reader: impl Read + matchmaker::SSS + 'static,
     context: [usize; 2],
     cwd: &Path,
     no_column: bool,
+    vis: Visibility,
     injector: FsInjector,
     toast_on_empty: bool,
     complete: Arc<AtomicBool>,
@@ -586,6 +798,27 @@ pub fn map_reader_rg(
             context[0] + context[1] + 1,
         ));
 
+        // the lines of a file are consecutive, so its predicates are checked once
+        let last_file = RefCell::new(None::<(PathBuf, bool)>);
+        let push = |item: PathItem| {
+            if !vis.predicates.is_empty() || vis.git.is_some() {
+                let mut last_file = last_file.borrow_mut();
+                let visible = match &*last_file {
+                    Some((path, visible)) if path == item.path.as_path() => *visible,
+                    _ => {
+                        let visible =
+                            vis.predicates.matches(&item.path) && vis.git_matches(&item.path);
+                        *last_file = Some((item.path.to_path_buf(), visible));
+                        visible
+                    }
+                };
+                if !visible {
+                    return;
+                }
+            }
+            let _ = injector.push(item);
+        };
+
         let mut path_buffer = String::new();
 
         let count = map_reader_lines::<anyhow::Error>(
@@ -599,9 +832,7 @@ pub fn map_reader_rg(
                 if !line.contains('\0') {
                     if line == "--" {
                         let mut buf = buffer.borrow_mut();
-                        flush_rg_buffer(context, &cwd, &mut buf, |item| {
-                            let _ = injector.push(item);
-                        });
+                        flush_rg_buffer(context, &cwd, &mut buf, push);
                         return Ok(());
                     }
                     path_buffer.push_str(&line);
@@ -629,9 +860,7 @@ pub fn map_reader_rg(
                     &cwd,
                     no_column,
                     &mut buf,
-                    |item| {
-                        let _ = injector.push(item);
-                    },
+                    push,
                 )?;
 
                 Ok(())
@@ -646,9 +875,7 @@ pub fn map_reader_rg(
             }
         } else {
             let mut buf = buffer.borrow_mut();
-            flush_rg_buffer(context, &cwd, &mut buf, |item| {
-                let _ = injector.push(item);
-            });
+            flush_rg_buffer(context, &cwd, &mut buf, push);
         }
 
         complete.store(true, Ordering::SeqCst);
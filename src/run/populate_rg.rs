@@ -1,9 +1,13 @@
 use std::{collections::VecDeque, path::Path};
 
 use ansi_to_tui::IntoText;
-use ratatui::text::{Line, Text};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+};
 
 use crate::{
+    find::grep::{FileMatches, GrepLine, NativeRg},
     run::item::PathItem,
     utils::text::{parse_rg_line, scrub_text_styles},
 };
@@ -233,6 +237,100 @@ impl MultilineRgParser {
     }
 }
 
+/// The items of one file searched by [`NativeRg`], shaped like those parsed
+/// from rg's output: in the `one_line` layout, an item per match with its
+/// context window, otherwise a single item whose lines carry their
+/// `LINE:COLUMN:` (context: `LINE-`) prefix.
+pub fn native_rg_items<F>(
+    rg: &NativeRg,
+    file: FileMatches,
+    one_line: bool,
+    cwd: &Path,
+    mut on_item: F,
+) where
+    F: FnMut(PathItem),
+{
+    let no_column = rg.is_inverted();
+
+    if one_line {
+        let [before, after] = rg.context();
+        for m in file.lines.iter().filter(|l| l.is_match) {
+            let mut item = PathItem::new(file.path.clone(), cwd);
+            item.set_loc(m.number as u32, m.column);
+            let window = m.number.saturating_sub(before as u64)..=m.number + after as u64;
+            let lines = file
+                .lines
+                .iter()
+                .filter(|l| window.contains(&l.number))
+                .map(|l| Line::from(native_rg_spans(l, rg.color)));
+            item.tail = Err(Text::from_iter(lines));
+            on_item(item);
+        }
+        return;
+    }
+
+    let mut item = PathItem::new(file.path, cwd);
+    if let Some(m) = file.lines.iter().find(|l| l.is_match) {
+        item.set_loc(m.number as u32, m.column);
+    }
+    let mut lines = vec![];
+    let mut last = None;
+    for l in &file.lines {
+        if rg.context_separator && last.is_some_and(|n| l.number > n + 1) {
+            lines.push(Line::from("--"));
+        }
+        last = Some(l.number);
+
+        let number = Span::styled(
+            l.number.to_string(),
+            if rg.color {
+                Style::new().fg(Color::Green)
+            } else {
+                Style::new()
+            },
+        );
+        let loc = match (l.is_match, no_column) {
+            (true, false) => format!(":{}:", l.column),
+            (true, true) => ":".into(),
+            (false, _) => "-".into(),
+        };
+        let mut spans = vec![number, Span::raw(loc)];
+        spans.extend(native_rg_spans(l, rg.color));
+        lines.push(Line::from(spans));
+    }
+    item.tail = Err(Text::from(lines));
+    on_item(item);
+}
+
+/// The text of a line, with its matches highlighted as rg colors them.
+fn native_rg_spans(
+    line: &GrepLine,
+    color: bool,
+) -> Vec<Span<'static>> {
+    if !color || line.matches.is_empty() {
+        return vec![Span::raw(line.text.clone())];
+    }
+    let highlight = Style::new().fg(Color::Red).add_modifier(Modifier::BOLD);
+    let mut spans = vec![];
+    let mut last = 0;
+    for m in &line.matches {
+        let (Some(before), Some(matched)) =
+            (line.text.get(last..m.start), line.text.get(m.clone()))
+        else {
+            continue;
+        };
+        if !before.is_empty() {
+            spans.push(Span::raw(before.to_string()));
+        }
+        spans.push(Span::styled(matched.to_string(), highlight));
+        last = m.end;
+    }
+    if let Some(rest) = line.text.get(last..).filter(|r| !r.is_empty()) {
+        spans.push(Span::raw(rest.to_string()));
+    }
+    spans
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -407,6 +505,40 @@ mod tests {
         assert!(tail2.contains("pub fn make_previewer("));
     }
 
+    #[test]
+    fn test_native_rg_items() {
+        let args: Vec<std::ffi::OsString> = ["--color=ansi", "-e", "b", "-C", "1"]
+            .iter()
+            .map(Into::into)
+            .collect();
+        let rg = NativeRg::parse(&args).unwrap().unwrap();
+        let line = |number, is_match, text: &str| GrepLine {
+            number,
+            column: if is_match { 1 } else { 0 },
+            is_match,
+            text: text.into(),
+            matches: std::iter::once(0..1).filter(|_| is_match).collect(),
+        };
+        let file = FileMatches {
+            path: "foo.rs".into(),
+            lines: vec![line(1, false, "a"), line(2, true, "b"), line(5, true, "b!")],
+        };
+        let cwd = PathBuf::from("/workspace");
+
+        let mut items = vec![];
+        native_rg_items(&rg, file.clone(), true, &cwd, |item| items.push(item));
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].loc(), (2, 1));
+        assert_eq!(items[0].tail_text().to_string(), "a\nb");
+        assert_eq!(items[1].tail_text().to_string(), "b!");
+
+        let mut items = vec![];
+        native_rg_items(&rg, file, false, &cwd, |item| items.push(item));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].path.inner(), PathBuf::from("/workspace/foo.rs"));
+        assert_eq!(items[0].tail_text().to_string(), "1-a\n2:1:b\n--\n5:1:b!");
+    }
+
     #[test]
     fn test_multiline_filepath_oneline_mode() {
         let cwd = PathBuf::from("/workspace");
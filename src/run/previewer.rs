@@ -1,18 +1,80 @@
 use crate::{
     aliases::MMState,
-    run::{FsMatchmaker, item::PathItem, state::STACK},
+    lessfilter::Preset,
+    run::{
+        FsMatchmaker, commander,
+        item::PathItem,
+        state::{STACK, STORE},
+    },
 };
+use fist_types::When;
 use cba::env_vars;
 use log::warn;
 use matchmaker::{
     AttachmentFormatter,
-    config::{HelpDisplayConfig, PreviewerConfig},
+    config::{HelpDisplayConfig, PreviewLayout, PreviewerConfig, Side},
     message::Event,
     nucleo::Text,
     preview::previewer::{PreviewMessage, Previewer},
+    ui::PreviewUI,
     use_formatter,
+    utils::Percentage,
 };
 
+/// Where [`dock_preview`] moves the preview.
+#[derive(Clone, Copy)]
+pub struct Dock {
+    pub side: Side,
+    /// Share the width evenly with the results.
+    pub even: bool,
+}
+
+/// The layout moved by [`dock_preview`], and how it was before.
+#[derive(Debug)]
+struct Undocked(usize, PreviewLayout);
+
+/// Moves the current preview layout to hold the other panel (see [`crate::run::commander`]), or
+/// back to how it was configured when `dock` is `None`.
+pub fn dock_preview(
+    preview: &mut PreviewUI,
+    dock: Option<Dock>,
+) {
+    let current = preview.setting().and_then(|s| {
+        preview
+            .config
+            .layout
+            .iter()
+            .position(|l| std::ptr::eq(l, s))
+    });
+    let half = Percentage::new(50);
+    if let Some(Dock { side, even }) = dock
+        && let Some(idx) = current
+        && let layout = &preview.config.layout[idx].layout
+        && layout.side == side
+        && (!even || layout.percentage == half)
+    {
+        return;
+    }
+
+    if let Some(Undocked(idx, layout)) = STORE::take()
+        && let Some(setting) = preview.config.layout.get_mut(idx)
+    {
+        setting.layout = layout;
+        preview.reinit();
+    }
+    if let Some(Dock { side, even }) = dock
+        && let Some(idx) = current
+    {
+        let layout = &mut preview.config.layout[idx].layout;
+        STORE::set(Undocked(idx, layout.clone()));
+        layout.side = side;
+        if even {
+            layout.percentage = half;
+        }
+        preview.reinit();
+    }
+}
+
 /// Causes the program to display a preview of the active result.
 /// The Previewer can be connected to [`Matchmaker`] using [`PickOptions::previewer`]
 pub fn make_previewer(
@@ -37,6 +99,36 @@ pub fn make_previewer(
                 return;
             }
 
+            let default_payload =
+                *state.preview_payload() == Preset::Preview.to_command_string(When::Auto);
+
+            // the main preview is the other panel of the dual-pane mode, on its own side
+            let panel = STACK::with_panel(|_, right| right);
+            let height = state.previewer_area().map_or(0, |r| r.height);
+            let dock = panel
+                .filter(|_| state.preview_visible() && default_payload)
+                .map(|right| Dock {
+                    side: if right { Side::Left } else { Side::Right },
+                    even: true,
+                });
+            if let Some(p) = state.preview_ui.as_mut() {
+                dock_preview(p, dock);
+            }
+
+            if dock.is_some() {
+                let tx = preview_tx.clone();
+                let send = move |text| {
+                    if tx.send(PreviewMessage::Set(text)).is_err() {
+                        warn!("Failed to send to preview: panel")
+                    }
+                };
+                let config = &state.picker_ui.results.config;
+                let prefixes = [config.default_prefix.clone(), config.multi_prefix.clone()];
+                commander::request_panel_listing(height, prefixes, send);
+                state.preview_set_payload = None;
+                return;
+            }
+
             if state.preview_visible()
                 && let m = state.preview_payload().clone()
                 && let cmd = use_formatter(&formatter, state, &m, None)
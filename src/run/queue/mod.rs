@@ -10,7 +10,7 @@ mod execute;
 mod status;
 pub use status::*;
 
-use std::{ffi::OsString, path::PathBuf, sync::Mutex};
+use std::{collections::HashSet, ffi::OsString, ops::Range, path::PathBuf, sync::Mutex};
 
 use cba::bath::{PathExt, auto_dest_for_src};
 
@@ -224,16 +224,25 @@ impl QUEUE {
     /// Enqueue `paths` into the shared queue under `kind`. Builtin kinds
     /// add one row per path, replacing a pending row with the same source
     /// and kind (moved to the tail); custom menu kinds add one multi-path
-    /// row.
-    pub fn enqueue(kind: QueueKind, paths: Vec<AbsPath>) {
+    /// row. Duplicate paths are enqueued once. Returns the indices of the new
+    /// rows.
+    pub fn enqueue(kind: QueueKind, mut paths: Vec<AbsPath>) -> Range<usize> {
         debug_assert!(
             is_valid_queue_kind(&kind),
             "enqueue kinds must parse as an ordinary queue kind, got {kind:?}"
         );
+        // a later duplicate would replace the row just pushed for the first one
+        let mut seen = HashSet::new();
+        paths.retain(|p| seen.insert(p.clone()));
         if paths.is_empty() {
-            return;
+            return 0..0;
         }
         let mut state = QUEUE_STATE.lock().unwrap();
+        let added = if BUILTIN_KINDS.contains(&kind.as_str()) {
+            paths.len()
+        } else {
+            1
+        };
         if BUILTIN_KINDS.contains(&kind.as_str()) {
             for path in paths {
                 if let Some(i) = state.shared.iter().position(|s| {
@@ -254,6 +263,7 @@ impl QUEUE {
                 dst: Default::default(),
             });
         }
+        state.shared.len() - added..state.shared.len()
     }
 
     // ------------- view ops --------------
@@ -601,6 +611,18 @@ mod selector_tests {
         assert_eq!(QueueSelector::Kind("zip".into()).as_kind(), Some("zip"));
     }
 
+    #[test]
+    fn test_enqueue_duplicates() {
+        let a = AbsPath::new_unchecked("/test_enqueue_duplicates/a");
+        let b = AbsPath::new_unchecked("/test_enqueue_duplicates/b");
+        let rows = QUEUE::enqueue("copy".into(), vec![a.clone(), b.clone(), a.clone()]);
+        assert_eq!(rows.len(), 2);
+
+        let state = QUEUE_STATE.lock().unwrap();
+        let srcs: Vec<_> = state.shared[rows].iter().map(|r| r.src.clone()).collect();
+        assert_eq!(srcs, vec![vec![a], vec![b]]);
+    }
+
     #[test]
     fn test_is_valid_queue_kind() {
         assert!(!is_valid_queue_kind(""));
@@ -1,4 +1,7 @@
-use std::process::{Command, Stdio};
+use std::{
+    process::{Command, Stdio},
+    time::Instant,
+};
 
 use cba::broc::{CommandExt, EnvVars, tty_or_inherit};
 use log::{info, warn};
@@ -7,9 +10,10 @@ use crate::{
     abspath::AbsPath,
     aliases::MMState,
     cli::paths::actions_dir,
+    db::{CommandKind, CommandRecord},
     lua::{execute, load_script},
     pager,
-    run::state::{ExecuteHandlerShouldProcessParent, MENU_ACTIONS, STACK, STORE},
+    run::state::{ExecuteHandlerShouldProcessParent, GLOBAL, MENU_ACTIONS, STACK, STORE},
     utils::{command::maybe_tty, formatter::format_path},
 };
 
@@ -48,6 +52,18 @@ impl ExecutionMode {
             _ => None,
         }
     }
+
+    /// How a run in this mode is recorded in the command history, if at all.
+    pub fn command_kind(self) -> Option<CommandKind> {
+        match self {
+            Self::Normal => Some(CommandKind::Execute),
+            Self::Paged => Some(CommandKind::Paged),
+            Self::Tty => Some(CommandKind::Tty),
+            Self::MenuAction | Self::LuaCommandPaged => Some(CommandKind::Menu),
+            _ => None,
+        }
+    }
+
     pub fn from_discriminant_for_silent(d: u8) -> Option<Self> {
         match d {
             0 => Some(Self::Copy),
@@ -77,25 +93,32 @@ pub(super) fn menu_lua_command(mode: ExecutionMode, payload: &str) -> Option<Str
 /// targeted paths table and an empty destination. The navigation directory
 /// is passed as the third argument when present. `set_progress` has no target
 /// here and is a silent no-op. The command runs in the process cwd — scripts
-/// are responsible for `cd`.
-pub(super) fn run_menu_lua(command: &str, paths: &[AbsPath], nav_cwd: Option<&AbsPath>) {
+/// are responsible for `cd`. Returns whether the command ran without error.
+pub(super) fn run_menu_lua(command: &str, paths: &[AbsPath], nav_cwd: Option<&AbsPath>) -> bool {
     let Some(source) = load_script(command, Some(actions_dir())) else {
         log::error!("Failed to load menu action lua command: {command}");
-        return;
+        return false;
     };
     if let Err(e) = execute(&source, paths, "", nav_cwd, None) {
         log::error!("Menu action lua error: {e}");
+        return false;
     }
+    true
 }
 
 /// Run a menu action's lua command paged: stdout (from `os.execute`,
 /// `io.popen`, prints, …) is piped into the pager while the script runs. The
 /// command runs in the process cwd — scripts are responsible for `cd`.
-/// `set_progress` has no target here and is a silent no-op.
-pub(super) fn run_menu_lua_paged(command: &str, paths: &[AbsPath], nav_cwd: Option<&AbsPath>) {
+/// `set_progress` has no target here and is a silent no-op. Returns whether
+/// the command ran without error.
+pub(super) fn run_menu_lua_paged(
+    command: &str,
+    paths: &[AbsPath],
+    nav_cwd: Option<&AbsPath>,
+) -> bool {
     let Some(source) = load_script(command, Some(actions_dir())) else {
         log::error!("Failed to load menu action lua command: {command}");
-        return;
+        return false;
     };
 
     // Bat opts are read on the calling thread before the feeder starts, so the
@@ -109,8 +132,7 @@ pub(super) fn run_menu_lua_paged(command: &str, paths: &[AbsPath], nav_cwd: Opti
             Ok(p) => p,
             Err(e) => {
                 warn!("Failed to create stdout pipe for paged lua: {e}; running unpaged");
-                run_menu_lua(command, paths, nav_cwd);
-                return;
+                return run_menu_lua(command, paths, nav_cwd);
             }
         };
         // The pipe write end is dup'd onto fd 1 so the script's stdout lands in
@@ -121,8 +143,7 @@ pub(super) fn run_menu_lua_paged(command: &str, paths: &[AbsPath], nav_cwd: Opti
             Ok(r) => r,
             Err(e) => {
                 warn!("Failed to redirect stdout for paged lua: {e}; running unpaged");
-                run_menu_lua(command, paths, nav_cwd);
-                return;
+                return run_menu_lua(command, paths, nav_cwd);
             }
         };
 
@@ -131,7 +152,7 @@ pub(super) fn run_menu_lua_paged(command: &str, paths: &[AbsPath], nav_cwd: Opti
         });
 
         let result = execute(&source, paths, "", nav_cwd, None);
-        if let Err(e) = result {
+        if let Err(e) = &result {
             log::error!("Menu action lua error: {e}");
         }
 
@@ -139,11 +160,12 @@ pub(super) fn run_menu_lua_paged(command: &str, paths: &[AbsPath], nav_cwd: Opti
         drop(redirect);
         drop(writer);
         let _ = feeder.join();
+        result.is_ok()
     }
 
     #[cfg(not(unix))]
     {
-        run_menu_lua(command, paths, nav_cwd);
+        run_menu_lua(command, paths, nav_cwd)
     }
 }
 
@@ -237,41 +259,58 @@ pub(super) fn build_exec_command(
     Some(builder)
 }
 
-/// Wait for a spawned child according to its mode and report whether the
-/// execution counts as successful (used for the db bump). The silent modes do
-/// not wait: a successful spawn counts, child completion is unknown.
+/// Wait for a spawned child according to its mode and report its exit status:
+/// `Some(0)` counts as successful (used for the db bump). Paged commands report
+/// 0 or 1 from the pager. The silent modes do not wait: their status is unknown.
 pub(super) fn wait_exec(
     mode: ExecutionMode,
     cmd: &str,
     mut child: std::process::Child,
     bat: Option<Vec<String>>,
-) -> bool {
+) -> Option<i32> {
     match mode {
         ExecutionMode::Paged => match pager::page_child(child, bat) {
             Ok(ok) => {
                 info!("Command [{cmd}] paged (success={ok})");
-                ok
+                Some(if ok { 0 } else { 1 })
             }
             Err(e) => {
                 info!("Failed to page command [{cmd}]: {e}");
-                false
+                None
             }
         },
-        ExecutionMode::Detached | ExecutionMode::Silent => false,
+        ExecutionMode::Detached | ExecutionMode::Silent => None,
         ExecutionMode::Normal | ExecutionMode::Tty => match child.wait() {
             Ok(i) => {
                 info!("Command [{cmd}] exited with {i}");
-                i.success()
+                i.code()
             }
             Err(e) => {
                 info!("Failed to wait on command [{cmd}]: {e}");
-                false
+                None
             }
         },
-        _ => true,
+        _ => Some(0),
     }
 }
 
+/// Append a finished run to the command history. `template` is the payload as
+/// invoked (the menu action key for [`CommandKind::Menu`]), `command` what ran.
+pub(super) fn record_command(
+    kind: CommandKind,
+    template: &str,
+    command: String,
+    cwd: Option<AbsPath>,
+    status: Option<i32>,
+    started: Instant,
+) {
+    let mut record = CommandRecord::new(template.to_string(), command, kind);
+    record.cwd = cwd;
+    record.status = status;
+    record.duration_ms = started.elapsed().as_millis() as i64;
+    GLOBAL::db().record_command(record);
+}
+
 /// Resolve the execution target: the cwd when the cursor is disabled,
 /// otherwise the current item. Honors [`ExecuteHandlerShouldProcessParent`]
 /// and returns `None` instead of panicking when there is no target or parent.
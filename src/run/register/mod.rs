@@ -2,7 +2,7 @@ mod execute;
 use execute::*;
 pub use execute::{ExecutionMode, resolve_target};
 
-use std::{ffi::OsString, process::Stdio};
+use std::{ffi::OsString, process::Stdio, time::Instant};
 
 use cba::{
     bait::ResultExt,
@@ -20,6 +20,7 @@ use crate::run::state::GLOBAL::db;
 use crate::{
     aliases::MMState,
     clipboard,
+    db::CommandKind,
     run::{
         FsMatchmaker,
         ahandlers::fs_reload,
@@ -116,19 +117,20 @@ impl FsMatchmaker {
     /// Handles `Interrupt::Execute`: the fist execution modes (`Execute`,
     /// `ExecPaged`, `ExecTTY`) and bare templates from e.g. `FsAction::Advance`
     /// (no discriminant = [`ExecutionMode::Normal`]). Waits for the child.
+    /// Runs of the fist modes are recorded in the command history; bare
+    /// templates are not.
     pub fn register_execute_handler(&mut self) {
         self.register_interrupt_handler(Interrupt::Execute, move |state| {
             if state.payload().is_empty() {
                 return;
             }
-            let mode = match state
+            let mode = state
                 .discriminant_payload
                 .take()
-                .and_then(ExecutionMode::from_discriminant_for_execute)
-            {
-                Some(x) => x,
-                _ => ExecutionMode::Normal,
-            };
+                .and_then(ExecutionMode::from_discriminant_for_execute);
+            let kind = mode.and_then(ExecutionMode::command_kind);
+            let mode = mode.unwrap_or(ExecutionMode::Normal);
+            let started = Instant::now();
             // menu action execution (discriminants 7/8/9): the payload is not a
             // command template — it is the action key (7/9) or the command
             // itself (8). The targeted paths were stashed at menu activation.
@@ -137,12 +139,16 @@ impl FsMatchmaker {
                     warn!("Menu action executed without targeted paths");
                     return;
                 };
-                if mode == ExecutionMode::LuaCommandPaged {
-                    let nav_cwd = STACK::nav_cwd();
-                    run_menu_lua_paged(&lua_cmd, &paths, nav_cwd.as_ref());
+                let nav_cwd = STACK::nav_cwd();
+                let ok = if mode == ExecutionMode::LuaCommandPaged {
+                    run_menu_lua_paged(&lua_cmd, &paths, nav_cwd.as_ref())
                 } else {
-                    let nav_cwd = STACK::nav_cwd();
-                    run_menu_lua(&lua_cmd, &paths, nav_cwd.as_ref());
+                    run_menu_lua(&lua_cmd, &paths, nav_cwd.as_ref())
+                };
+                if let Some(kind) = kind {
+                    let status = Some(if ok { 0 } else { 1 });
+                    let cwd = STACK::cwd();
+                    record_command(kind, state.payload(), lua_cmd, cwd, status, started);
                 }
                 return;
             }
@@ -171,7 +177,12 @@ impl FsMatchmaker {
             let Some(child) = builder._spawn() else {
                 return;
             };
-            if wait_exec(mode, &cmd, child, bat) {
+            let status = wait_exec(mode, &cmd, child, bat);
+            if let Some(kind) = kind {
+                let cwd = STACK::cwd();
+                record_command(kind, state.payload(), cmd, cwd, status, started);
+            }
+            if status == Some(0) {
                 db().bump_path(path.is_dir(), path);
             }
         });
@@ -200,8 +211,14 @@ impl FsMatchmaker {
                     return;
                 };
                 let nav_cwd = STACK::nav_cwd();
+                let record = (mode == ExecutionMode::MenuAction)
+                    .then(|| (state.payload().clone(), STACK::cwd(), Instant::now()));
                 TASKS::spawn_blocking("menu lua", move || {
-                    run_menu_lua(&lua_cmd, &paths, nav_cwd.as_ref())
+                    let ok = run_menu_lua(&lua_cmd, &paths, nav_cwd.as_ref());
+                    if let Some((key, cwd, started)) = record {
+                        let status = Some(if ok { 0 } else { 1 });
+                        record_command(CommandKind::Menu, &key, lua_cmd, cwd, status, started);
+                    }
                 });
                 return;
             }
@@ -226,7 +243,7 @@ impl FsMatchmaker {
                             clipboard::copy_text(contents, true);
                         };
                     } else if let Some(child) = c._spawn() {
-                        if wait_exec(mode, &cmd, child, None) {
+                        if wait_exec(mode, &cmd, child, None) == Some(0) {
                             db().bump_path(path.is_dir(), path);
                         }
                     };
@@ -0,0 +1,276 @@
+//! Replace mode of the Search pane: the replacement preview rendered in
+//! place of the matched lines, and applying the selected matches to their
+//! files with a backup in the operation journal.
+
+use std::collections::BTreeSet;
+
+use indexmap::IndexMap;
+use matchmaker::render::MMState;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+};
+
+use crate::{
+    abspath::AbsPath,
+    db::{DbTable, JournalOp},
+    errors::DbError,
+    fs::replace::{Replacer, Segment, write_atomic},
+    run::{
+        FsPane,
+        item::PathItem,
+        state::{GLOBAL, STACK, STORE, TOAST, ToastStyle},
+    },
+};
+
+/// Set while the Search pane previews a replacement ([`crate::run::FsAction::Replace`]).
+#[derive(Debug, Clone)]
+pub struct ReplacePreview(pub Replacer);
+
+/// The current Search pane's patterns compiled with `replacement`, or `None`
+/// outside of a Search pane.
+pub fn replacer_for(pane: &FsPane, replacement: String) -> Option<Result<Replacer, String>> {
+    match pane {
+        FsPane::Search {
+            patterns,
+            fixed_strings,
+            case,
+            ..
+        } => Some(
+            Replacer::new(patterns, *fixed_strings, *case, replacement).map_err(|e| e.to_string()),
+        ),
+        _ => None,
+    }
+}
+
+/// Preview `replacement` in the current Search pane and select all matches.
+pub fn set_preview(state: &mut MMState<'_, PathItem, ()>, replacement: String) {
+    match STACK::with_current(|p| replacer_for(p, replacement)) {
+        Some(Ok(replacer)) => {
+            STORE::set(ReplacePreview(replacer));
+            select_all(state);
+        }
+        Some(Err(e)) => TOAST::notice(ToastStyle::Error, e),
+        None => return,
+    }
+    state.picker_ui.results.set_dirty();
+}
+
+/// Recompile the previewed replacement against the pane's current patterns,
+/// ending the preview if they no longer compile.
+pub fn refresh_preview(pane: &FsPane) {
+    let Some(replacement) = STORE::with(|p: &ReplacePreview| p.0.replacement.clone()) else {
+        return;
+    };
+    match replacer_for(pane, replacement) {
+        Some(Ok(replacer)) => STORE::set(ReplacePreview(replacer)),
+        _ => {
+            STORE::take::<ReplacePreview>();
+        }
+    }
+}
+
+/// The tail of a Search pane item with the replacement applied to its match
+/// lines: each match is shown struck out, followed by its replacement.
+/// `one_line`: the Search pane's layout, see [`split_loc`].
+pub fn preview_tail(
+    replacer: &Replacer,
+    item: &PathItem,
+    one_line: bool,
+) -> Option<Text<'static>> {
+    let Err(text) = &item.tail else {
+        return None;
+    };
+    let mut changed = false;
+    let lines = text.lines.iter().map(|line| {
+        let content: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        let (loc, rest) = if one_line {
+            ("", content.as_str())
+        } else {
+            split_loc(&content)
+        };
+        let Some(segments) = replacer.diff_line(rest) else {
+            return line.clone();
+        };
+        changed = true;
+        let old_style = Style::new().fg(Color::Red).add_modifier(Modifier::CROSSED_OUT);
+        let mut spans = vec![Span::raw(loc.to_string())];
+        for segment in segments {
+            match segment {
+                Segment::Keep(s) => spans.push(Span::raw(s.to_string())),
+                Segment::Replace(old, new) => {
+                    spans.push(Span::styled(old.to_string(), old_style));
+                    spans.push(Span::styled(new, Style::new().fg(Color::Green)));
+                }
+            }
+        }
+        Line::from(spans)
+    });
+    let text = Text::from(lines.collect::<Vec<_>>());
+    changed.then_some(text)
+}
+
+/// Split the `LINE:COLUMN:` prefix of a match line in the multi-line layout.
+/// Lines without one (context lines are `LINE-`) are returned whole.
+fn split_loc(line: &str) -> (&str, &str) {
+    let mut end = 0;
+    for _ in 0..2 {
+        let rest = &line[end..];
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 || rest.as_bytes().get(digits) != Some(&b':') {
+            break;
+        }
+        end += digits + 1;
+    }
+    line.split_at(end)
+}
+
+/// Select every matched item of the pane, for the user to deselect the matches to keep.
+pub fn select_all(state: &mut MMState<'_, PathItem, ()>) {
+    let indices: Vec<u32> = state.picker_ui.worker.matched_indices().collect();
+    state.picker_ui.selector.clear();
+    state.picker_ui.selector.extend(indices);
+}
+
+/// The lines to replace in each file: the match lines of the selected items,
+/// which in the multi-line layout are read from the `LINE:COLUMN:` prefixes.
+pub fn selected_edits(
+    state: &MMState<'_, PathItem, ()>,
+    one_line: bool,
+) -> IndexMap<AbsPath, BTreeSet<u32>> {
+    let mut edits: IndexMap<AbsPath, BTreeSet<u32>> = IndexMap::new();
+    let items = state.map_selected_to_vec(|_, item| {
+        let lines = if one_line {
+            BTreeSet::from([item.loc().0])
+        } else {
+            match_lines(item)
+        };
+        (item.path.clone(), lines)
+    });
+    for (path, lines) in items {
+        edits
+            .entry(path)
+            .or_default()
+            .extend(lines.into_iter().filter(|&l| l > 0));
+    }
+    edits.retain(|_, lines| !lines.is_empty());
+    edits
+}
+
+/// The match lines of a multi-line layout item; context lines have no
+/// `LINE:COLUMN:` prefix.
+fn match_lines(item: &PathItem) -> BTreeSet<u32> {
+    let Err(text) = &item.tail else {
+        return BTreeSet::new();
+    };
+    text.lines
+        .iter()
+        .filter_map(|line| {
+            let content: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+            let (loc, _) = split_loc(&content);
+            loc.split(':').next()?.parse().ok()
+        })
+        .collect()
+}
+
+/// The outcome of [`apply`].
+#[derive(Debug, Default)]
+pub struct Applied {
+    pub files: usize,
+    pub lines: usize,
+    /// Files which could not be read as text, or were not written.
+    pub failed: usize,
+}
+
+/// Apply `replacer` to the `edits`, backing up the original contents in the
+/// journal first: nothing is written unless the backup succeeds.
+pub async fn apply(
+    replacer: &Replacer,
+    edits: IndexMap<AbsPath, BTreeSet<u32>>,
+) -> Result<Applied, DbError> {
+    let mut ret = Applied::default();
+    let mut originals = vec![];
+    let mut updates = vec![];
+    for (path, lines) in edits {
+        let Ok(content) = std::fs::read(&path) else {
+            ret.failed += 1;
+            continue;
+        };
+        let Ok(text) = std::str::from_utf8(&content) else {
+            ret.failed += 1;
+            continue;
+        };
+        if let Some((new, changed)) = replacer.apply(text, Some(&lines)) {
+            ret.lines += changed;
+            updates.push((path.clone(), new));
+            originals.push((path, content));
+        }
+    }
+    if updates.is_empty() {
+        return Ok(ret);
+    }
+
+    let mut conn = GLOBAL::db().get_conn(DbTable::files).await?;
+    conn.journal_backup("replace", &originals, GLOBAL::cfg().fs.journal_max)
+        .await?;
+
+    for (path, new) in updates {
+        match write_atomic(&path, new.as_bytes()) {
+            Ok(()) => ret.files += 1,
+            Err(e) => {
+                log::error!("Failed to write {}: {e}", path.to_string_lossy());
+                ret.failed += 1;
+            }
+        }
+    }
+    Ok(ret)
+}
+
+/// Restore the files of the latest journaled operation. The operation leaves
+/// the journal once all its files are restored. Returns the operation and the
+/// number of files which failed to restore.
+pub async fn undo() -> Result<Option<(JournalOp, usize)>, DbError> {
+    let mut conn = GLOBAL::db().get_conn(DbTable::files).await?;
+    let Some(op) = conn.journal_last().await? else {
+        return Ok(None);
+    };
+
+    let mut failed = 0;
+    for (path, content) in &op.files {
+        if let Err(e) = write_atomic(path, content) {
+            log::error!("Failed to restore {}: {e}", path.to_string_lossy());
+            failed += 1;
+        }
+    }
+    if failed == 0 {
+        conn.journal_drop(op.op).await?;
+    }
+    Ok(Some((op, failed)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_loc() {
+        assert_eq!(split_loc("12:4:let x"), ("12:4:", "let x"));
+        assert_eq!(split_loc("12:let x"), ("12:", "let x"));
+        assert_eq!(split_loc("11-context"), ("", "11-context"));
+        assert_eq!(split_loc("let x"), ("", "let x"));
+    }
+
+    #[test]
+    fn test_match_lines() {
+        let mut item = PathItem::new(AbsPath::new("/a.rs"), std::path::Path::new("/"));
+        item.tail = Err(Text::from(vec![
+            Line::from("11-context"),
+            Line::from(vec![Span::raw("12"), Span::raw(":4:"), Span::raw("let x")]),
+            Line::from("30:1:let y"),
+        ]));
+        assert_eq!(match_lines(&item), BTreeSet::from([12, 30]));
+
+        item.tail = Ok(Default::default());
+        assert!(match_lines(&item).is_empty());
+    }
+}
@@ -2,14 +2,30 @@
 //! paths before a worker restart invalidates their nucleo indices, then
 //! rehydrated against the fresh item store once the pane finishes
 //! populating. The hashes survive the wipe period between the two.
+//!
+//! Also the bulk selections ([`crate::run::FsAction::SelectMatching`] and
+//! friends), which go over the full item store rather than the matched rows,
+//! and the visual mode ([`crate::run::FsAction::Visual`]).
 use std::{
     collections::HashMap,
+    ffi::OsStr,
     hash::{Hash, Hasher},
+    path::{Path, PathBuf},
 };
 
 use ahash::AHasher;
+use globset::{GlobBuilder, GlobMatcher};
+use indexmap::IndexSet;
 
-use crate::{abspath::AbsPath, run::item::PathItem};
+use crate::{
+    abspath::AbsPath,
+    aliases::MMState,
+    run::{
+        ahandlers::refresh_prompt,
+        item::PathItem,
+        state::{STORE, TOAST, VisualAnchor},
+    },
+};
 
 /// Fast, deterministic path hash. The seed is fixed across threads within
 /// the process (hashes are never persisted, so cross-process/cross-version
@@ -48,3 +64,219 @@ pub fn rehydrate<'a>(
         .filter_map(|hash| reverse.get(hash).copied())
         .collect()
 }
+
+/// A glob matching file names, or paths when it contains a separator: absolute patterns match
+/// the whole path, and relative ones the path relative to the base they are given.
+pub struct PathGlob {
+    matcher: GlobMatcher,
+    /// `None` when matching file names.
+    base: Option<PathBuf>,
+}
+
+impl PathGlob {
+    pub fn new(
+        pattern: &str,
+        base: &Path,
+    ) -> Result<Self, globset::Error> {
+        let matcher = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()?
+            .compile_matcher();
+        let base = if !pattern.contains(std::path::MAIN_SEPARATOR) {
+            None
+        } else if Path::new(pattern).is_absolute() {
+            Some(PathBuf::new())
+        } else {
+            Some(base.to_path_buf())
+        };
+        Ok(Self { matcher, base })
+    }
+
+    pub fn is_match(
+        &self,
+        path: &Path,
+    ) -> bool {
+        match &self.base {
+            Some(base) => path
+                .strip_prefix(base)
+                .is_ok_and(|rel| self.matcher.is_match(rel)),
+            None => path
+                .file_name()
+                .is_some_and(|name| self.matcher.is_match(name)),
+        }
+    }
+}
+
+/// Whether `path` is a file with the extension `ext` (`None`: without one). Directories never
+/// match.
+pub fn has_extension(
+    path: &Path,
+    ext: Option<&OsStr>,
+) -> bool {
+    !path.is_dir() && path.extension() == ext
+}
+
+/// Add every item of the store satisfying `pred` to the selection, and report how many were
+/// selected.
+pub fn select_where(
+    state: &mut MMState<'_>,
+    pred: impl Fn(&PathItem) -> bool,
+) {
+    let items = state.picker_ui.worker.nucleo.items();
+    let indices: Vec<u32> = items
+        .iter()
+        .filter(|(_, item)| pred(item))
+        .map(|(i, _)| i)
+        .collect();
+    TOAST::msg(format!("Selected {} items", indices.len()), true);
+    state.picker_ui.selector.extend(indices);
+}
+
+/// Select the items of the store which are not selected, and deselect the others.
+pub fn invert(state: &mut MMState<'_>) {
+    let picker = &mut *state.picker_ui;
+    let items = picker.worker.nucleo.items();
+    let indices: Vec<u32> = items
+        .iter()
+        .map(|(i, _)| i)
+        .filter(|i| !picker.selector.contains(i))
+        .collect();
+    picker.selector.clear();
+    picker.selector.extend(indices);
+}
+
+/// Enter the visual mode at the cursor, or leave it, keeping the selection.
+pub fn toggle_visual(state: &mut MMState<'_>) {
+    if STORE::take::<VisualAnchor>().is_none() {
+        if state.picker_ui.results.cursor_disabled() || state.current_raw().is_none() {
+            return;
+        }
+        STORE::set(VisualAnchor {
+            anchor: state.picker_ui.results.index(),
+            base: state.picker_ui.selector.iter().copied().collect(),
+            ..Default::default()
+        });
+        extend_visual(state);
+    }
+    refresh_prompt(state);
+}
+
+/// In the visual mode, select the items from the anchor to the cursor on top of the selection
+/// the mode was entered with. Items toggled since the last extension keep their toggle.
+pub fn extend_visual(state: &mut MMState<'_>) {
+    let Some(mut visual) = STORE::get::<VisualAnchor>() else {
+        return;
+    };
+    let picker = &mut *state.picker_ui;
+    let index = picker.results.index();
+    let range: Vec<u32> = (visual.anchor.min(index)..=visual.anchor.max(index))
+        .filter_map(|i| picker.worker.get_nth_indexed(i).map(|(id, _)| id))
+        .collect();
+    let selected: IndexSet<u32> = picker.selector.iter().copied().collect();
+    let selection = visual_selection(&mut visual, &selected, range);
+    picker.selector.clear();
+    picker.selector.extend(selection);
+    STORE::set(visual);
+}
+
+/// The selection of the visual mode over `range`, given the `selected` items: the changes to
+/// the selection since it was last applied are recorded in `visual` as toggles.
+fn visual_selection(
+    visual: &mut VisualAnchor,
+    selected: &IndexSet<u32>,
+    range: Vec<u32>,
+) -> IndexSet<u32> {
+    // toggled on
+    for id in selected {
+        if !visual.applied.contains(id) {
+            visual.excluded.remove(id);
+            visual.base.insert(*id);
+        }
+    }
+    // toggled off
+    visual
+        .base
+        .retain(|id| selected.contains(id) || !visual.applied.contains(id));
+    visual.excluded.extend(
+        visual
+            .applied
+            .iter()
+            .filter(|id| !selected.contains(*id)),
+    );
+
+    let mut selection = visual.base.clone();
+    selection.extend(range.into_iter().filter(|id| !visual.excluded.contains(id)));
+    visual.applied = selection.iter().copied().collect();
+    selection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_glob() {
+        let base = Path::new("/home/user/fist");
+        let glob = PathGlob::new("*.log", base).unwrap();
+        assert!(glob.is_match(Path::new("/var/log/a.log")));
+        assert!(!glob.is_match(Path::new("/var/log/a.log.1")));
+        // only the name is matched
+        assert!(
+            !PathGlob::new("log*", base)
+                .unwrap()
+                .is_match(Path::new("/log/a"))
+        );
+
+        let glob = PathGlob::new("/src/*/mod.rs", base).unwrap();
+        assert!(glob.is_match(Path::new("/src/run/mod.rs")));
+        assert!(!glob.is_match(Path::new("/src/run/state/mod.rs")));
+
+        // relative patterns match below the base
+        let glob = PathGlob::new("src/*.rs", base).unwrap();
+        assert!(glob.is_match(Path::new("/home/user/fist/src/lib.rs")));
+        assert!(!glob.is_match(Path::new("/home/user/fist/src/run/mod.rs")));
+        assert!(!glob.is_match(Path::new("/home/user/other/src/lib.rs")));
+        let glob = PathGlob::new("**/mod.rs", base).unwrap();
+        assert!(glob.is_match(Path::new("/home/user/fist/src/run/mod.rs")));
+
+        assert!(PathGlob::new("a[", base).is_err());
+    }
+
+    #[test]
+    fn test_visual_selection() {
+        let ids = |ids: &[u32]| ids.iter().copied().collect::<IndexSet<u32>>();
+        let mut visual = VisualAnchor {
+            anchor: 2,
+            base: ids(&[9]),
+            ..Default::default()
+        };
+        // in order, unlike the equality of sets
+        let order = |s: &IndexSet<u32>| s.iter().copied().collect::<Vec<u32>>();
+
+        let first = visual_selection(&mut visual, &ids(&[9]), vec![2, 3]);
+        assert_eq!(order(&first), [9, 2, 3]);
+
+        // a toggle off in the range and a toggle on outside of it survive the next extension
+        let selection = visual_selection(&mut visual, &ids(&[9, 3, 7]), vec![2, 3, 4]);
+        assert_eq!(order(&selection), [9, 7, 3, 4]);
+        let selection = visual_selection(&mut visual, &selection, vec![2, 3]);
+        assert_eq!(order(&selection), [9, 7, 3]);
+
+        // toggled back on
+        let selection = visual_selection(&mut visual, &ids(&[9, 7, 3, 2]), vec![2, 3]);
+        assert_eq!(order(&selection), [9, 7, 2, 3]);
+    }
+
+    #[test]
+    fn test_has_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub.rs");
+        std::fs::create_dir(&sub).unwrap();
+
+        assert!(has_extension(Path::new("/a/b.rs"), Some(OsStr::new("rs"))));
+        assert!(!has_extension(Path::new("/a/b.rs"), Some(OsStr::new("r"))));
+        assert!(has_extension(Path::new("/a/Makefile"), None));
+        assert!(!has_extension(Path::new("/a/b.rs"), None));
+        assert!(!has_extension(&sub, Some(OsStr::new("rs"))));
+    }
+}
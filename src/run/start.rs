@@ -3,13 +3,16 @@
 use std::{borrow::Cow, ffi::OsString, sync::Arc};
 
 use cba::{_trace, bog::BogOkExt, prints};
-use fist_types::filters::SortOrder;
+use fist_types::{
+    filters::SortOrder,
+    git::{self, GitStatus},
+};
 use matchmaker::{
     Either, MatchError, Matchmaker, PickOptions,
     config::{PreviewerConfig, RenderConfig, TerminalConfig, UiConfig},
     event::EventLoop,
     message::Event,
-    nucleo::{Column, Text, Worker, injector::WorkerInjector},
+    nucleo::{Color, Column, Line, Span, Style, Text, Worker, injector::WorkerInjector},
     preview::AppendOnly,
 };
 
@@ -25,13 +28,19 @@ use crate::{
     run::{
         action::{fsaction_aliaser, fsaction_handler},
         ahandlers::{self, fs_post_reload_new, paste_handler},
+        columns,
         item::PathItem,
+        meta::{self, MetaColumn},
         mm_config::{MATCHER_CONFIG, MMConfig},
+        mouse,
         pane::FsPane,
         previewer::make_previewer,
         register::{MMExt, emit_print, path_formatter, query_handler, sync_handler},
+        replace::{ReplacePreview, preview_tail},
+        selection,
         state::{
-            AcceptFlavor, DB_FILTER, GLOBAL, HideMetadata, MENU_ACTIONS, STACK, STORE, TASKS,
+            AcceptFlavor, CONFIG_PATH, DB_FILTER, GLOBAL, HideMetadata, MENU_ACTIONS, STACK, STORE,
+            TASKS,
             context::ActionContext, sort, ui::global_ui_init,
         },
     },
@@ -83,7 +92,14 @@ fn make_mm(
                 }
             })
             .without_filtering(),
-        ],
+        ]
+        .into_iter()
+        .chain(MetaColumn::ALL.map(|column| {
+            Column::new(column.name(), move |item: &PathItem, _d: &()| {
+                column.render(item)
+            })
+            .without_filtering()
+        })),
         0,
     );
     // stability is applied by sort::set_sort_in_nucleo (fs_reload), which knows the
@@ -139,13 +155,79 @@ fn make_mm(
 
     // cwd prompt <=> cursor_disabled can change on any cursor movement
     mm.register_event_handler(Event::CursorChange, |state, _| {
+        selection::extend_visual(state);
         ahandlers::refresh_prompt(state);
+        mouse::track_cursor(state);
+    });
+
+    // Miller columns: the parent of the Nav pane is listed in the header
+    mm.register_event_handler(Event::Start | Event::Resize | Event::Synced, |state, _| {
+        columns::refresh_parent(state);
+    });
+
+    // the metadata columns are budgeted on the width of the results
+    mm.register_event_handler(Event::Start | Event::Resize, |state, _| {
+        meta::set_width(state.picker_ui.results.width());
     });
 
     (mm, injector)
 }
 
-/// Column 2 display: the sort value (mtime/atime/size) when hard-sorted with
+/// Column 2 display: the changed lines in the Search pane's replace mode,
+/// else [`format_value_or_tail`] behind the item's git status marker.
+fn format_tail(item: &PathItem) -> Text<'static> {
+    // the Search pane's replace mode shows the changed lines
+    let preview = STORE::with(|p: &ReplacePreview| {
+        STACK::with_current(|pane| match pane {
+            FsPane::Search { one_line, .. } => preview_tail(&p.0, item, *one_line),
+            _ => None,
+        })
+    });
+    if let Some(Some(text)) = preview {
+        return text;
+    }
+
+    let mut text = format_value_or_tail(item);
+    if let Some(status) = git_status(item) {
+        let marker = Span::styled(format!("{} ", status.marker()), git_status_style(status));
+        match text.lines.first_mut() {
+            Some(line) => line.spans.insert(0, marker),
+            None => text = Text::from(Line::from(marker)),
+        }
+    }
+    text
+}
+
+/// The git status of items of the panes listing the filesystem, when enabled and not shown in
+/// its own [`MetaColumn::Git`] column. Statuses are read from the cache only, which populate
+/// fills off the render thread.
+fn git_status(item: &PathItem) -> Option<GitStatus> {
+    if !GLOBAL::cfg().interface.git_status
+        || meta::enabled(MetaColumn::Git)
+        || !STACK::with_current(|p| {
+            matches!(
+                p,
+                FsPane::Nav { .. } | FsPane::Find { .. } | FsPane::Custom { .. }
+            )
+        })
+    {
+        return None;
+    }
+    git::cached_status(&item.path)
+}
+
+pub(crate) fn git_status_style(status: GitStatus) -> Style {
+    let color = match status {
+        GitStatus::Ignored => Color::DarkGray,
+        GitStatus::Untracked => Color::Magenta,
+        GitStatus::Staged => Color::Green,
+        GitStatus::Modified => Color::Yellow,
+        GitStatus::Conflicted => Color::Red,
+    };
+    Style::new().fg(color)
+}
+
+/// The sort value (mtime/atime/size) when hard-sorted with
 /// an empty tail, else the tail text.
 ///
 /// The metadata override is skipped while a fresh sort override is hiding it
@@ -159,7 +241,7 @@ fn make_mm(
 /// sort reads the [`fist_size::DirSizeCache`] directly, so the display must
 /// too ([`sort::size_of`]): `None` = dir not computed yet → blank; `Some(0)`
 /// = genuinely empty file/dir → "0 B".
-fn format_tail(item: &PathItem) -> Text<'static> {
+fn format_value_or_tail(item: &PathItem) -> Text<'static> {
     let order = sort::get_sort().order;
     // gated on an empty tail so the filter string (always the tail) can never
     // mismatch the display
@@ -201,8 +283,10 @@ pub async fn start(
     let CliOpts {
         lock_prompt,
         output,
+        config: config_path,
         ..
     } = cli;
+    let _ = CONFIG_PATH.set(config_path);
 
     // init configs
     let MMConfig {
@@ -240,10 +324,15 @@ pub async fn start(
         print_handle.clone(),
     );
 
-    let event_loop = EventLoop::with_binds(binds)
+    let mut event_loop = EventLoop::with_binds(binds)
         .with_tick_rate(tick_rate)
         .with_mouse_events(mouse_events)
         .with_scroll_debounce(mouse_scroll_debounce_ms);
+    if mouse_events {
+        mouse::listen(&mut event_loop);
+    } else {
+        mouse::release_capture(&mut mm);
+    }
 
     let bind_tx = event_loop.bind_controller();
 
@@ -1,15 +1,21 @@
-//! In-memory backing store of transient stash panes.
+//! In-memory backing store of transient stash panes, the operations
+//! combining stashes regardless of their backing store, and the stash list
+//! formats used to export and import them.
 
 use std::{
+    collections::HashSet,
     sync::Mutex,
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use cba::vecmap::VecMap;
+use cba::{StringError, vecmap::VecMap};
 
 use crate::{
     abspath::AbsPath,
-    db::{Epoch, StashEntry},
+    config::StashPaneKind,
+    db::{DbTable, Epoch, StashEntry},
+    errors::DbError,
+    run::{FsAction, state::GLOBAL},
 };
 
 /// Entries of the transient stashes, keyed by stash name. Each stash's
@@ -75,6 +81,12 @@ pub fn mem_remove(name: &str, paths: &[AbsPath]) -> usize {
     before - entries.len()
 }
 
+/// Replace the entries of the named transient stash.
+pub fn mem_set(name: &str, entries: Vec<StashEntry>) {
+    let mut mem = MEM_STASHES.lock().unwrap();
+    *mem.get_or_insert_mut(name.to_string(), Vec::new()) = entries;
+}
+
 /// Set the tail (alias analogue of the apps pane) of the named transient
 /// stash entry whose path matches. Returns whether an entry was updated.
 pub fn mem_set_tail(name: &str, path: &AbsPath, tail: &str) -> bool {
@@ -90,3 +102,374 @@ pub fn mem_set_tail(name: &str, path: &AbsPath, tail: &str) -> bool {
         None => false,
     }
 }
+
+// ------------- SET OPERATIONS -----------------
+
+/// A set operation between two stashes, see [`StashOperands`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum StashOp {
+    /// Entries of either stash.
+    Union,
+    /// Entries of the first stash which are also in the second.
+    Intersect,
+    /// Entries of the first stash which are not in the second.
+    Diff,
+    /// Entries of exactly one of the stashes.
+    SymDiff,
+}
+
+impl StashOp {
+    pub const ALL: [Self; 4] = [Self::Union, Self::Intersect, Self::Diff, Self::SymDiff];
+
+    /// Combine the entries of two stashes, compared by path. Each path occurs
+    /// once in the result, with the entry of `lhs` when both contain it
+    /// (keeping its tail); the result is ordered newest first.
+    pub fn apply(self, lhs: Vec<StashEntry>, rhs: Vec<StashEntry>) -> Vec<StashEntry> {
+        let lhs = dedup(lhs);
+        let rhs = dedup(rhs);
+        let in_lhs: HashSet<AbsPath> = lhs.iter().map(|e| e.stash.clone()).collect();
+        let in_rhs: HashSet<AbsPath> = rhs.iter().map(|e| e.stash.clone()).collect();
+
+        let keep_lhs = |e: &StashEntry| match self {
+            Self::Union => true,
+            Self::Intersect => in_rhs.contains(&e.stash),
+            Self::Diff | Self::SymDiff => !in_rhs.contains(&e.stash),
+        };
+        let keep_rhs = |e: &StashEntry| {
+            matches!(self, Self::Union | Self::SymDiff) && !in_lhs.contains(&e.stash)
+        };
+
+        let mut ret: Vec<StashEntry> = lhs
+            .into_iter()
+            .filter(keep_lhs)
+            .chain(rhs.into_iter().filter(keep_rhs))
+            .collect();
+        ret.sort_by_key(|e| std::cmp::Reverse(e.add_time));
+        ret
+    }
+
+    /// The action applying this operation.
+    pub fn action(self, operands: StashOperands) -> FsAction {
+        match self {
+            Self::Union => FsAction::StashUnion(operands),
+            Self::Intersect => FsAction::StashIntersect(operands),
+            Self::Diff => FsAction::StashDiff(operands),
+            Self::SymDiff => FsAction::StashSymDiff(operands),
+        }
+    }
+
+    /// The verb shown in the stash pane's menu.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Union => "union with",
+            Self::Intersect => "intersect with",
+            Self::Diff => "subtract",
+            Self::SymDiff => "symmetric diff with",
+        }
+    }
+}
+
+/// Keep the first (newest) entry of each path.
+fn dedup(entries: Vec<StashEntry>) -> Vec<StashEntry> {
+    let mut seen = HashSet::new();
+    entries
+        .into_iter()
+        .filter(|e| seen.insert(e.stash.clone()))
+        .collect()
+}
+
+/// Operands of the stash set actions: `lhs,rhs[,into]`, where `into` (the
+/// stash receiving the result, created if needed) defaults to `lhs`. For
+/// [`crate::run::FsAction::MoveStash`], `lhs,rhs` moves `lhs` into `rhs`.
+/// Empty names refer to the unnamed stash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StashOperands {
+    pub lhs: String,
+    pub rhs: String,
+    pub into: Option<String>,
+}
+
+impl StashOperands {
+    pub fn new(lhs: impl Into<String>, rhs: impl Into<String>) -> Self {
+        Self {
+            lhs: lhs.into(),
+            rhs: rhs.into(),
+            into: None,
+        }
+    }
+
+    /// The stash receiving the result.
+    pub fn target(&self) -> &str {
+        self.into.as_deref().unwrap_or(&self.lhs)
+    }
+}
+
+impl std::str::FromStr for StashOperands {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(',').map(str::trim).collect();
+        match parts[..] {
+            [lhs, rhs] => Ok(Self::new(lhs, rhs)),
+            [lhs, rhs, into] => Ok(Self {
+                into: Some(into.to_string()),
+                ..Self::new(lhs, rhs)
+            }),
+            _ => Err(format!("Expected 'stash,stash[,into]', got '{s}'")),
+        }
+    }
+}
+
+impl std::fmt::Display for StashOperands {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.lhs, self.rhs)?;
+        if let Some(into) = &self.into {
+            write!(f, ",{into}")?;
+        }
+        Ok(())
+    }
+}
+
+// ------------- IMPORT/EXPORT -----------------
+
+/// Format of exported and imported stashes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum StashFormat {
+    /// One path per line.
+    #[default]
+    Lines,
+    /// NUL-separated paths.
+    Nul,
+    /// An array of `{ path, tail, add_time }` objects, keeping the tails.
+    Json,
+}
+
+impl StashFormat {
+    /// [`Self::Json`] for `.json` files, [`Self::Nul`] for `.nul` files, otherwise
+    /// [`Self::Lines`].
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension() {
+            Some(e) if e.eq_ignore_ascii_case("json") => Self::Json,
+            Some(e) if e.eq_ignore_ascii_case("nul") => Self::Nul,
+            _ => Self::Lines,
+        }
+    }
+}
+
+/// A stash entry as exported in [`StashFormat::Json`].
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct StashRecord {
+    pub path: String,
+    #[serde(default)]
+    pub tail: String,
+    #[serde(default)]
+    pub add_time: Option<Epoch>,
+}
+
+/// Render `entries` in `format`; lists end with a separator.
+pub fn export_entries(entries: &[StashEntry], format: StashFormat) -> Result<String, StringError> {
+    let paths = || entries.iter().map(|e| e.stash.to_string_lossy());
+    Ok(match format {
+        StashFormat::Lines => paths().map(|p| format!("{p}\n")).collect(),
+        StashFormat::Nul => paths().map(|p| format!("{p}\0")).collect(),
+        StashFormat::Json => {
+            let records: Vec<_> = entries
+                .iter()
+                .map(|e| StashRecord {
+                    path: e.stash.to_string_lossy().into(),
+                    tail: e.tail.clone(),
+                    add_time: Some(e.add_time),
+                })
+                .collect();
+            let mut s = serde_json::to_string_pretty(&records)
+                .map_err(|e| StringError::from(e.to_string()))?;
+            s.push('\n');
+            s
+        }
+    })
+}
+
+/// Parse entries in `format`; relative paths resolve against the cwd and
+/// blank items are skipped. Entries without an add time are given
+/// decreasing times from now, so the input order (newest first) is kept.
+pub fn import_entries(input: &str, format: StashFormat) -> Result<Vec<StashEntry>, StringError> {
+    let records: Vec<StashRecord> = match format {
+        StashFormat::Lines | StashFormat::Nul => {
+            let sep = if format == StashFormat::Nul { '\0' } else { '\n' };
+            input
+                .split(sep)
+                .map(|p| p.trim_end_matches('\r'))
+                .filter(|p| !p.trim().is_empty())
+                .map(|p| StashRecord {
+                    path: p.to_string(),
+                    tail: String::new(),
+                    add_time: None,
+                })
+                .collect()
+        }
+        StashFormat::Json => serde_json::from_str(input)
+            .map_err(|e| StringError::from(format!("Invalid stash JSON: {e}")))?,
+    };
+
+    let now = now();
+    Ok(records
+        .into_iter()
+        .filter(|r| !r.path.is_empty())
+        .enumerate()
+        .map(|(i, r)| StashEntry {
+            id: 0,
+            name: String::new(),
+            stash: AbsPath::new(r.path),
+            tail: r.tail,
+            add_time: r.add_time.unwrap_or(now - i as Epoch),
+        })
+        .collect())
+}
+
+fn stash_kind(name: &str) -> StashPaneKind {
+    GLOBAL::cfg().panes.stash_setting(name).kind
+}
+
+/// All entries of the named stash from its backing store (newest first).
+pub async fn get_entries(name: &str) -> Result<Vec<StashEntry>, DbError> {
+    if stash_kind(name) == StashPaneKind::Transient {
+        Ok(mem_get(name))
+    } else {
+        let mut conn = GLOBAL::db().get_conn(DbTable::stashes).await?;
+        conn.get_stash_entries(name).await
+    }
+}
+
+/// Replace the entries of the named stash in its backing store.
+pub async fn set_entries(name: &str, entries: Vec<StashEntry>) -> Result<(), DbError> {
+    if stash_kind(name) == StashPaneKind::Transient {
+        mem_set(name, entries);
+        Ok(())
+    } else {
+        let mut conn = GLOBAL::db().get_conn(DbTable::stashes).await?;
+        conn.set_stash_entries(name, &entries).await
+    }
+}
+
+/// Set the tail (note) of the named stash's entry for `path` in its backing
+/// store. An empty `tail` clears it.
+pub async fn set_tail(name: &str, path: &AbsPath, tail: &str) -> Result<(), DbError> {
+    if stash_kind(name) == StashPaneKind::Transient {
+        mem_set_tail(name, path, tail);
+        Ok(())
+    } else {
+        let mut conn = GLOBAL::db().get_conn(DbTable::stashes).await?;
+        conn.set_stash_tail(name, path, tail).await
+    }
+}
+
+/// Apply `op` to the operand stashes and store the result in the target.
+/// Returns the number of entries in the result.
+pub async fn combine(op: StashOp, operands: &StashOperands) -> Result<usize, DbError> {
+    let lhs = get_entries(&operands.lhs).await?;
+    let rhs = get_entries(&operands.rhs).await?;
+    let ret = op.apply(lhs, rhs);
+    let len = ret.len();
+    set_entries(operands.target(), ret).await?;
+    Ok(len)
+}
+
+/// Move all entries of `from` into `to` (entries already in `to` are kept),
+/// leaving `from` empty. Returns the number of entries moved.
+pub async fn move_all(from: &str, to: &str) -> Result<usize, DbError> {
+    if from == to {
+        return Ok(0);
+    }
+    let moved = get_entries(from).await?;
+    let len = moved.len();
+    let ret = StashOp::Union.apply(get_entries(to).await?, moved);
+    set_entries(to, ret).await?;
+    set_entries(from, Vec::new()).await?;
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, tail: &str, add_time: Epoch) -> StashEntry {
+        StashEntry {
+            id: 0,
+            name: String::new(),
+            stash: AbsPath::new(path),
+            tail: tail.into(),
+            add_time,
+        }
+    }
+
+    fn paths(entries: &[StashEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.stash.to_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_stash_ops() {
+        let lhs = vec![entry("/a", "lhs", 3), entry("/b", "", 2), entry("/a", "old", 1)];
+        let rhs = vec![entry("/c", "", 4), entry("/b", "rhs", 0)];
+
+        let union = StashOp::Union.apply(lhs.clone(), rhs.clone());
+        assert_eq!(paths(&union), ["/c", "/a", "/b"]);
+        // shared paths keep the lhs entry
+        assert_eq!(union[1].tail, "lhs");
+        assert_eq!(union[2].add_time, 2);
+
+        let intersect = StashOp::Intersect.apply(lhs.clone(), rhs.clone());
+        assert_eq!(paths(&intersect), ["/b"]);
+        let diff = StashOp::Diff.apply(lhs.clone(), rhs.clone());
+        assert_eq!(paths(&diff), ["/a"]);
+        let sym = StashOp::SymDiff.apply(lhs, rhs);
+        assert_eq!(paths(&sym), ["/c", "/a"]);
+    }
+
+    #[test]
+    fn test_stash_export_import() {
+        let entries = vec![entry("/a", "note", 3), entry("/b c", "", 2)];
+
+        let lines = export_entries(&entries, StashFormat::Lines).unwrap();
+        assert_eq!(lines, "/a\n/b c\n");
+        let nul = export_entries(&entries, StashFormat::Nul).unwrap();
+        assert_eq!(nul, "/a\0/b c\0");
+        let imported = import_entries(&nul, StashFormat::Nul).unwrap();
+        assert_eq!(paths(&imported), ["/a", "/b c"]);
+        // the order is kept through the assigned add times
+        assert!(imported[0].add_time > imported[1].add_time);
+
+        // json keeps the tails and add times
+        let json = export_entries(&entries, StashFormat::Json).unwrap();
+        let imported = import_entries(&json, StashFormat::Json).unwrap();
+        assert_eq!(paths(&imported), ["/a", "/b c"]);
+        assert_eq!((imported[0].tail.as_str(), imported[0].add_time), ("note", 3));
+
+        let imported = import_entries("/x\r\n\n  \n/y", StashFormat::Lines).unwrap();
+        assert_eq!(paths(&imported), ["/x", "/y"]);
+        assert!(import_entries("[{}]", StashFormat::Json).is_err());
+
+        let format = |p: &str| StashFormat::from_path(std::path::Path::new(p));
+        assert_eq!(format("a.json"), StashFormat::Json);
+        assert_eq!(format("a.NUL"), StashFormat::Nul);
+        assert_eq!(format("a.txt"), StashFormat::Lines);
+        assert_eq!(format("nul"), StashFormat::Lines);
+    }
+
+    #[test]
+    fn test_stash_operands() {
+        let ops: StashOperands = "a, b".parse().unwrap();
+        assert_eq!(ops, StashOperands::new("a", "b"));
+        assert_eq!(ops.target(), "a");
+
+        // the unnamed stash is the empty name
+        let ops: StashOperands = ",bookmark,c".parse().unwrap();
+        assert_eq!(ops.lhs, "");
+        assert_eq!(ops.target(), "c");
+        assert_eq!(ops.to_string(), ",bookmark,c");
+
+        assert!("a".parse::<StashOperands>().is_err());
+        assert_eq!("symdiff".parse::<StashOp>().unwrap(), StashOp::SymDiff);
+    }
+}
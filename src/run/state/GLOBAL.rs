@@ -4,6 +4,7 @@
 
 use std::cell::OnceCell;
 use std::sync::OnceLock;
+use std::path::Path;
 
 use cba::bait::ResultExt;
 use log::debug;
@@ -99,6 +100,11 @@ pub fn init_test_senders() {
 }
 
 // ------------ SENDERS --------------
+/// Whether the TUI is running, i.e. [`send_action`] and [`send_mm`] can be called.
+pub fn has_render_tx() -> bool {
+    RENDER_TX.get().is_some()
+}
+
 pub fn send_action(action: impl Into<Action<FsAction>>) {
     RENDER_TX
         .get()
@@ -134,10 +140,13 @@ pub fn db() -> &'static Pool {
     DB.get().expect("GLOBAL::init not called")
 }
 
+/// `context`: the directory of the requesting pane, used for
+/// [`crate::db::zoxide::HistoryConfig::context_boost`].
 pub async fn get_db_entries(
     conn: &mut Connection,
     sort: SortOrder,
+    context: Option<&Path>,
 ) -> Result<Vec<crate::db::Entry>, DbError> {
     let config = DB_FILTER.get().expect("DB_FILTER not initialized");
-    conn.get_entries(sort, config, conn.table).await
+    conn.get_entries(sort, config, conn.table, context).await
 }
@@ -1,8 +1,15 @@
 #![allow(non_snake_case)]
-use std::sync::OnceLock;
+use std::{
+    path::PathBuf,
+    sync::{Mutex, OnceLock},
+};
 
-use crate::db::zoxide::HistoryConfig;
+use cba::vecmap::VecMap;
+
+use crate::config::SavedSearch;
+use crate::db::{CommandRecord, zoxide::HistoryConfig};
 use crate::menu::MenuActions;
+use crate::run::marks::MarkListing;
 
 mod filters;
 pub use filters::*;
@@ -29,6 +36,21 @@ pub static DB_FILTER: OnceLock<HistoryConfig> = OnceLock::new();
 /// action's command under `TASKS::spawn_blocking` — so it is a process-global,
 /// thread-safe `OnceLock` rather than a thread-local.
 pub static MENU_ACTIONS: OnceLock<MenuActions> = OnceLock::new();
+/// The command history fetched by [`crate::run::FsAction::CommandHistory`],
+/// handed to the menu overlay which lists it instead of the actions. Filled
+/// from a db task, hence process-global rather than in the thread-local [`STORE`].
+pub static COMMAND_HISTORY: Mutex<Option<Vec<CommandRecord>>> = Mutex::new(None);
+/// The marks fetched by [`crate::run::FsAction::SetMark`] and
+/// [`crate::run::FsAction::JumpMark`] without a mark, listed by the menu overlay,
+/// which takes the next key as the mark. Filled from a db task, as
+/// [`COMMAND_HISTORY`].
+pub static MARK_LISTING: Mutex<Option<MarkListing>> = Mutex::new(None);
+/// The config file the app was started with: [`crate::run::FsAction::SaveSearch`]
+/// appends to it.
+pub static CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
+/// Searches saved during this session, which the loaded config doesn't know
+/// about until the next start.
+pub static SESSION_SEARCHES: Mutex<VecMap<String, SavedSearch>> = Mutex::new(VecMap::new());
 
 #[cfg(test)]
 mod tests {
@@ -49,6 +71,7 @@ mod tests {
         let pool = Pool {
             pool: sqlx::SqlitePool::connect_lazy("sqlite::memory:").unwrap(),
             lambda: None,
+            visits_max: 0,
         };
         let pane = FsPane::Nav {
             cwd: crate::abspath::AbsPath::new("/tmp"),
@@ -57,6 +80,7 @@ mod tests {
             input: (String::new(), 0),
             complete: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
             depth: 0,
+            toggled: Default::default(),
         };
 
         GLOBAL::init(
@@ -7,15 +7,15 @@
 
 use std::{
     cmp::Ordering,
-    path::Path,
+    path::{Path, PathBuf},
     sync::{Arc, LazyLock, Mutex},
     time::UNIX_EPOCH,
 };
 
 use fist_types::filters::SortOrder;
+use matchmaker::message::RenderCommand;
 
 use crate::{
-    abspath::AbsPath,
     aliases::MMState,
     run::{
         item::PathItem,
@@ -45,6 +45,8 @@ static DIR_SIZE: LazyLock<fist_size::DirSizeCache> = LazyLock::new(|| {
         if get_sort().order == SortOrder::size {
             GLOBAL::send_action(FsAction::ResortSizes);
         }
+        // fill in the size column
+        GLOBAL::send_mm(RenderCommand::Redraw);
     });
     cache
 });
@@ -94,7 +96,14 @@ fn set_global_sort_only_from_pane() -> SortMode {
 pub fn set_sort_from_pane(state: &mut MMState<'_>) {
     let SortMode { order, threshold } = set_global_sort_only_from_pane();
 
-    state.picker_ui.worker.nucleo.sort_with(sort_fn_for(order));
+    // trees are ordered per level as they populate, and keep that order (their threshold is
+    // u32::MAX)
+    let sort_fn = if STACK::with_current(FsPane::is_tree) {
+        None
+    } else {
+        sort_fn_for(order)
+    };
+    state.picker_ui.worker.nucleo.sort_with(sort_fn);
     state.picker_ui.worker.nucleo.set_stability(threshold);
 }
 
@@ -154,10 +163,10 @@ pub fn clear_dir_sizes() {
 }
 
 fn stat_time(
-    path: &AbsPath,
+    path: impl AsRef<Path>,
     order: SortOrder,
 ) -> u64 {
-    std::fs::metadata(path.inner())
+    std::fs::metadata(path)
         .ok()
         .and_then(|m| {
             if order == SortOrder::mtime {
@@ -187,6 +196,29 @@ pub fn store_sort_value(
     }
 }
 
+/// Order the entries of one level of a Nav tree, which keeps the insertion order (see
+/// [`set_sort_from_pane`]). Sizes are waited for, so that they are known before the level is
+/// ordered.
+pub fn sort_level(
+    paths: &mut [PathBuf],
+    order: SortOrder,
+) {
+    match order {
+        SortOrder::name => paths.sort(),
+        SortOrder::mtime | SortOrder::atime => paths.sort_by_cached_key(|p| {
+            std::cmp::Reverse(stat_time(p, order))
+        }),
+        SortOrder::size => {
+            for p in paths.iter() {
+                DIR_SIZE.add(p);
+            }
+            DIR_SIZE.wait();
+            paths.sort_by_cached_key(|p| std::cmp::Reverse(size_of(p).unwrap_or(0)));
+        }
+        SortOrder::none => {}
+    }
+}
+
 /// Fill flow for a mid-pane sort change (no reload): snapshot the existing
 /// items, compute the new sort values off-thread, then dispatch `ReSort`.
 pub fn fill_then_resort(state: &MMState<'_>) {
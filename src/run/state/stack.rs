@@ -44,12 +44,39 @@ pub fn render_path() -> Option<AbsPath> {
 pub struct STACK {
     stack: Vec<FsPane>, // invariants: nonempty
     index: usize,
+    /// One entry per tab. The active tab's panes are `stack`/`index`, so its entry is left empty
+    /// until it is switched away from.
+    tabs: Vec<Tab>,
+    tab: usize,
+    /// The inactive panel of the current tab in the dual-pane mode.
+    panel: Option<Panel>,
 }
+
+/// A pane stack parked while another tab is active.
+#[derive(Default)]
+struct Tab {
+    stack: Vec<FsPane>,
+    index: usize,
+    /// Hashes of the paths selected when the tab was left (see [`crate::run::selection`]).
+    selections: Vec<u64>,
+    panel: Option<Panel>,
+}
+
+/// The panel of the dual-pane mode which is not being shown (see [`crate::run::commander`]).
+struct Panel {
+    tab: Box<Tab>,
+    /// Whether the active panel is the right one.
+    right: bool,
+}
+
 impl STACK {
     const fn new() -> Self {
         Self {
             stack: Vec::new(),
             index: 0,
+            tabs: Vec::new(),
+            tab: 0,
+            panel: None,
         }
     }
 
@@ -58,6 +85,9 @@ impl STACK {
             *s.borrow_mut() = Self {
                 stack: vec![pane],
                 index: 0,
+                tabs: vec![Tab::default()],
+                tab: 0,
+                panel: None,
             }
         });
     }
@@ -68,7 +98,7 @@ impl STACK {
 
     pub fn push(pane: FsPane) -> bool {
         STACK.with(|cell| {
-            let Self { stack, index } = &mut *cell.borrow_mut();
+            let Self { stack, index, .. } = &mut *cell.borrow_mut();
             stack.truncate(*index + 1);
 
             let same = discriminant(&stack[*index]) == discriminant(&pane);
@@ -226,6 +256,10 @@ impl STACK {
         let cfg = GLOBAL::cfg().clone();
         Self::with_current(|pane| {
             let msg = match &pane {
+                // expanded directories are listed too
+                FsPane::Nav { cwd, .. } if pane.is_tree() => {
+                    WatcherMessage::Switch(cwd.inner(), notify::RecursiveMode::Recursive)
+                }
                 FsPane::Nav { cwd, .. } | FsPane::Custom { cwd, .. } => {
                     WatcherMessage::Switch(cwd.inner(), notify::RecursiveMode::NonRecursive)
                 }
@@ -242,33 +276,195 @@ impl STACK {
     }
 }
 
-// ---------------- utilities
+// ---------------- tabs
 impl STACK {
-    /// Return the cwd for Nav/Custom/Fd
-    pub fn cwd() -> Option<AbsPath> {
+    /// Park the current stack with its `selections` and open a new tab after it on `pane`.
+    pub fn new_tab(
+        pane: FsPane,
+        selections: Vec<u64>,
+    ) {
         STACK.with(|cell| {
-            let Self { stack, index, .. } = &*cell.borrow();
-            if stack.is_empty() {
+            let this = &mut *cell.borrow_mut();
+            this.park(selections);
+            this.tab += 1;
+            this.tabs.insert(this.tab, Tab::default());
+            this.stack = vec![pane];
+            this.index = 0;
+            log::debug!("Opened tab {}", this.tab);
+        })
+    }
+
+    /// Park the current stack with its `selections` and switch to tab `n` (0-based), returning
+    /// the selections it was left with. `None` if there is no such tab, or it is the current one.
+    pub fn goto_tab(
+        n: usize,
+        selections: Vec<u64>,
+    ) -> Option<Vec<u64>> {
+        STACK.with(|cell| {
+            let this = &mut *cell.borrow_mut();
+            if n >= this.tabs.len() || n == this.tab {
                 return None;
             }
-            let mut seen = false;
-            for s in stack[0..=*index].iter().rev() {
-                match s {
-                    FsPane::Files { .. } | FsPane::Folders { .. } | FsPane::Stash { .. } => {
-                        seen = true
-                    }
-                    FsPane::Nav { cwd, .. }
-                    | FsPane::Custom { cwd, .. }
-                    | FsPane::Find { cwd, .. }
-                    | FsPane::Search { cwd, .. } => {
-                        return Some(cwd.clone());
+            this.park(selections);
+            Some(this.unpark(n))
+        })
+    }
+
+    /// Close the current tab and switch to the next one (or the previous one, from the last
+    /// tab), returning the selections it was left with. `None` if this is the only tab.
+    pub fn close_tab() -> Option<Vec<u64>> {
+        STACK.with(|cell| {
+            let this = &mut *cell.borrow_mut();
+            if this.tabs.len() < 2 {
+                return None;
+            }
+            this.tabs.remove(this.tab);
+            let n = this.tab.min(this.tabs.len() - 1);
+            Some(this.unpark(n))
+        })
+    }
+
+    /// The number of tabs and the index of the current one.
+    pub fn tabs() -> (usize, usize) {
+        STACK.with(|cell| {
+            let this = &*cell.borrow();
+            (this.tabs.len(), this.tab)
+        })
+    }
+
+    /// Apply `f` to the current pane of every tab, in order.
+    pub fn map_tabs<R>(f: impl Fn(&FsPane) -> R) -> Vec<R> {
+        STACK.with(|cell| {
+            let this = &*cell.borrow();
+            this.tabs
+                .iter()
+                .enumerate()
+                .map(|(i, t)| {
+                    if i == this.tab {
+                        f(&this.stack[this.index])
+                    } else {
+                        f(&t.stack[t.index])
                     }
-                    FsPane::Apps { .. } => return None,
-                }
+                })
+                .collect()
+        })
+    }
+
+    fn park(
+        &mut self,
+        selections: Vec<u64>,
+    ) {
+        self.tabs[self.tab] = Tab {
+            stack: std::mem::take(&mut self.stack),
+            index: self.index,
+            selections,
+            panel: self.panel.take(),
+        };
+    }
+
+    fn unpark(
+        &mut self,
+        n: usize,
+    ) -> Vec<u64> {
+        let Tab {
+            stack,
+            index,
+            selections,
+            panel,
+        } = std::mem::take(&mut self.tabs[n]);
+        self.stack = stack;
+        self.index = index;
+        self.panel = panel;
+        self.tab = n;
+        selections
+    }
+}
+
+// ---------------- dual-pane mode
+impl STACK {
+    /// Open the other panel of the current tab on `pane`, the current one becoming the left one.
+    pub fn open_panel(pane: FsPane) {
+        STACK.with(|cell| {
+            cell.borrow_mut().panel = Some(Panel {
+                tab: Box::new(Tab {
+                    stack: vec![pane],
+                    ..Default::default()
+                }),
+                right: false,
+            })
+        })
+    }
+
+    /// Close the other panel, returning whether the dual-pane mode was on.
+    pub fn close_panel() -> bool {
+        STACK.with(|cell| cell.borrow_mut().panel.take().is_some())
+    }
+
+    /// Park the current stack with its `selections` and switch to the other panel, returning
+    /// the selections it was left with. `None` outside of the dual-pane mode.
+    pub fn swap_panel(selections: Vec<u64>) -> Option<Vec<u64>> {
+        STACK.with(|cell| {
+            let Self {
+                stack,
+                index,
+                panel,
+                ..
+            } = &mut *cell.borrow_mut();
+            let Panel { tab, right } = panel.as_mut()?;
+            std::mem::swap(stack, &mut tab.stack);
+            std::mem::swap(index, &mut tab.index);
+            *right = !*right;
+            Some(std::mem::replace(&mut tab.selections, selections))
+        })
+    }
+
+    /// Apply `f` to the current pane of the other panel, and whether the active panel is the
+    /// right one. `None` outside of the dual-pane mode.
+    pub fn with_panel<R>(f: impl FnOnce(&FsPane, bool) -> R) -> Option<R> {
+        STACK.with(|cell| {
+            let this = &*cell.borrow();
+            let Panel { tab, right } = this.panel.as_ref()?;
+            Some(f(&tab.stack[tab.index], *right))
+        })
+    }
+
+    /// The cwd of the other panel (see [`Self::cwd`]).
+    pub fn panel_cwd() -> Option<AbsPath> {
+        STACK.with(|cell| {
+            let this = &*cell.borrow();
+            let Panel { tab, .. } = this.panel.as_ref()?;
+            cwd_of(&tab.stack, tab.index)
+        })
+    }
+
+    /// The selections the other panel is shown with when switched to.
+    pub fn panel_selections() -> Vec<u64> {
+        STACK.with(|cell| {
+            let this = &*cell.borrow();
+            this.panel
+                .as_ref()
+                .map(|Panel { tab, .. }| tab.selections.clone())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Set the selections the other panel is shown with when switched to.
+    pub fn set_panel_selections(selections: Vec<u64>) {
+        STACK.with(|cell| {
+            if let Some(Panel { tab, .. }) = &mut cell.borrow_mut().panel {
+                tab.selections = selections;
             }
+        })
+    }
+}
 
-            // FsPane::Files looks for the last directory, or else the original
-            seen.then_some(AbsPath::initial())
+// ---------------- utilities
+impl STACK {
+    /// Return the cwd for Nav/Custom/Fd
+    pub fn cwd() -> Option<AbsPath> {
+        STACK.with(|cell| {
+            let Self { stack, index, .. } = &*cell.borrow();
+            cwd_of(stack, *index)
         })
     }
 
@@ -308,11 +504,12 @@ impl STACK {
                 | FsPane::Nav { input, .. }
                 | FsPane::Files { input, .. }
                 | FsPane::Folders { input, .. }
-                | FsPane::Stash { input, .. } => {
+                | FsPane::Stash { input, .. }
+                | FsPane::Aliases { input, .. } => {
                     log::debug!("saving: {content} {cursor}");
                     *input = (content, cursor)
                 }
-                FsPane::Find { input, .. } => {
+                FsPane::Find { input, .. } | FsPane::Dupes { input, .. } => {
                     // input.0 = content,
                     *input = (content, cursor)
                 }
@@ -333,7 +530,9 @@ impl STACK {
                 | FsPane::Nav { input, .. }
                 | FsPane::Files { input, .. }
                 | FsPane::Folders { input, .. }
-                | FsPane::Stash { input, .. } => {
+                | FsPane::Stash { input, .. }
+                | FsPane::Aliases { input, .. }
+                | FsPane::Dupes { input, .. } => {
                     let ret = std::mem::take(&mut input.1);
                     // 0 -> None because we only store index
                     (ret != 0).then_some(ret)
@@ -347,6 +546,14 @@ impl STACK {
         i
     }
 
+    /// Name of the current pane, `None` outside of the interface.
+    pub fn pane_name() -> Option<&'static str> {
+        STACK.with(|cell| {
+            let Self { stack, index, .. } = &*cell.borrow();
+            stack.get(*index).map(FsPane::pane_name)
+        })
+    }
+
     pub fn in_app() -> bool {
         STACK::with_current(|x| matches!(x, FsPane::Apps { .. }))
     }
@@ -355,6 +562,38 @@ impl STACK {
     }
 }
 
+/// The cwd of the pane at `index` of `stack`, see [`STACK::cwd`].
+fn cwd_of(
+    stack: &[FsPane],
+    index: usize,
+) -> Option<AbsPath> {
+    if stack.is_empty() {
+        return None;
+    }
+    let mut seen = false;
+    for s in stack[0..=index].iter().rev() {
+        match s {
+            FsPane::Files { .. }
+            | FsPane::Folders { .. }
+            | FsPane::Stash { .. }
+            | FsPane::Aliases { .. } => {
+                seen = true
+            }
+            FsPane::Nav { cwd, .. }
+            | FsPane::Custom { cwd, .. }
+            | FsPane::Find { cwd, .. }
+            | FsPane::Search { cwd, .. }
+            | FsPane::Dupes { cwd, .. } => {
+                return Some(cwd.clone());
+            }
+            FsPane::Apps { .. } => return None,
+        }
+    }
+
+    // FsPane::Files looks for the last directory, or else the original
+    seen.then_some(AbsPath::initial())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,6 +609,7 @@ mod tests {
             sort: Default::default(),
             vis: Default::default(),
             depth: 1,
+            toggled: Default::default(),
             input: (String::new(), 0),
             complete: Default::default(),
         });
@@ -382,6 +622,7 @@ mod tests {
             sort: Default::default(),
             vis: Default::default(),
             depth: 1,
+            toggled: Default::default(),
             input: (String::new(), 0),
             complete: Default::default(),
         });
@@ -394,4 +635,85 @@ mod tests {
         let bg_cwd = std::thread::spawn(STACK::cwd).join().unwrap();
         assert_eq!(bg_cwd, None);
     }
+
+    #[test]
+    fn test_tabs() {
+        let nav = |p: &str| FsPane::Nav {
+            cwd: AbsPath::new_unchecked(Path::new(p)),
+            sort: Default::default(),
+            vis: Default::default(),
+            depth: 1,
+            toggled: Default::default(),
+            input: (String::new(), 0),
+            complete: Default::default(),
+        };
+        let cwd = |p: &str| Some(AbsPath::new_unchecked(Path::new(p)));
+
+        STACK::init(nav("/a"));
+        STACK::push(nav("/a/b"));
+        assert_eq!(STACK::close_tab(), None);
+
+        // each tab keeps its own stack
+        STACK::new_tab(nav("/c"), vec![1, 2]);
+        assert_eq!(STACK::tabs(), (2, 1));
+        assert_eq!(STACK::cwd(), cwd("/c"));
+        assert!(!STACK::stack_prev());
+
+        assert_eq!(STACK::goto_tab(1, vec![]), None);
+        assert_eq!(STACK::goto_tab(2, vec![]), None);
+        assert_eq!(STACK::goto_tab(0, vec![3]), Some(vec![1, 2]));
+        assert_eq!(STACK::cwd(), cwd("/a/b"));
+        assert!(STACK::stack_prev());
+        assert_eq!(STACK::cwd(), cwd("/a"));
+
+        // new tabs open after the current one
+        STACK::new_tab(nav("/d"), vec![]);
+        assert_eq!(STACK::map_tabs(FsPane::tab_label), ["a", "d", "c"]);
+
+        assert_eq!(STACK::close_tab(), Some(vec![3]));
+        assert_eq!(STACK::tabs(), (2, 1));
+        assert_eq!(STACK::cwd(), cwd("/c"));
+        // closing the last tab falls back to the previous one
+        assert_eq!(STACK::close_tab(), Some(vec![]));
+        assert_eq!(STACK::tabs(), (1, 0));
+        assert_eq!(STACK::cwd(), cwd("/a"));
+    }
+
+    #[test]
+    fn test_panels() {
+        let nav = |p: &str| FsPane::Nav {
+            cwd: AbsPath::new_unchecked(Path::new(p)),
+            sort: Default::default(),
+            vis: Default::default(),
+            depth: 1,
+            toggled: Default::default(),
+            input: (String::new(), 0),
+            complete: Default::default(),
+        };
+        let cwd = |p: &str| Some(AbsPath::new_unchecked(Path::new(p)));
+
+        STACK::init(nav("/a"));
+        assert_eq!(STACK::swap_panel(vec![]), None);
+        assert_eq!(STACK::panel_cwd(), None);
+
+        STACK::open_panel(nav("/b"));
+        assert_eq!(STACK::panel_cwd(), cwd("/b"));
+        STACK::set_panel_selections(vec![1]);
+        assert_eq!(STACK::panel_selections(), vec![1]);
+        assert_eq!(STACK::swap_panel(vec![2]), Some(vec![1]));
+        assert_eq!(STACK::cwd(), cwd("/b"));
+        assert_eq!(STACK::panel_cwd(), cwd("/a"));
+        assert_eq!(STACK::with_panel(|_, right| right), Some(true));
+
+        // the panels belong to their tab
+        STACK::new_tab(nav("/c"), vec![]);
+        assert_eq!(STACK::panel_cwd(), None);
+        STACK::goto_tab(0, vec![]);
+        assert_eq!(STACK::panel_cwd(), cwd("/a"));
+
+        assert_eq!(STACK::swap_panel(vec![]), Some(vec![2]));
+        assert!(STACK::close_panel());
+        assert_eq!(STACK::cwd(), cwd("/a"));
+        assert!(!STACK::close_panel());
+    }
 }
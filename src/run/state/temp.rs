@@ -1,9 +1,10 @@
 #![allow(clippy::upper_case_acronyms)]
 #![allow(non_snake_case)]
-use std::{cell::RefCell, fmt::Debug, sync::OnceLock};
+use std::{cell::RefCell, collections::HashSet, fmt::Debug, sync::OnceLock};
 
 use anymap::AnyMap;
 use cba::{_dbg, define_collection_wrapper};
+use indexmap::IndexSet;
 
 use crate::{
     abspath::AbsPath, cli::paths::lessfilter_cfg_path, config::pager_cfg,
@@ -68,6 +69,38 @@ pub struct InitialPreserveWhitespaceInSearch;
 #[derive(Debug, Clone)]
 pub struct InPrompt;
 
+/// The results position of the cursor as of the last cursor change, the anchor of a shift-click
+/// (see [`crate::run::mouse`]).
+#[derive(Debug, Clone)]
+pub struct LastCursor(pub u32);
+
+/// The results position of the last left click on an item, the anchor of a drag selection, and
+/// the selection the drag extends.
+#[derive(Debug, Clone)]
+pub struct DragAnchor {
+    pub anchor: u32,
+    pub base: Vec<u32>,
+}
+
+/// The directory left by the last change of directory (see
+/// [`crate::run::ahandlers::enter_dir_pane`]), the target of the mark `'` (see
+/// [`crate::run::marks`]).
+#[derive(Debug, Clone)]
+pub struct PreviousDir(pub AbsPath);
+
+/// The visual selection mode (see [`crate::run::selection::toggle_visual`]): the results position
+/// it was entered at, and the selection it extends.
+#[derive(Debug, Clone, Default)]
+pub struct VisualAnchor {
+    pub anchor: u32,
+    /// The selection the mode was entered with, and the items toggled on since, in order.
+    pub base: IndexSet<u32>,
+    /// The items toggled off since, which the range leaves out.
+    pub excluded: HashSet<u32>,
+    /// The selection last set by the mode, to tell the toggles made since.
+    pub applied: HashSet<u32>,
+}
+
 /// Set by the action aliaser when an accept keypress resolves to the *print*
 /// flavor (`alt_accept` XOR alt-enter, outside app panes); consumed by the
 /// [`matchmaker::Matchmaker`] accept hook, which then emits the selection and
@@ -1,24 +1,31 @@
 use std::collections::HashMap;
 
-use cba::define_collection_wrapper;
+use cba::{bath::PathExt, define_collection_wrapper};
 
 use crate::run::state::GLOBAL::db;
 use crate::{
     abspath::AbsPath,
+    cli::paths::__home,
+    db::{CommandKind, CommandRecord},
     menu::{MenuActions, MenuEvaluationContext, MenuStrategy},
     run::{
         FsPane,
         action::FsAction,
         ahandlers::fs_reload,
         item::{PathItem, short_display},
+        marks,
         queue::QUEUE,
+        replace::ReplacePreview,
+        stash::StashOp,
         state::{
-            GLOBAL, MenuCommandPaths, MenuPrompt, STACK, STORE, TOAST, ToastStyle, lessfilter_cfg,
+            COMMAND_HISTORY, GLOBAL, MARK_LISTING, MenuCommandPaths, MenuPrompt, STACK, STORE, TOAST,
+            ToastStyle, lessfilter_cfg,
         },
     },
     spawn::open_wrapped,
     ui::{
         OVERLAY_TICK_RATE,
+        confirm_overlay::ConfirmPrompt,
         prompt_overlay::{PromptConfig, PromptOverlay},
     },
     utils::serde::border_result,
@@ -113,6 +120,44 @@ pub enum MenuItem {
         action: String,
         alias: Option<String>,
     },
+    /// Stash pane only: combine with another stash.
+    Stash(StashOp),
+    /// Stash pane only: move all entries to another stash.
+    MoveStash,
+    /// Stash pane only: remove all entries, after confirmation.
+    ClearStash,
+    /// Stash pane only: write the entries to a file or the clipboard.
+    ExportStash,
+    /// Stash pane only: edit the note of the current entry.
+    Annotate,
+    /// Find and Search panes only: save the pane as a named search.
+    SaveSearch,
+    /// Search pane only: preview replacing the matches.
+    Replace,
+    /// Search pane only, while previewing: replace the selected matches.
+    ApplyReplace,
+    /// Search pane only, while previewing: end the preview.
+    CancelReplace,
+    /// Search pane only: restore the files of the latest replacement.
+    UndoOperation,
+    /// Select the items matching a glob.
+    SelectMatching,
+    /// Select the files of a category.
+    SelectCategory,
+    /// Select the files with the extension of the current item.
+    SelectSameExtension,
+    /// Swap the selected and unselected items.
+    InvertSelection,
+    /// Toggle the visual selection mode.
+    Visual,
+    /// A command from the history, re-run on the current item(s).
+    Command {
+        name: String,
+        template: String,
+        kind: CommandKind,
+    },
+    /// A directory mark, set or jumped to (see [`crate::run::marks`]).
+    Mark { name: String, mark: char },
 }
 
 impl MenuItem {
@@ -129,7 +174,24 @@ impl MenuItem {
             MenuItem::Delete => "delete",
             MenuItem::Open => "open",
             MenuItem::OpenWith => "open with",
-            MenuItem::Custom { name, .. } => name,
+            MenuItem::Stash(op) => op.label(),
+            MenuItem::MoveStash => "move all to",
+            MenuItem::ClearStash => "clear stash",
+            MenuItem::ExportStash => "export stash",
+            MenuItem::Annotate => "note",
+            MenuItem::SaveSearch => "save search",
+            MenuItem::Replace => "replace",
+            MenuItem::ApplyReplace => "apply replace",
+            MenuItem::CancelReplace => "cancel replace",
+            MenuItem::UndoOperation => "undo replace",
+            MenuItem::SelectMatching => "select matching",
+            MenuItem::SelectCategory => "select category",
+            MenuItem::SelectSameExtension => "select same extension",
+            MenuItem::InvertSelection => "invert selection",
+            MenuItem::Visual => "visual select",
+            MenuItem::Custom { name, .. }
+            | MenuItem::Command { name, .. }
+            | MenuItem::Mark { name, .. } => name,
         }
     }
 
@@ -146,7 +208,23 @@ impl MenuItem {
             MenuItem::OpenWith => Some("W"),
             MenuItem::Symlink => None,
             MenuItem::Goto => None,
+            MenuItem::Stash(_)
+            | MenuItem::MoveStash
+            | MenuItem::ClearStash
+            | MenuItem::ExportStash
+            | MenuItem::Annotate
+            | MenuItem::SaveSearch
+            | MenuItem::Replace
+            | MenuItem::ApplyReplace
+            | MenuItem::CancelReplace
+            | MenuItem::UndoOperation
+            | MenuItem::SelectMatching
+            | MenuItem::SelectCategory
+            | MenuItem::SelectSameExtension
+            | MenuItem::InvertSelection
+            | MenuItem::Visual => None,
             MenuItem::Custom { alias, .. } => alias.as_deref(),
+            MenuItem::Command { .. } | MenuItem::Mark { .. } => None,
         }
     }
 
@@ -205,12 +283,74 @@ impl MenuItem {
                 });
                 Err(false)
             }
+            MenuItem::Stash(op) => {
+                let title = format!("{} (stash[,into])", op.label());
+                Ok(MenuPrompt::new(PromptKind::Stash(*op)).title(title))
+            }
+            MenuItem::MoveStash => Ok(MenuPrompt::new(PromptKind::MoveStash)),
+            MenuItem::ExportStash => Ok(MenuPrompt::new(PromptKind::ExportStash)),
+            MenuItem::SaveSearch => Ok(MenuPrompt::new(PromptKind::SaveSearch)),
+            MenuItem::Replace => Ok(replace_prompt()),
+            MenuItem::ApplyReplace => {
+                GLOBAL::send_action(FsAction::ApplyReplace);
+                Err(false)
+            }
+            MenuItem::CancelReplace => {
+                GLOBAL::send_action(FsAction::CancelReplace);
+                Err(false)
+            }
+            MenuItem::UndoOperation => {
+                GLOBAL::send_action(FsAction::UndoOperation);
+                Err(false)
+            }
+            MenuItem::SelectMatching => Ok(MenuPrompt::new(PromptKind::SelectMatching)),
+            MenuItem::SelectCategory => Ok(MenuPrompt::new(PromptKind::SelectCategory)),
+            MenuItem::SelectSameExtension => {
+                GLOBAL::send_action(FsAction::SelectSameExtension);
+                Err(false)
+            }
+            MenuItem::InvertSelection => {
+                GLOBAL::send_action(FsAction::InvertSelection);
+                Err(false)
+            }
+            MenuItem::Visual => {
+                GLOBAL::send_action(FsAction::Visual);
+                Err(false)
+            }
+            // reopens the input bar prefilled with the current note
+            MenuItem::Annotate => {
+                GLOBAL::send_action(FsAction::AnnotateStashEntry);
+                Err(false)
+            }
+            MenuItem::ClearStash => {
+                let Some(name) = STACK::with_current(|p| match p {
+                    FsPane::Stash { stash_name, .. } => Some(stash_name.clone()),
+                    _ => None,
+                }) else {
+                    return Err(false);
+                };
+                STORE::set(ConfirmPrompt {
+                    prompt: Line::from("Clear the stash?"),
+                    option_handler: Box::new(move |idx| {
+                        if idx == 0 {
+                            GLOBAL::send_action(FsAction::ClearStash(name));
+                        }
+                    }),
+                    cursor: 1, // Default to No
+                    ..Default::default()
+                });
+                GLOBAL::send_action(FsAction::Confirm);
+                Err(false)
+            }
             MenuItem::OpenWith => {
                 unreachable!("OpenWith is handled in MenuOverlay::execute")
             }
             MenuItem::Custom { action, .. } => {
                 unreachable!("custom items are routed through MenuOverlay::run_custom")
             }
+            MenuItem::Command { .. } | MenuItem::Mark { .. } => {
+                unreachable!("commands and marks are handled in MenuOverlay::execute")
+            }
         }
     }
 }
@@ -288,6 +428,17 @@ impl ColumnIndexable for MenuEntry {
     }
 }
 
+/// What the menu overlay lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Listing {
+    Actions,
+    /// The command history ([`FsAction::CommandHistory`]).
+    Commands,
+    /// The directory marks ([`FsAction::SetMark`], [`FsAction::JumpMark`]): the next key is
+    /// taken as the mark to set (`set`) or jump to.
+    Marks { set: bool },
+}
+
 pub const MENU_ITEMS: [MenuItem; 10] = [
     MenuItem::New,
     MenuItem::Rename,
@@ -301,6 +452,15 @@ pub const MENU_ITEMS: [MenuItem; 10] = [
     MenuItem::Goto,
 ];
 
+/// The selection items, listed after [`MENU_ITEMS`].
+pub const SELECTION_ITEMS: [MenuItem; 5] = [
+    MenuItem::SelectMatching,
+    MenuItem::SelectCategory,
+    MenuItem::SelectSameExtension,
+    MenuItem::InvertSelection,
+    MenuItem::Visual,
+];
+
 /// The menu overlay: a two-column picker of the available actions
 /// (name, alias), with a query line, backed by its own nucleo worker.
 pub struct MenuOverlay {
@@ -324,6 +484,8 @@ pub struct MenuOverlay {
     pub prompt: PromptOverlay,
     /// The custom actions; only those whose conditions pass are listed.
     pub actions: MenuActions,
+    /// What the menu lists.
+    listing: Listing,
 
     // required to update table
     selector: Selector,
@@ -354,6 +516,7 @@ impl MenuOverlay {
             prompt_kind: None,
             prompt: PromptOverlay::new(prompt_config),
             actions,
+            listing: Listing::Actions,
             selector: Selector::new(),
         }
     }
@@ -416,6 +579,24 @@ impl MenuOverlay {
             return self.run_custom(&key, state);
         }
 
+        if let MenuItem::Mark { mark, .. } = item {
+            self.run_mark(mark);
+            return OverlayEffect::Disable;
+        }
+
+        // re-run a recorded command the way it was run; menu actions go
+        // through their configured strategy again
+        if let MenuItem::Command { template, kind, .. } = item {
+            let action = match kind {
+                CommandKind::Execute => FsAction::Execute(template),
+                CommandKind::Paged => FsAction::ExecPaged(template),
+                CommandKind::Tty => FsAction::ExecTTY(template),
+                CommandKind::Menu => return self.run_custom(&template, state),
+            };
+            GLOBAL::send_action(action);
+            return OverlayEffect::Disable;
+        }
+
         // OpenWith creates the app pane right there: the selected items
         // (or the cursor item when nothing is selected) preload it, then
         // the pane reloads — the same bookkeeping as FsAction::App, but
@@ -465,8 +646,26 @@ impl MenuOverlay {
         let mut items = if STACK::in_app() {
             Vec::new()
         } else {
-            MENU_ITEMS.to_vec()
+            [MENU_ITEMS.as_slice(), SELECTION_ITEMS.as_slice()].concat()
         };
+        if STACK::with_current(|p| matches!(p, FsPane::Stash { .. })) {
+            items.extend(StashOp::ALL.map(MenuItem::Stash));
+            items.extend([
+                MenuItem::MoveStash,
+                MenuItem::ClearStash,
+                MenuItem::ExportStash,
+                MenuItem::Annotate,
+            ]);
+        }
+        if STACK::with_current(|p| matches!(p, FsPane::Find { .. } | FsPane::Search { .. })) {
+            items.push(MenuItem::SaveSearch);
+        }
+        if STACK::with_current(|p| matches!(p, FsPane::Search { .. })) {
+            if STORE::contains::<ReplacePreview>() {
+                items.extend([MenuItem::ApplyReplace, MenuItem::CancelReplace]);
+            }
+            items.extend([MenuItem::Replace, MenuItem::UndoOperation]);
+        }
         for (key, action) in self.actions.iter() {
             if ctx.is_applicable(action) {
                 items.push(MenuItem::Custom {
@@ -499,6 +698,52 @@ impl MenuOverlay {
             .collect();
     }
 
+    /// Rebuild the item list from the command history, most recent first.
+    /// Commands carry no aliases: the query only filters.
+    fn build_command_items(&mut self, history: Vec<CommandRecord>) {
+        self.menu_items = history
+            .into_iter()
+            .filter_map(|record| {
+                let kind = record.kind()?;
+                let name = match kind {
+                    CommandKind::Execute => record.template.clone(),
+                    _ => format!("{} ({kind})", record.template),
+                };
+                let item = MenuItem::Command {
+                    name,
+                    template: record.template,
+                    kind,
+                };
+                Some(MenuEntry { item, alias: None })
+            })
+            .collect();
+        self.aliases = AliasSet::new();
+    }
+
+    /// Rebuild the item list from the marks. Each mark is its own hotkey,
+    /// though any key is taken as a mark (see [`Self::handle_input`]).
+    fn build_mark_items(&mut self, marks: Vec<(char, AbsPath)>) {
+        self.menu_items = marks
+            .into_iter()
+            .map(|(mark, path)| MenuEntry {
+                item: MenuItem::Mark {
+                    name: format!("{mark}  {}", path.display_short(__home())),
+                    mark,
+                },
+                alias: Some(mark.to_string()),
+            })
+            .collect();
+        self.aliases = AliasSet::new();
+    }
+
+    /// Set or jump to `mark`, as the marks were listed for.
+    fn run_mark(&self, mark: char) {
+        match self.listing {
+            Listing::Marks { set: true } => marks::set(mark),
+            _ => marks::jump(mark),
+        }
+    }
+
     /// Run a custom action on the target items (the current selection, or
     /// the target item when nothing is selected).
     fn run_custom(&mut self, key: &str, state: &mut MMState<'_, PathItem, ()>) -> OverlayEffect {
@@ -557,9 +802,19 @@ impl Overlay<FsAction, PathItem, ()> for MenuOverlay {
             self.set_prompt(prompt, state);
         }
 
-        // list the custom actions whose conditions pass (evaluated once,
+        // list the command history or the marks when they were just fetched,
+        // otherwise the custom actions whose conditions pass (evaluated once,
         // against the state at open)
-        self.build_items(state);
+        if let Some(history) = COMMAND_HISTORY.lock().unwrap().take() {
+            self.listing = Listing::Commands;
+            self.build_command_items(history);
+        } else if let Some(listing) = MARK_LISTING.lock().unwrap().take() {
+            self.listing = Listing::Marks { set: listing.set };
+            self.build_mark_items(listing.marks);
+        } else {
+            self.listing = Listing::Actions;
+            self.build_items(state);
+        }
         self.build_worker();
         self.query_dirty = true;
         self.results.set_dirty();
@@ -575,6 +830,11 @@ impl Overlay<FsAction, PathItem, ()> for MenuOverlay {
     }
 
     fn handle_input(&mut self, c: char, state: &mut MMState<'_, PathItem, ()>) -> OverlayEffect {
+        // the key following the marks prefix is the mark
+        if let Listing::Marks { .. } = self.listing {
+            self.run_mark(c);
+            return OverlayEffect::Disable;
+        }
         if let Some(p) = self.prompt_kind {
             if let OverlayEffect::Disable = self.prompt.handle_input(c, state) {
                 self.on_prompt_accept(p, state)
@@ -752,8 +1012,14 @@ impl Overlay<FsAction, PathItem, ()> for MenuOverlay {
         // no actions available (e.g. the app pane lists custom actions
         // only, and none matched): a compact box with the message only
         if self.menu_items.is_empty() {
+            let message = match self.listing {
+                Listing::Actions => "No available\nactions",
+                Listing::Commands => "No recorded\ncommands",
+                Listing::Marks { set: true } => "Press a key\nto mark",
+                Listing::Marks { set: false } => "No marks",
+            };
             frame.render_widget(
-                Paragraph::new(Text::from("No available\nactions")).alignment(Alignment::Center),
+                Paragraph::new(Text::from(message)).alignment(Alignment::Center),
                 inner,
             );
             return;
@@ -823,6 +1089,7 @@ mod tests {
         let pool = Pool {
             pool: sqlx::SqlitePool::connect_lazy("sqlite::memory:").unwrap(),
             lambda: None,
+            visits_max: 0,
         };
         let pane = FsPane::Nav {
             cwd: AbsPath::new("/tmp"),
@@ -831,6 +1098,7 @@ mod tests {
             input: (String::new(), 0),
             complete: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
             depth: 0,
+            toggled: Default::default(),
         };
 
         GLOBAL::init(
@@ -1001,4 +1269,26 @@ mod tests {
         };
         assert!(matches!(effect, OverlayEffect::None));
     }
+
+    #[test]
+    fn command_items() {
+        let mut overlay = MenuOverlay::new(
+            full_config(),
+            crate::ui::prompt_overlay::PromptConfig::default(),
+            crate::menu::MenuActions::default(),
+        );
+        let record = |template: &str, kind| CommandRecord::new(template.into(), String::new(), kind);
+        let mut unknown = record("ls {}", CommandKind::Execute);
+        unknown.kind = "removed".into();
+        overlay.build_command_items(vec![
+            record("wc -l {}", CommandKind::Execute),
+            record("bat {}", CommandKind::Paged),
+            unknown,
+        ]);
+
+        let labels: Vec<_> = overlay.menu_items.iter().map(MenuEntry::label).collect();
+        assert_eq!(labels, ["wc -l {}", "bat {} (paged)"]);
+        assert!(overlay.menu_items.iter().all(|e| e.alias.is_none()));
+        assert!(overlay.aliases.is_empty());
+    }
 }
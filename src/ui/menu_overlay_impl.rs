@@ -1,17 +1,19 @@
 use crate::run::state::GLOBAL::db;
 use crate::{
     abspath::AbsPath,
-    config::StashPaneKind,
     db::DbTable,
     fs::{auto_dest, create_all, rename},
     run::{
         FsAction,
         item::{PathItem, short_display},
-        state::{GLOBAL, MenuPrompt, STACK, TASKS, TOAST, ToastStyle},
+        replace::{self, ReplacePreview},
+        stash::{self, StashOp, StashOperands},
+        state::{FILTERS, GLOBAL, MenuPrompt, STACK, STORE, TASKS, TOAST, ToastStyle},
     },
 };
 
 use cba::bath::{PathExt, RenamePolicy, auto_dest_for_src};
+use fist_types::{categories::FileCategory, predicates::PredicateKind};
 use matchmaker::{
     nucleo::{Color, Span},
     render::MMState,
@@ -31,6 +33,28 @@ pub enum PromptKind {
     Goto,
     #[strum(serialize = "Set alias")]
     SetAlias,
+    /// Edit the note (tail) of the current stash entry.
+    #[strum(serialize = "Note")]
+    Annotate,
+    /// Combine the current stash with the input stash (`name[,into]`).
+    #[strum(serialize = "Combine with")]
+    Stash(StashOp),
+    #[strum(serialize = "Move all to")]
+    MoveStash,
+    #[strum(serialize = "Export to (empty: clipboard)")]
+    ExportStash,
+    #[strum(serialize = "Save search as")]
+    SaveSearch,
+    /// The replacement previewed in the Search pane (empty: delete the matches).
+    #[strum(serialize = "Replace with")]
+    Replace,
+    /// A visibility predicate, edited from the options overlay (empty: clear it).
+    #[strum(serialize = "Filter")]
+    Predicate(PredicateKind),
+    #[strum(serialize = "Select matching (glob)")]
+    SelectMatching,
+    #[strum(serialize = "Select category")]
+    SelectCategory,
 }
 
 /// The rename prompt for `path`: the input is prepopulated with the full path
@@ -50,6 +74,12 @@ pub fn rename_prompt_for(path: &AbsPath) -> MenuPrompt {
     }
 }
 
+/// The replace prompt, prepopulated with the replacement being previewed.
+pub fn replace_prompt() -> MenuPrompt {
+    let initial = STORE::with(|p: &ReplacePreview| p.0.replacement.clone()).unwrap_or_default();
+    MenuPrompt::new(PromptKind::Replace).initial(initial)
+}
+
 /// Whether `path` is the process's current working directory. The logical
 /// paths are compared first, falling back to canonical comparison in case the
 /// cwd was reached through a symlink. Must be called while `path` still
@@ -243,40 +273,8 @@ impl MenuOverlay {
                 let alias = self.prompt.input.value();
 
                 // in a stash pane this edits the entry's tail column instead
-                let stash_name = STACK::with_current(|p| match p {
-                    crate::run::FsPane::Stash { stash_name, .. } => Some(stash_name.clone()),
-                    _ => None,
-                });
-
-                if let Some(name) = stash_name {
-                    let kind = GLOBAL::cfg()
-                        .panes
-                        .stashes
-                        .get(&name)
-                        .map(|s| s.kind)
-                        .unwrap_or_default();
-                    let tail = alias.clone();
-                    let path = path.clone();
-
-                    if kind == StashPaneKind::Transient {
-                        // in-memory stash: nothing async needed
-                        crate::run::stash::mem_set_tail(&name, &path, &tail);
-                        GLOBAL::send_action(FsAction::Reload);
-                    } else {
-                        TASKS::spawn("set alias", async move {
-                            match db().get_conn(DbTable::stashes).await {
-                                Ok(mut conn) => {
-                                    if let Err(e) = conn.set_stash_tail(&name, &path, &tail).await {
-                                        log::error!("Error setting alias: {e}");
-                                    }
-                                }
-                                Err(e) => {
-                                    log::error!("Error getting connection: {e}");
-                                }
-                            }
-                            GLOBAL::send_action(FsAction::Reload);
-                        });
-                    }
+                if let Some(name) = current_stash() {
+                    set_stash_note(name, path.clone(), alias.clone());
                 } else {
                     let table = if path.is_dir() {
                         DbTable::dirs
@@ -297,6 +295,80 @@ impl MenuOverlay {
                     TOAST::push(ToastStyle::Success, "Alias set: ", [short_display(&path)]);
                 }
             }
+            PromptKind::Annotate => {
+                let Some(name) = current_stash() else {
+                    return OverlayEffect::Disable;
+                };
+                let path = self.target_path(state);
+                let note = self.prompt.input.value().trim().to_string();
+
+                if note.is_empty() {
+                    TOAST::push(ToastStyle::Normal, "Note cleared: ", [short_display(&path)]);
+                } else {
+                    TOAST::push(ToastStyle::Success, "Note set: ", [short_display(&path)]);
+                }
+                set_stash_note(name, path, note);
+            }
+            PromptKind::SaveSearch => {
+                let name = self.prompt.input.value().trim().to_string();
+                if !name.is_empty() {
+                    GLOBAL::send_action(FsAction::SaveSearch(name));
+                }
+            }
+            PromptKind::Replace => {
+                replace::set_preview(state, self.prompt.input.value());
+            }
+            PromptKind::Predicate(kind) => {
+                let input = self.prompt.input.value();
+                if let Err(e) = FILTERS::with_mut(|vis| kind.set(&mut vis.predicates, &input)) {
+                    TOAST::notice(ToastStyle::Error, e);
+                    return OverlayEffect::None;
+                }
+                GLOBAL::send_action(FsAction::Refilter);
+            }
+            PromptKind::SelectMatching => {
+                let pattern = self.prompt.input.value();
+                if !pattern.is_empty() {
+                    GLOBAL::send_action(FsAction::SelectMatching(pattern));
+                }
+            }
+            PromptKind::SelectCategory => {
+                let input = self.prompt.input.value();
+                match input.trim().parse::<FileCategory>() {
+                    Ok(category) => GLOBAL::send_action(FsAction::SelectCategory(category)),
+                    Err(_) => {
+                        TOAST::push(ToastStyle::Error, "Unknown category: ", [Span::raw(input)]);
+                        return OverlayEffect::None;
+                    }
+                }
+            }
+            PromptKind::ExportStash => {
+                let dest = self.prompt.input.value();
+                GLOBAL::send_action(FsAction::ExportStash(dest.trim().into()));
+            }
+            PromptKind::Stash(_) | PromptKind::MoveStash => {
+                let Some(current) = STACK::with_current(|p| match p {
+                    crate::run::FsPane::Stash { stash_name, .. } => Some(stash_name.clone()),
+                    _ => None,
+                }) else {
+                    return OverlayEffect::Disable;
+                };
+                // the input names the other stash, optionally followed by the
+                // stash receiving the result
+                let input = self.prompt.input.value();
+                let operands = match format!("{current},{input}").parse::<StashOperands>() {
+                    Ok(o) => o,
+                    Err(e) => {
+                        TOAST::notice(ToastStyle::Error, e);
+                        return OverlayEffect::None;
+                    }
+                };
+                let action = match prompt {
+                    PromptKind::Stash(op) => op.action(operands),
+                    _ => FsAction::MoveStash(operands),
+                };
+                GLOBAL::send_action(action);
+            }
         }
 
         self.prompt_kind = None;
@@ -304,3 +376,21 @@ impl MenuOverlay {
         OverlayEffect::Disable
     }
 }
+
+/// The name of the current stash pane, if any.
+fn current_stash() -> Option<String> {
+    STACK::with_current(|p| match p {
+        crate::run::FsPane::Stash { stash_name, .. } => Some(stash_name.clone()),
+        _ => None,
+    })
+}
+
+/// Store `note` as the tail of the stash entry for `path`, then reload.
+fn set_stash_note(name: String, path: AbsPath, note: String) {
+    TASKS::spawn("set stash note", async move {
+        if let Err(e) = stash::set_tail(&name, &path, &note).await {
+            log::error!("Error setting stash note: {e}");
+        }
+        GLOBAL::send_action(FsAction::Reload);
+    });
+}
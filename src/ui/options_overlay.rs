@@ -1,18 +1,22 @@
 use crate::{
+    db::RangePreset,
     run::{
         action::FsAction,
         item::PathItem,
-        state::{FILTERS, GLOBAL, STACK},
+        meta::{self, MetaColumn},
+        state::{MenuPrompt, FILTERS, GLOBAL, STACK, STORE},
         FsPane,
     },
+    ui::menu_overlay::PromptKind,
     utils::{serde::border_result, text::bold_indices},
 };
 
 use cba::bum::UsizeExt;
-use fist_types::{filters::*, When};
+use fist_types::{filters::*, git::GitFilter, predicates::PredicateKind, When};
 use matchmaker::{
     action::Action,
     config::{BorderSetting, OverlayLayoutSettings, PartialBorderSetting},
+    message::RenderCommand,
     render::MMState,
     ui::{utils, Overlay, OverlayEffect},
 };
@@ -154,7 +158,7 @@ impl OptionsOverlay {
                     self.cursor[0].wsub(1, num_panes);
                 }
             }
-            Action::Accept => self.toggle_selected_option(),
+            Action::Accept => return self.toggle_selected_option(),
             Action::Quit(_) => return OverlayEffect::Disable,
             _ => {}
         }
@@ -279,6 +283,11 @@ impl OptionsOverlay {
                 (bold_indices("Dirs", [0], self.item_style()), Some(vis.dirs))
             }
         };
+        let mut git_label = bold_indices("git", [0], self.item_style());
+        if let Some(filter) = vis.git {
+            git_label.push(Span::styled(format!(" {filter}"), self.item_style()));
+        }
+
         let ret = vec![
             (hidden_label, Some(vis.hidden || vis.hidden_only)),
             (
@@ -287,9 +296,35 @@ impl OptionsOverlay {
             ),
             dirs_label,
             (bold_indices("all", [0], self.item_style()), Some(vis.all())),
+            (git_label, Some(vis.git.is_some())),
         ];
 
-        ret
+        // the predicates show their value, and are edited in a prompt
+        let predicates = PredicateKind::ALL.map(|kind| {
+            let value = kind.value(&vis.predicates);
+            let label = match &value {
+                Some(v) => format!("{} {v}", kind.label()),
+                None => kind.label().to_string(),
+            };
+            (vec![Span::styled(label, self.item_style())], Some(value.is_some()))
+        });
+
+        ret.into_iter().chain(predicates).collect()
+    }
+
+    /// Open a prompt editing a predicate, prepopulated with its value. An empty
+    /// input clears it.
+    fn edit_predicate(kind: PredicateKind) -> OverlayEffect {
+        let value = kind
+            .value(&FILTERS::visibility().predicates)
+            .unwrap_or_default();
+        STORE::set_menu_prompt(Some(
+            MenuPrompt::new(PromptKind::Predicate(kind))
+                .title(kind.prompt())
+                .initial(value),
+        ));
+        GLOBAL::send_action(Action::Overlay(4));
+        OverlayEffect::Disable
     }
 
     // Returns Vec<Span> for sort options
@@ -338,19 +373,63 @@ impl OptionsOverlay {
                     (regex, Some(!*fixed_strings)),
                 ]
             }
-            // FsPane::Fd { .. } => {
-
-            // }
+            // visit date range: the uppercased first letter is the key
+            FsPane::Files { range, .. } | FsPane::Folders { range, .. } => RangePreset::ALL
+                .iter()
+                .map(|preset| {
+                    (
+                        bold_indices(preset.label(), [0], self.item_style()),
+                        Some(preset.range() == *range),
+                    )
+                })
+                .collect(),
+            // metadata columns: the uppercased highlighted letter is the key
+            FsPane::Nav { .. } | FsPane::Find { .. } => MetaColumn::ALL
+                .iter()
+                .map(|column| {
+                    let (label, key) = column.label();
+                    (
+                        bold_indices(label, [key], self.item_style()),
+                        Some(meta::enabled(*column)),
+                    )
+                })
+                .collect(),
             _ => vec![],
         })
     }
 
+    /// Filter the history pane by a visit date range. Returns whether the range changed.
+    fn set_range(preset: RangePreset) -> bool {
+        STACK::with_current_mut(|p| match p {
+            FsPane::Files { range, .. } | FsPane::Folders { range, .. } => {
+                let new = preset.range();
+                let changed = new != *range;
+                *range = new;
+                changed
+            }
+            _ => false,
+        })
+    }
+
+    /// Show or hide a metadata column of the current pane.
+    fn toggle_column(column: MetaColumn) {
+        if !meta::toggle(column) {
+            return;
+        }
+        // recompute the column widths
+        GLOBAL::send_mm(RenderCommand::Redraw);
+        // the statuses are only computed while populating
+        if column == MetaColumn::Git && meta::enabled(column) {
+            GLOBAL::send_action(FsAction::Reload);
+        }
+    }
+
     fn on_inactive_y(&self) -> bool {
         let [x, y] = self.cursor;
         assert!(self.pane_lens[x] != 0);
 
         match x {
-            2 => matches!(y, 0 | 3),
+            2 => STACK::in_rg() && matches!(y, 0 | 3),
             // the sort pane only lists orders the current pane supports,
             // so no row is inactive there
             _ => false,
@@ -434,17 +513,24 @@ impl OptionsOverlay {
     // --------------------------------------------------------------
 
     /// Handler for cursor selection
-    fn toggle_selected_option(&mut self) {
+    fn toggle_selected_option(&mut self) -> OverlayEffect {
         let [x, y] = self.cursor;
         let mut refilter = false;
         let mut reload = false;
 
         match x {
+            // predicate rows, below the visibility toggles
+            0 if y >= 5 => {
+                if let Some(kind) = PredicateKind::ALL.get(y - 5) {
+                    return Self::edit_predicate(*kind);
+                }
+            }
+
             // visibility pane
             0 => {
                 refilter = true;
                 FILTERS::with_mut(|vis| {
-                    if !matches!(y, 2 | 3) {
+                    if !matches!(y, 2..=4) {
                         vis.set_all(false);
                     }
                     match y {
@@ -470,6 +556,7 @@ impl OptionsOverlay {
                             }
                         }
                         3 => vis.toggle_all(),
+                        4 => vis.git = GitFilter::cycle(vis.git),
                         _ => {}
                     }
                 });
@@ -492,6 +579,18 @@ impl OptionsOverlay {
                 });
             }
 
+            2 if meta::supported() => {
+                if let Some(column) = MetaColumn::ALL.get(y) {
+                    Self::toggle_column(*column);
+                }
+            }
+
+            2 if !STACK::in_rg() => {
+                reload = RangePreset::ALL
+                    .get(y)
+                    .is_some_and(|preset| Self::set_range(*preset));
+            }
+
             2 => {
                 reload = true;
                 STACK::with_current_mut(|p| match p {
@@ -529,6 +628,7 @@ impl OptionsOverlay {
         if reload {
             GLOBAL::send_action(FsAction::Reload);
         }
+        OverlayEffect::None
     }
 }
 
@@ -548,7 +648,9 @@ impl Overlay<FsAction, PathItem, ()> for OptionsOverlay {
             // the sort and visibility panes, and 'c' (a sort key on db
             // panes) and 'd'/'D' (dirs/files visibility keys) collide
             // with those panes' keys, so they must be dispatched first.
-            'b' | 'B' | 'd' | 'D' | 'c' | 'C' | 'e' | '1' | 'r' if self.pane_lens[2] > 0 => {
+            'b' | 'B' | 'd' | 'D' | 'c' | 'C' | 'e' | '1' | 'r'
+                if self.pane_lens[2] > 0 && STACK::in_rg() =>
+            {
                 reload = true;
 
                 STACK::with_current_mut(|p| match p {
@@ -583,6 +685,23 @@ impl Overlay<FsAction, PathItem, ()> for OptionsOverlay {
                 });
             }
 
+            // metadata column keys, uppercase like the date range keys
+            'S' | 'M' | 'P' | 'O' | 'G' | 'C' if meta::supported() => {
+                if let Some(column) = MetaColumn::ALL.into_iter().find(|col| col.key() == c) {
+                    Self::toggle_column(column);
+                }
+            }
+
+            // history date range keys, uppercase to leave the sort keys free
+            'A' | 'T' | 'Y' | 'W' | 'M' if self.pane_lens[2] > 0 && !STACK::in_rg() => {
+                if let Some(preset) = RangePreset::ALL
+                    .into_iter()
+                    .find(|p| p.label().starts_with(c))
+                {
+                    reload = Self::set_range(preset);
+                }
+            }
+
             // time-sort cycle key (the highlighted 't'): any other sort ->
             // mtime, mtime -> atime, atime -> none, none -> mtime. Every
             // pane supports the full cycle.
@@ -656,10 +775,10 @@ impl Overlay<FsAction, PathItem, ()> for OptionsOverlay {
             }
 
             // visibility toggles
-            'h' | 'H' | 'I' | 'd' | 'D' | 'a' if self.pane_lens[0] > 0 => {
+            'h' | 'H' | 'I' | 'd' | 'D' | 'a' | 'g' if self.pane_lens[0] > 0 => {
                 refilter = FILTERS::with_mut(|vis| {
                     let before = *vis;
-                    if !matches!(c, 'D' | 'a') {
+                    if !matches!(c, 'D' | 'a' | 'g') {
                         vis.set_all(false);
                     }
                     match c {
@@ -682,6 +801,8 @@ impl Overlay<FsAction, PathItem, ()> for OptionsOverlay {
                             }
                         }
                         'I' => vis.ignore = !vis.ignore,
+                        // cycles through the status filters
+                        'g' => vis.git = GitFilter::cycle(vis.git),
                         _ => {}
                     }
                     *vis != before
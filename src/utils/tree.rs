@@ -1,6 +1,24 @@
 use std::fmt::Display;
 use std::io::{self, Write};
 
+/// Connector before an intermediate child.
+pub const BRANCH: &str = "├── ";
+/// Connector before the last child.
+pub const LAST_BRANCH: &str = "└── ";
+/// Continuation below an intermediate child.
+pub const PIPE: &str = "│   ";
+/// Continuation below the last child.
+pub const BLANK: &str = "    ";
+
+/// The connector before a node, and the prefix continuing below it.
+pub fn connectors(is_last: bool) -> (&'static str, &'static str) {
+    if is_last {
+        (LAST_BRANCH, BLANK)
+    } else {
+        (BRANCH, PIPE)
+    }
+}
+
 /// Trait for items that can be rendered in a tree hierarchy.
 pub trait TreeItem: Display {
     fn children(&self) -> &[Self]
@@ -87,9 +105,7 @@ where
     F: Fn(&T) -> String + Copy,
 {
     for (i, node) in nodes.iter().enumerate() {
-        let is_last = i == nodes.len() - 1;
-        let connector = if is_last { "└── " } else { "├── " };
-        let child_prefix = if is_last { "    " } else { "│   " };
+        let (connector, child_prefix) = connectors(i == nodes.len() - 1);
 
         writeln!(writer, "{prefix}{connector}{}", format_node(node))?;
         render_subtree(
@@ -1,4 +1,5 @@
-use crate::run::action::FsAction;
+use crate::run::{action::FsAction, meta};
+use fist_types::git;
 use matchmaker::{action::Action, event::RenderSender, message::RenderCommand};
 use notify::{
     Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher as NotifyWatcher,
@@ -78,7 +79,8 @@ impl Default for WatcherConfig {
 pub struct FsWatcher {
     path_rx: mpsc::UnboundedReceiver<WatcherMessage>,
     path_tx: mpsc::UnboundedSender<WatcherMessage>,
-    current_path: Option<PathBuf>,
+    /// The watched path, and whether the watch is recursive.
+    current_path: Option<(PathBuf, RecursiveMode)>,
     /// Set by `MustWatch`: thrash throttling is disabled while this dir is
     /// being watched.
     must_watch: Option<PathBuf>,
@@ -106,6 +108,7 @@ impl FsWatcher {
     // start watcher, returning a handle
     pub fn start_watcher(&self) -> Result<RecommendedWatcher, notify::Error> {
         let watcher_tx = self.path_tx.clone();
+        let render_tx = self.render_tx.clone();
         let notify_config = Config::default().with_poll_interval(self.config.fs_poll_ms);
 
         RecommendedWatcher::new(
@@ -116,8 +119,33 @@ impl FsWatcher {
                         | EventKind::Modify(ModifyKind::Name(_))
                         | EventKind::Remove(_) => {
                             log::debug!("WatcherEvent: {:?}", event.kind);
+                            git::invalidate(&event.paths);
                             let _ = watcher_tx.send(WatcherMessage::Reload);
                         }
+                        // content changes only matter to the metadata columns and the git
+                        // status markers, which are redrawn without a reload
+                        EventKind::Modify(ModifyKind::Data(_) | ModifyKind::Any) => {
+                            if meta::invalidate(&event.paths) {
+                                let _ = render_tx.send(RenderCommand::Redraw);
+                            }
+                            let roots = git::invalidate(&event.paths);
+                            if roots.is_empty() {
+                                return;
+                            }
+                            log::debug!("WatcherEvent: {:?}", event.kind);
+                            let render_tx = render_tx.clone();
+                            std::thread::spawn(move || {
+                                for root in &roots {
+                                    git::repo_status(root);
+                                }
+                                let _ = render_tx.send(RenderCommand::Redraw);
+                            });
+                        }
+                        EventKind::Modify(ModifyKind::Metadata(_)) => {
+                            if meta::invalidate(&event.paths) {
+                                let _ = render_tx.send(RenderCommand::Redraw);
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -180,14 +208,15 @@ impl FsWatcher {
                                 match &mut self.current_path {
                                     None => {
                                         let _ = watcher.watch(&new_path, recursive_mode);
-                                        self.current_path = Some(new_path.clone());
+                                        self.current_path = Some((new_path.clone(), recursive_mode));
                                         log::debug!("Watching: {:?}", new_path);
                                     }
-                                    Some(old_path) => {
-                                        if &new_path != old_path {
-                                            let _ = watcher.unwatch(old_path);
+                                    Some(old) => {
+                                        // a Nav pane switching to a tree rewatches recursively
+                                        if (&new_path, recursive_mode) != (&old.0, old.1) {
+                                            let _ = watcher.unwatch(&old.0);
                                             let _ = watcher.watch(&new_path, recursive_mode);
-                                            *old_path = new_path.clone();
+                                            *old = (new_path.clone(), recursive_mode);
                                             log::debug!("Watching: {:?}", new_path);
                                         }
                                     }
@@ -204,7 +233,7 @@ impl FsWatcher {
                                 }
                             }
                             WatcherMessage::Pause => {
-                                if let Some(old_path) = self.current_path.take() {
+                                if let Some((old_path, _)) = self.current_path.take() {
                                     let _ = watcher.unwatch(&old_path);
                                 }
                                 self.must_watch = None;